@@ -27,61 +27,69 @@ fn main() {
         return;
     }
 
-    // use php-config to fetch includes
-    let includes_cmd = Command::new("php-config")
-        .arg("--includes")
-        .output()
-        .expect("Unable to run `php-config`. Please ensure it is visible in your PATH.");
-
-    if !includes_cmd.status.success() {
-        let stderr = String::from_utf8(includes_cmd.stderr)
-            .unwrap_or_else(|_| String::from("Unable to read stderr"));
-        panic!("Error running `php-config`: {}", stderr);
-    }
+    let windows = env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows");
+    let info = PhpInfo::get();
 
     // Ensure the PHP API version is supported.
-    // We could easily use grep and sed here but eventually we want to support Windows,
-    // so it's easier to just use regex.
-    let php_i_cmd = Command::new("php")
-        .arg("-i")
-        .output()
-        .expect("Unable to run `php -i`. Please ensure it is visible in your PATH.");
-
-    if !php_i_cmd.status.success() {
-        let stderr = str::from_utf8(&includes_cmd.stderr).unwrap_or("Unable to read stderr");
-        panic!("Error running `php -i`: {}", stderr);
-    }
-
-    let api_ver = Regex::new(r"PHP API => ([0-9]+)")
-        .unwrap()
-        .captures_iter(
-            str::from_utf8(&php_i_cmd.stdout).expect("Unable to parse `php -i` stdout as UTF-8"),
-        )
-        .next()
-        .and_then(|ver| ver.get(1))
-        .and_then(|ver| ver.as_str().parse::<u32>().ok())
-        .expect("Unable to retrieve PHP API version from `php -i`.");
-
+    let api_ver = info.api_version();
     if api_ver < MIN_PHP_API_VER || api_ver > MAX_PHP_API_VER {
         panic!("The current version of PHP is not supported. Current PHP API version: {}, requires a version between {} and {}", api_ver, MIN_PHP_API_VER, MAX_PHP_API_VER);
     }
 
-    let includes =
-        String::from_utf8(includes_cmd.stdout).expect("unable to parse `php-config` stdout");
+    // `php-config` does not exist on Windows, so the headers are taken from the PHP development
+    // pack instead, and the ZTS and debug flags are read from `php -i`.
+    let (includes, defines, zts, debug) = if windows {
+        let dev_pack = windows_dev_pack();
+        let (zts, debug) = (info.zts(), info.debug());
 
-    // Build `wrapper.c` and link to Rust.
-    cc::Build::new()
-        .file("src/wrapper/wrapper.c")
-        .includes(
-            str::replace(includes.as_ref(), "-I", "")
-                .split(' ')
-                .map(|path| Path::new(path)),
+        println!(
+            "cargo:rustc-link-search=native={}",
+            dev_pack.join("lib").display()
+        );
+        println!(
+            "cargo:rustc-link-lib=dylib=php{}{}",
+            info.major_version(),
+            if zts { "ts" } else { "" }
+        );
+
+        let mut defines = vec![
+            ("ZEND_WIN32", "1"),
+            ("PHP_WIN32", "1"),
+            ("WIN32", "1"),
+            ("_WINDOWS", "1"),
+            ("ZEND_DEBUG", if debug { "1" } else { "0" }),
+        ];
+        if zts {
+            defines.push(("ZTS", "1"));
+        }
+
+        (windows_includes(&dev_pack), defines, zts, debug)
+    } else {
+        let configure = Configure::get();
+        (
+            unix_includes(),
+            vec![],
+            configure.has_zts(),
+            configure.debug(),
         )
-        .compile("wrapper");
+    };
+
+    // Build `wrapper.c` and link to Rust.
+    let mut build = cc::Build::new();
+    build.file("src/wrapper/wrapper.c").includes(&includes);
+    for (name, value) in defines.iter() {
+        build.define(name, Some(*value));
+    }
+    build.compile("wrapper");
 
     let mut bindgen = bindgen::Builder::default()
         .header("src/wrapper/wrapper.h")
-        .clang_args(includes.split(' '))
+        .clang_args(includes.iter().map(|path| format!("-I{}", path.display())))
+        .clang_args(
+            defines
+                .iter()
+                .map(|(name, value)| format!("-D{}={}", name, value)),
+        )
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
         .rustfmt_bindings(true)
         .no_copy("_zend_value")
@@ -100,17 +108,127 @@ fn main() {
         .write_to_file(out_path)
         .expect("Unable to write bindings file.");
 
-    let configure = Configure::get();
-
-    if configure.has_zts() {
+    if zts {
         println!("cargo:rustc-cfg=php_zts");
     }
 
-    if configure.debug() {
+    if debug {
         println!("cargo:rustc-cfg=php_debug");
     }
 }
 
+/// Retrieves the include paths of the PHP headers using `php-config`.
+fn unix_includes() -> Vec<PathBuf> {
+    let includes_cmd = Command::new("php-config")
+        .arg("--includes")
+        .output()
+        .expect("Unable to run `php-config`. Please ensure it is visible in your PATH.");
+
+    if !includes_cmd.status.success() {
+        let stderr = String::from_utf8(includes_cmd.stderr)
+            .unwrap_or_else(|_| String::from("Unable to read stderr"));
+        panic!("Error running `php-config`: {}", stderr);
+    }
+
+    String::from_utf8(includes_cmd.stdout)
+        .expect("unable to parse `php-config` stdout")
+        .split_whitespace()
+        .map(|path| PathBuf::from(path.trim_start_matches("-I")))
+        .collect()
+}
+
+/// Retrieves the path to the PHP development pack on Windows, which contains the PHP headers and
+/// the `php8ts.lib` (or `php8.lib`) library to link against. The path is read from the
+/// `PHP_DEV_PACK` environment variable.
+fn windows_dev_pack() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=PHP_DEV_PACK");
+
+    let path = env::var("PHP_DEV_PACK").map(PathBuf::from).expect(
+        "Unable to find the PHP development pack. Please set the `PHP_DEV_PACK` environment variable to its path.",
+    );
+
+    if !path.join("include").is_dir() || !path.join("lib").is_dir() {
+        panic!(
+            "The PHP development pack at `{}` does not contain the `include` and `lib` directories.",
+            path.display()
+        );
+    }
+
+    path
+}
+
+/// Returns the include paths of the PHP headers inside the PHP development pack, matching the
+/// output of `php-config --includes` on other platforms.
+fn windows_includes(dev_pack: &Path) -> Vec<PathBuf> {
+    let include = dev_pack.join("include");
+
+    ["", "main", "Zend", "TSRM", "ext"]
+        .iter()
+        .map(|dir| include.join(dir))
+        .collect()
+}
+
+/// Output of `php -i`, used to retrieve information about the PHP installation.
+struct PhpInfo(String);
+
+impl PhpInfo {
+    pub fn get() -> Self {
+        // Allow the PHP executable to be overridden, as `php.exe` is not always in the PATH on
+        // Windows.
+        println!("cargo:rerun-if-env-changed=PHP");
+        let php = env::var("PHP").unwrap_or_else(|_| "php".into());
+
+        let cmd = Command::new(&php).arg("-i").output().unwrap_or_else(|_| {
+            panic!(
+                "Unable to run `{} -i`. Please ensure it is visible in your PATH or set the `PHP` environment variable.",
+                php
+            )
+        });
+
+        if !cmd.status.success() {
+            let stderr = str::from_utf8(&cmd.stderr).unwrap_or("Unable to read stderr");
+            panic!("Error running `{} -i`: {}", php, stderr);
+        }
+
+        Self(String::from_utf8(cmd.stdout).expect("Unable to parse `php -i` stdout as UTF-8"))
+    }
+
+    /// Retrieves the value of a line in the form `key => value`.
+    fn get_key(&self, key: &str) -> Option<&str> {
+        let prefix = format!("{} => ", key);
+        self.0
+            .lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .map(str::trim)
+    }
+
+    pub fn api_version(&self) -> u32 {
+        self.get_key("PHP API")
+            .and_then(|ver| ver.parse::<u32>().ok())
+            .expect("Unable to retrieve PHP API version from `php -i`.")
+    }
+
+    pub fn major_version(&self) -> u32 {
+        Regex::new(r"^([0-9]+)\.")
+            .unwrap()
+            .captures(
+                self.get_key("PHP Version")
+                    .expect("Unable to retrieve PHP version from `php -i`."),
+            )
+            .and_then(|ver| ver.get(1))
+            .and_then(|ver| ver.as_str().parse::<u32>().ok())
+            .expect("Unable to parse PHP version from `php -i`.")
+    }
+
+    pub fn zts(&self) -> bool {
+        self.get_key("Thread Safety") == Some("enabled")
+    }
+
+    pub fn debug(&self) -> bool {
+        self.get_key("Debug Build") == Some("yes")
+    }
+}
+
 struct Configure(String);
 
 impl Configure {
@@ -149,6 +267,7 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "_call_user_function_impl",
     "_efree",
     "_emalloc",
+    "_erealloc",
     "_zend_executor_globals",
     "_zend_expected_type",
     "_zend_expected_type_Z_EXPECTED_ARRAY",
@@ -161,12 +280,15 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "_zend_new_array",
     "_zval_struct__bindgen_ty_1",
     "_zval_struct__bindgen_ty_2",
+    "display_ini_entries",
     "ext_php_rs_executor_globals",
     "ext_php_rs_php_build_id",
     "ext_php_rs_zend_object_alloc",
     "ext_php_rs_zend_object_release",
     "ext_php_rs_zend_string_init",
     "ext_php_rs_zend_string_release",
+    "module_registry",
+    "object_init_ex",
     "object_properties_init",
     "php_info_print_table_end",
     "php_info_print_table_header",
@@ -175,22 +297,38 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "std_object_handlers",
     "zend_array_destroy",
     "zend_array_dup",
+    "zend_call_function",
+    "zend_call_known_function",
+    "zend_ce_aggregate",
     "zend_ce_argument_count_error",
     "zend_ce_arithmetic_error",
+    "zend_ce_arrayaccess",
     "zend_ce_compile_error",
+    "zend_ce_countable",
     "zend_ce_division_by_zero_error",
     "zend_ce_error_exception",
     "zend_ce_exception",
+    "zend_ce_iterator",
     "zend_ce_parse_error",
+    "zend_ce_serializable",
+    "zend_ce_stringable",
     "zend_ce_throwable",
+    "zend_ce_traversable",
     "zend_ce_type_error",
     "zend_ce_unhandled_match_error",
     "zend_ce_value_error",
     "zend_class_entry",
-    "zend_declare_class_constant",
+    "zend_class_implements",
+    "zend_class_constant",
+    "zend_clear_exception",
+    "zend_constant",
+    "zend_declare_class_constant_ex",
     "zend_declare_property",
     "zend_do_implement_interface",
+    "zend_error",
     "zend_execute_data",
+    "zend_fcall_info",
+    "zend_fcall_info_cache",
     "zend_function_entry",
     "zend_hash_clean",
     "zend_hash_index_del",
@@ -199,33 +337,58 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "zend_hash_next_index_insert",
     "zend_hash_str_del",
     "zend_hash_str_find",
+    "zend_hash_str_find_ptr_lc",
     "zend_hash_str_update",
+    "zend_ini_entry_def",
+    "zend_ini_string_ex",
     "zend_internal_arg_info",
     "zend_is_callable",
+    "zend_is_callable_ex",
+    "zend_is_true",
     "zend_long",
     "zend_lookup_class_ex",
+    "zend_module_dep",
     "zend_module_entry",
     "zend_object",
     "zend_object_handlers",
     "zend_object_std_init",
+    "zend_parse_arg_bool_weak",
+    "zend_parse_arg_double_weak",
+    "zend_parse_arg_long_weak",
+    "zend_parse_arg_str_weak",
     "zend_objects_clone_members",
     "zend_register_bool_constant",
     "zend_register_double_constant",
+    "zend_read_static_property",
     "zend_register_internal_class_ex",
     "zend_register_long_constant",
+    "zend_register_null_constant",
+    "zend_register_ini_entries",
     "zend_register_string_constant",
     "zend_resource",
     "zend_string",
     "zend_string_init_interned",
     "zend_throw_exception_ex",
+    "zend_throw_exception_object",
     "zend_type",
+    "zend_unexpected_extra_named_error",
+    "zend_unregister_ini_entries",
+    "zend_update_static_property",
     "zend_value",
+    "zend_wrong_parameter_class_error",
+    "zend_wrong_parameter_class_or_null_error",
+    "zend_wrong_parameter_type_error",
     "zend_wrong_parameters_count_error",
     "zval",
     "CONST_CS",
     "CONST_DEPRECATED",
     "CONST_NO_FILE_CACHE",
     "CONST_PERSISTENT",
+    "E_DEPRECATED",
+    "E_ERROR",
+    "E_NOTICE",
+    "E_WARNING",
+    "GC_IMMUTABLE",
     "HT_MIN_SIZE",
     "IS_ARRAY",
     "IS_ARRAY_EX",
@@ -234,7 +397,9 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "IS_CONSTANT_AST_EX",
     "IS_DOUBLE",
     "IS_FALSE",
+    "IS_INDIRECT",
     "IS_INTERNED_STRING_EX",
+    "IS_ITERABLE",
     "IS_LONG",
     "IS_MIXED",
     "IS_NULL",
@@ -251,8 +416,12 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "IS_TYPE_REFCOUNTED",
     "IS_UNDEF",
     "IS_VOID",
+    "BP_VAR_IS",
     "MAY_BE_ANY",
     "MAY_BE_BOOL",
+    "MODULE_DEP_CONFLICTS",
+    "MODULE_DEP_OPTIONAL",
+    "MODULE_DEP_REQUIRED",
     "USING_ZTS",
     "ZEND_ACC_ABSTRACT",
     "ZEND_ACC_ANON_CLASS",
@@ -298,8 +467,15 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "ZEND_ACC_USES_THIS",
     "ZEND_ACC_USE_GUARDS",
     "ZEND_ACC_VARIADIC",
+    "ZEND_CALL_HAS_EXTRA_NAMED_PARAMS",
     "ZEND_DEBUG",
+    "ZEND_INTERNAL_CLASS",
+    "ZEND_INTERNAL_FUNCTION",
     "ZEND_HAS_STATIC_IN_METHODS",
+    "ZEND_INI_ALL",
+    "ZEND_INI_PERDIR",
+    "ZEND_INI_SYSTEM",
+    "ZEND_INI_USER",
     "ZEND_ISEMPTY",
     "ZEND_MM_ALIGNMENT",
     "ZEND_MM_ALIGNMENT_MASK",
@@ -311,6 +487,7 @@ const ALLOWED_BINDINGS: &[&str] = &[
     "_ZEND_IS_VARIADIC_BIT",
     "_ZEND_SEND_MODE_SHIFT",
     "_ZEND_TYPE_NULLABLE_BIT",
+    "ts_resource_ex",
     "ts_rsrc_id",
     "_ZEND_TYPE_NAME_BIT",
 ];
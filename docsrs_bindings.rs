@@ -16,6 +16,7 @@ pub const ZEND_DEBUG: u32 = 1;
 pub const ZEND_MM_ALIGNMENT: u32 = 8;
 pub const _ZEND_TYPE_NAME_BIT: u32 = 8388608;
 pub const _ZEND_TYPE_NULLABLE_BIT: u32 = 2;
+pub const GC_IMMUTABLE: u32 = 64;
 pub const HT_MIN_SIZE: u32 = 8;
 pub const IS_UNDEF: u32 = 0;
 pub const IS_NULL: u32 = 1;
@@ -30,9 +31,11 @@ pub const IS_RESOURCE: u32 = 9;
 pub const IS_REFERENCE: u32 = 10;
 pub const IS_CONSTANT_AST: u32 = 11;
 pub const IS_CALLABLE: u32 = 12;
+pub const IS_ITERABLE: u32 = 13;
 pub const IS_VOID: u32 = 14;
 pub const IS_MIXED: u32 = 16;
 pub const _IS_BOOL: u32 = 17;
+pub const IS_INDIRECT: u32 = 12;
 pub const Z_TYPE_FLAGS_SHIFT: u32 = 8;
 pub const IS_TYPE_REFCOUNTED: u32 = 1;
 pub const IS_TYPE_COLLECTABLE: u32 = 2;
@@ -91,14 +94,29 @@ pub const ZEND_ACC_GENERATOR: u32 = 16777216;
 pub const ZEND_ACC_DONE_PASS_TWO: u32 = 33554432;
 pub const ZEND_ACC_HEAP_RT_CACHE: u32 = 67108864;
 pub const ZEND_ACC_STRICT_TYPES: u32 = 2147483648;
+pub const ZEND_CALL_HAS_EXTRA_NAMED_PARAMS: u32 = 134217728;
+pub const ZEND_INTERNAL_FUNCTION: u32 = 1;
+pub const ZEND_INTERNAL_CLASS: u32 = 1;
 pub const ZEND_ISEMPTY: u32 = 1;
 pub const _ZEND_SEND_MODE_SHIFT: u32 = 24;
 pub const _ZEND_IS_VARIADIC_BIT: u32 = 67108864;
+pub const BP_VAR_IS: u32 = 3;
+pub const MODULE_DEP_REQUIRED: u32 = 1;
+pub const MODULE_DEP_CONFLICTS: u32 = 2;
+pub const MODULE_DEP_OPTIONAL: u32 = 3;
 pub const ZEND_MODULE_API_NO: u32 = 20200930;
 pub const USING_ZTS: u32 = 0;
 pub const MAY_BE_BOOL: u32 = 12;
 pub const MAY_BE_ANY: u32 = 1022;
+pub const ZEND_INI_USER: u32 = 1;
+pub const ZEND_INI_PERDIR: u32 = 2;
+pub const ZEND_INI_SYSTEM: u32 = 4;
+pub const ZEND_INI_ALL: u32 = 7;
 pub const CONST_CS: u32 = 0;
+pub const E_ERROR: u32 = 1;
+pub const E_WARNING: u32 = 2;
+pub const E_NOTICE: u32 = 8;
+pub const E_DEPRECATED: u32 = 8192;
 pub const CONST_PERSISTENT: u32 = 1;
 pub const CONST_NO_FILE_CACHE: u32 = 2;
 pub const CONST_DEPRECATED: u32 = 4;
@@ -315,6 +333,16 @@ extern "C" {
         __zend_orig_lineno: u32,
     ) -> *mut ::std::os::raw::c_void;
 }
+extern "C" {
+    pub fn _erealloc(
+        ptr: *mut ::std::os::raw::c_void,
+        size: size_t,
+        __zend_filename: *const ::std::os::raw::c_char,
+        __zend_lineno: u32,
+        __zend_orig_filename: *const ::std::os::raw::c_char,
+        __zend_orig_lineno: u32,
+    ) -> *mut ::std::os::raw::c_void;
+}
 extern "C" {
     pub fn _efree(
         ptr: *mut ::std::os::raw::c_void,
@@ -369,6 +397,13 @@ extern "C" {
         len: size_t,
     ) -> *mut zval;
 }
+extern "C" {
+    pub fn zend_hash_str_find_ptr_lc(
+        ht: *const HashTable,
+        str_: *const ::std::os::raw::c_char,
+        len: size_t,
+    ) -> *mut ::std::os::raw::c_void;
+}
 extern "C" {
     pub fn zend_hash_index_find(ht: *const HashTable, h: zend_ulong) -> *mut zval;
 }
@@ -1086,6 +1121,10 @@ pub struct _zend_module_dep {
     pub version: *const ::std::os::raw::c_char,
     pub type_: ::std::os::raw::c_uchar,
 }
+pub type zend_module_dep = _zend_module_dep;
+extern "C" {
+    pub static mut module_registry: HashTable;
+}
 extern "C" {
     pub fn zend_lookup_class_ex(
         name: *mut zend_string,
@@ -1123,6 +1162,19 @@ extern "C" {
         callable_name: *mut *mut zend_string,
     ) -> zend_bool;
 }
+extern "C" {
+    pub fn zend_is_callable_ex(
+        callable: *mut zval,
+        object: *mut zend_object,
+        check_flags: u32,
+        callable_name: *mut *mut zend_string,
+        fcc: *mut zend_fcall_info_cache,
+        error: *mut *mut ::std::os::raw::c_char,
+    ) -> zend_bool;
+}
+extern "C" {
+    pub fn zend_is_true(op: *mut zval) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn zend_declare_property(
         ce: *mut zend_class_entry,
@@ -1133,16 +1185,80 @@ extern "C" {
     );
 }
 extern "C" {
-    pub fn zend_declare_class_constant(
-        ce: *mut zend_class_entry,
+    pub fn zend_update_static_property(
+        scope: *mut zend_class_entry,
         name: *const ::std::os::raw::c_char,
         name_length: size_t,
         value: *mut zval,
-    );
+    ) -> zend_result;
+}
+extern "C" {
+    pub fn zend_read_static_property(
+        scope: *mut zend_class_entry,
+        name: *const ::std::os::raw::c_char,
+        name_length: size_t,
+        silent: bool,
+    ) -> *mut zval;
+}
+#[repr(C)]
+pub struct _zend_class_constant {
+    pub value: zval,
+    pub doc_comment: *mut zend_string,
+    pub ce: *mut zend_class_entry,
+}
+pub type zend_class_constant = _zend_class_constant;
+extern "C" {
+    pub fn zend_declare_class_constant_ex(
+        ce: *mut zend_class_entry,
+        name: *mut zend_string,
+        value: *mut zval,
+        flags: ::std::os::raw::c_int,
+        doc_comment: *mut zend_string,
+    ) -> *mut zend_class_constant;
 }
 extern "C" {
     pub fn object_properties_init(object: *mut zend_object, class_type: *mut zend_class_entry);
 }
+extern "C" {
+    pub fn object_init_ex(arg: *mut zval, ce: *mut zend_class_entry) -> zend_result;
+}
+#[repr(C)]
+pub struct _zend_fcall_info {
+    pub size: size_t,
+    pub function_name: zval,
+    pub retval: *mut zval,
+    pub params: *mut zval,
+    pub object: *mut zend_object,
+    pub param_count: u32,
+    pub named_params: *mut HashTable,
+}
+pub type zend_fcall_info = _zend_fcall_info;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_fcall_info_cache {
+    pub function_handler: *mut zend_function,
+    pub calling_scope: *mut zend_class_entry,
+    pub called_scope: *mut zend_class_entry,
+    pub object: *mut zend_object,
+}
+pub type zend_fcall_info_cache = _zend_fcall_info_cache;
+extern "C" {
+    pub fn zend_call_function(
+        fci: *mut zend_fcall_info,
+        fci_cache: *mut zend_fcall_info_cache,
+    ) -> zend_result;
+}
+extern "C" {
+    pub fn zend_call_known_function(
+        fn_: *mut zend_function,
+        object: *mut zend_object,
+        called_scope: *mut zend_class_entry,
+        retval_ptr: *mut zval,
+        param_count: u32,
+        params: *mut zval,
+        named_params: *mut HashTable,
+    );
+}
 extern "C" {
     pub fn _call_user_function_impl(
         object: *mut zval,
@@ -1187,9 +1303,46 @@ pub const _zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING: _zend_expected_type =
 pub const _zend_expected_type_Z_EXPECTED_OBJECT_OR_STRING_OR_NULL: _zend_expected_type = 31;
 pub const _zend_expected_type_Z_EXPECTED_LAST: _zend_expected_type = 32;
 pub type _zend_expected_type = ::std::os::raw::c_uint;
+pub use self::_zend_expected_type as zend_expected_type;
 extern "C" {
     pub fn zend_wrong_parameters_count_error(min_num_args: u32, max_num_args: u32);
 }
+extern "C" {
+    pub fn zend_unexpected_extra_named_error();
+}
+extern "C" {
+    pub fn zend_wrong_parameter_class_error(
+        num: u32,
+        name: *const ::std::os::raw::c_char,
+        arg: *mut zval,
+    );
+}
+extern "C" {
+    pub fn zend_wrong_parameter_class_or_null_error(
+        num: u32,
+        name: *const ::std::os::raw::c_char,
+        arg: *mut zval,
+    );
+}
+extern "C" {
+    pub fn zend_wrong_parameter_type_error(
+        num: u32,
+        expected_type: zend_expected_type,
+        arg: *mut zval,
+    );
+}
+extern "C" {
+    pub fn zend_parse_arg_bool_weak(arg: *mut zval, dest: *mut bool) -> bool;
+}
+extern "C" {
+    pub fn zend_parse_arg_long_weak(arg: *mut zval, dest: *mut zend_long) -> bool;
+}
+extern "C" {
+    pub fn zend_parse_arg_double_weak(arg: *mut zval, dest: *mut f64) -> bool;
+}
+extern "C" {
+    pub fn zend_parse_arg_str_weak(arg: *mut zval, dest: *mut *mut zend_string) -> bool;
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _zend_ini_entry {
@@ -1217,6 +1370,66 @@ pub struct _zend_ini_entry {
     pub orig_modifiable: u8,
     pub modified: u8,
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _zend_ini_entry_def {
+    pub name: *const ::std::os::raw::c_char,
+    pub on_modify: ::std::option::Option<
+        unsafe extern "C" fn(
+            entry: *mut zend_ini_entry,
+            new_value: *mut zend_string,
+            mh_arg1: *mut ::std::os::raw::c_void,
+            mh_arg2: *mut ::std::os::raw::c_void,
+            mh_arg3: *mut ::std::os::raw::c_void,
+            stage: ::std::os::raw::c_int,
+        ) -> ::std::os::raw::c_int,
+    >,
+    pub mh_arg1: *mut ::std::os::raw::c_void,
+    pub mh_arg2: *mut ::std::os::raw::c_void,
+    pub mh_arg3: *mut ::std::os::raw::c_void,
+    pub value: *const ::std::os::raw::c_char,
+    pub displayer: ::std::option::Option<
+        unsafe extern "C" fn(ini_entry: *mut zend_ini_entry, type_: ::std::os::raw::c_int),
+    >,
+    pub value_length: u32,
+    pub name_length: u16,
+    pub modifiable: u8,
+}
+pub type zend_ini_entry_def = _zend_ini_entry_def;
+extern "C" {
+    pub fn zend_register_ini_entries(
+        ini_entry: *const zend_ini_entry_def,
+        module_number: ::std::os::raw::c_int,
+    ) -> zend_result;
+}
+extern "C" {
+    pub fn zend_unregister_ini_entries(module_number: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn zend_ini_string_ex(
+        name: *const ::std::os::raw::c_char,
+        name_length: size_t,
+        orig: ::std::os::raw::c_int,
+        exists: *mut zend_bool,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn display_ini_entries(module: *mut zend_module_entry);
+}
+#[repr(C)]
+pub struct _zend_constant {
+    pub value: zval,
+    pub name: *mut zend_string,
+}
+pub type zend_constant = _zend_constant;
+extern "C" {
+    pub fn zend_register_null_constant(
+        name: *const ::std::os::raw::c_char,
+        name_len: size_t,
+        flags: ::std::os::raw::c_int,
+        module_number: ::std::os::raw::c_int,
+    );
+}
 extern "C" {
     pub fn zend_register_bool_constant(
         name: *const ::std::os::raw::c_char,
@@ -1306,9 +1519,46 @@ extern "C" {
         ...
     ) -> *mut zend_object;
 }
+extern "C" {
+    pub fn zend_throw_exception_object(exception: *mut zval);
+}
+extern "C" {
+    pub fn zend_clear_exception();
+}
+extern "C" {
+    pub fn zend_error(type_: ::std::os::raw::c_int, format: *const ::std::os::raw::c_char, ...);
+}
 extern "C" {
     pub fn zend_do_implement_interface(ce: *mut zend_class_entry, iface: *mut zend_class_entry);
 }
+extern "C" {
+    pub fn zend_class_implements(
+        class_entry: *mut zend_class_entry,
+        num_interfaces: ::std::os::raw::c_int,
+        ...
+    );
+}
+extern "C" {
+    pub static mut zend_ce_traversable: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_aggregate: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_iterator: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_arrayaccess: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_serializable: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_countable: *mut zend_class_entry;
+}
+extern "C" {
+    pub static mut zend_ce_stringable: *mut zend_class_entry;
+}
 extern "C" {
     pub fn ext_php_rs_zend_string_init(
         str_: *const ::std::os::raw::c_char,
@@ -42,8 +42,8 @@ use ext_php_rs::{
 //     y: f64,
 //     z: Option<f64>,
 // ) -> Result<ZendHashTable, String> {
-//     for (k, x, y) in arr.iter() {
-//         println!("{:?} {:?} {:?}", k, x, y.string());
+//     for (k, v) in arr.iter() {
+//         println!("{:?} {:?}", k, v.string());
 //     }
 
 //     dbg!(x, y, z);
@@ -120,7 +120,7 @@ pub fn test_str(input: &str) -> &str {
 // #[global_allocator]
 // static GLOBAL: PhpAllocator = PhpAllocator::new();
 
-#[php_class]
+#[php_class(clone)]
 #[property(test = 0)]
 #[property(another = "Hello world")]
 #[derive(Default, Debug, Clone)]
@@ -2,6 +2,22 @@
 
 include 'vendor/autoload.php';
 
+// Cloning an instance of a PHP subclass keeps the subclass and its properties.
+class SubTest extends Test
+{
+    public $extra = 'extra';
+}
+
+$sub = new SubTest;
+$sub->setStr('cloned');
+$sub->extra = 'changed';
+$copy = clone $sub;
+assert($copy instanceof SubTest);
+assert($copy->getStr() === 'cloned');
+assert($copy->extra === 'changed');
+$copy->setStr('copy');
+assert($sub->getStr() === 'cloned');
+
 $ext = new ReflectionExtension('skel');
 
 dd($ext);
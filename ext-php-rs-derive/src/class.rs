@@ -28,6 +28,8 @@ pub enum ParsedAttribute {
 #[darling(default)]
 pub struct AttrArgs {
     name: Option<String>,
+    property_handler: bool,
+    clone: bool,
 }
 
 pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream> {
@@ -67,7 +69,28 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
 
     let ItemStruct { ident, .. } = &input;
     let class_name = args.name.unwrap_or_else(|| ident.to_string());
+    validate_name("Class", &class_name)?;
     let meta = Ident::new(&format!("_{}_META", ident.to_string()), Span::call_site());
+    let property_handler = if args.property_handler {
+        quote! { handlers.set_property_handler::<Self>(); }
+    } else {
+        quote! {}
+    };
+    let clone_handler = if args.clone {
+        quote! { handlers.set_clone_handler::<Self>(); }
+    } else {
+        quote! {}
+    };
+    let init_handlers = if args.property_handler || args.clone {
+        quote! {
+            fn init_handlers(handlers: &mut ::ext_php_rs::php::types::object::ZendObjectHandlers) {
+                #property_handler
+                #clone_handler
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     let output = quote! {
         #input
@@ -80,6 +103,8 @@ pub fn parser(args: AttributeArgs, mut input: ItemStruct) -> Result<TokenStream>
             fn get_metadata() -> &'static ::ext_php_rs::php::types::object::ClassMetadata<Self> {
                 &#meta
             }
+
+            #init_handlers
         }
     };
 
@@ -131,6 +156,38 @@ impl syn::parse::Parse for PropertyAttr {
     }
 }
 
+/// Checks that the given class or function name, which may be namespaced, is a valid PHP name.
+/// Each segment must be a valid PHP identifier, and the name must not be fully qualified, as PHP
+/// registers the name verbatim, leaving the class or function unreachable.
+///
+/// `kind` is the capitalized kind of item being named, i.e. `Class`, used in error messages.
+pub fn validate_name(kind: &str, name: &str) -> Result<()> {
+    let valid_segment = |segment: &str| {
+        let mut chars = segment.chars();
+        matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic() || !c.is_ascii())
+            && chars.all(|c| c == '_' || c.is_ascii_alphanumeric() || !c.is_ascii())
+    };
+
+    if name.starts_with('\\') {
+        bail!(
+            "{} name `{}` must not start with a backslash, as it is already relative to the global namespace.",
+            kind,
+            name
+        );
+    }
+
+    if !name.split('\\').all(valid_segment) {
+        bail!(
+            "Invalid {} name `{}`. Namespaced names must be separated by single backslashes, i.e. `My\\Namespace\\{}`.",
+            kind.to_lowercase(),
+            name,
+            kind
+        );
+    }
+
+    Ok(())
+}
+
 fn parse_attribute(attr: &Attribute) -> Result<Option<ParsedAttribute>> {
     let name = attr.path.to_token_stream().to_string();
 
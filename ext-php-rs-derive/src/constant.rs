@@ -4,12 +4,12 @@ use proc_macro2::{Ident, Literal, TokenStream};
 use quote::quote;
 use syn::ItemConst;
 
-use crate::STATE;
+use crate::{impl_::Visibility, STATE};
 
 #[derive(Debug)]
 pub struct Constant {
     pub name: String,
-    // pub visibility: Visibility,
+    pub visibility: Visibility,
     pub value: String,
 }
 
@@ -22,6 +22,7 @@ pub fn parser(input: ItemConst) -> Result<TokenStream> {
 
     state.constants.push(Constant {
         name: input.ident.to_string(),
+        visibility: Visibility::Public,
         value: input.expr.to_token_stream().to_string(),
     });
 
@@ -39,13 +40,13 @@ impl Constant {
             .unwrap_or(quote! { Default::default() })
     }
 
-    // pub fn get_flags(&self) -> TokenStream {
-    //     let flag = match self.visibility {
-    //         Visibility::Public => quote! { Public },
-    //         Visibility::Protected => quote! { Protected },
-    //         Visibility::Private => quote! { Private },
-    //     };
+    pub fn get_flags(&self) -> TokenStream {
+        let flag = match self.visibility {
+            Visibility::Public => quote! { Public },
+            Visibility::Protected => quote! { Protected },
+            Visibility::Private => quote! { Private },
+        };
 
-    //     quote! { ::ext_php_rs::php::flags::ConstantFlags}
-    // }
+        quote! { ::ext_php_rs::php::flags::ConstantFlags::#flag }
+    }
 }
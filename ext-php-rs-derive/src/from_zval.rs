@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::impl_::{parse_attribute, ParsedAttribute};
+
+pub fn parser(input: DeriveInput) -> Result<TokenStream> {
+    let DeriveInput {
+        ident,
+        data,
+        generics,
+        ..
+    } = input;
+
+    let variants = match data {
+        Data::Enum(data) => data.variants,
+        _ => bail!("`FromZval` can only be derived for enums."),
+    };
+
+    if !generics.params.is_empty() {
+        bail!("`FromZval` cannot be derived for generic enums.");
+    }
+
+    if variants.is_empty() {
+        bail!("`FromZval` cannot be derived for enums without variants.");
+    }
+
+    // Enums with explicit discriminants are backed by integers, otherwise by strings.
+    let int_backed = variants
+        .iter()
+        .any(|variant| variant.discriminant.is_some());
+    let mut idents = Vec::with_capacity(variants.len());
+    let mut names = Vec::with_capacity(variants.len());
+    let mut seen = HashSet::new();
+
+    for variant in variants.iter() {
+        if !matches!(variant.fields, Fields::Unit) {
+            bail!(
+                "Variant `{}` must not have any fields to derive `FromZval`.",
+                variant.ident
+            );
+        }
+
+        let mut name = None;
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("rename"))
+        {
+            if int_backed {
+                bail!(
+                    "Variant `{}` cannot be renamed, as the enum is backed by integers.",
+                    variant.ident
+                );
+            }
+
+            if let ParsedAttribute::Rename(rename) = parse_attribute(attr)? {
+                name = Some(rename);
+            }
+        }
+
+        let name = name.unwrap_or_else(|| {
+            ident_case::RenameRule::SnakeCase.apply_to_variant(variant.ident.to_string())
+        });
+
+        if !int_backed && !seen.insert(name.clone()) {
+            bail!("Value `{}` is used for more than one variant.", name);
+        }
+
+        idents.push(&variant.ident);
+        names.push(name);
+    }
+
+    Ok(if int_backed {
+        quote! {
+            impl<'a> ::ext_php_rs::php::types::zval::FromZval<'a> for #ident {
+                const TYPE: ::ext_php_rs::php::enums::DataType =
+                    ::ext_php_rs::php::enums::DataType::Long;
+
+                fn from_zval(zval: &'a ::ext_php_rs::php::types::zval::Zval) -> ::std::option::Option<Self> {
+                    let value = zval.long()?;
+                    #(
+                        if value == Self::#idents as ::ext_php_rs::php::types::long::ZendLong {
+                            return ::std::option::Option::Some(Self::#idents);
+                        }
+                    )*
+                    ::std::option::Option::None
+                }
+            }
+
+            impl ::std::convert::TryFrom<&::ext_php_rs::php::types::zval::Zval> for #ident {
+                type Error = ::ext_php_rs::errors::Error;
+
+                fn try_from(zval: &::ext_php_rs::php::types::zval::Zval) -> ::ext_php_rs::errors::Result<Self> {
+                    let value = zval.long().ok_or(::ext_php_rs::errors::Error::ZvalTypeMismatch(
+                        zval.get_type()?,
+                        ::ext_php_rs::php::enums::DataType::Long,
+                    ))?;
+
+                    <Self as ::ext_php_rs::php::types::zval::FromZval>::from_zval(zval).ok_or_else(|| {
+                        ::ext_php_rs::errors::Error::InvalidValue(
+                            value.to_string(),
+                            vec![#((Self::#idents as ::ext_php_rs::php::types::long::ZendLong).to_string()),*],
+                        )
+                    })
+                }
+            }
+        }
+    } else {
+        let quoted = names.iter().map(|name| format!("{:?}", name));
+
+        quote! {
+            impl<'a> ::ext_php_rs::php::types::zval::FromZval<'a> for #ident {
+                const TYPE: ::ext_php_rs::php::enums::DataType =
+                    ::ext_php_rs::php::enums::DataType::String;
+
+                fn from_zval(zval: &'a ::ext_php_rs::php::types::zval::Zval) -> ::std::option::Option<Self> {
+                    match zval.str()? {
+                        #(#names => ::std::option::Option::Some(Self::#idents),)*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+            }
+
+            impl ::std::convert::TryFrom<&::ext_php_rs::php::types::zval::Zval> for #ident {
+                type Error = ::ext_php_rs::errors::Error;
+
+                fn try_from(zval: &::ext_php_rs::php::types::zval::Zval) -> ::ext_php_rs::errors::Result<Self> {
+                    let value = zval.string_strict()?;
+
+                    match value.as_str() {
+                        #(#names => ::std::result::Result::Ok(Self::#idents),)*
+                        _ => ::std::result::Result::Err(::ext_php_rs::errors::Error::InvalidValue(
+                            format!("{:?}", value),
+                            vec![#(#quoted.to_string()),*],
+                        )),
+                    }
+                }
+            }
+        }
+    })
+}
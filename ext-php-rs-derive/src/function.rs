@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::STATE;
+use crate::{class::validate_name, STATE};
 use anyhow::{anyhow, bail, Result};
 use darling::{FromMeta, ToTokens};
 use proc_macro2::{Ident, Literal, Span, TokenStream};
@@ -14,6 +14,7 @@ use syn::{
 #[derive(Default, Debug, FromMeta)]
 #[darling(default)]
 pub struct AttrArgs {
+    name: Option<String>,
     optional: Option<String>,
     ignore_module: bool,
     defaults: HashMap<String, Lit>,
@@ -85,8 +86,11 @@ pub fn parser(args: AttributeArgs, input: ItemFn) -> Result<(TokenStream, Functi
         bail!("The `#[php_module]` macro must be called last to ensure functions are registered. To ignore this error, pass the `ignore_module` option into this attribute invocation: `#[php_function(ignore_module)]`");
     }
 
+    let name = attr_args.name.unwrap_or_else(|| ident.to_string());
+    validate_name("Function", &name)?;
+
     let function = Function {
-        name: ident.to_string(),
+        name,
         ident: internal_ident.to_string(),
         args,
         optional,
@@ -187,6 +191,13 @@ pub fn build_arg_parser<'a>(
         })
         .collect::<Result<Vec<_>>>()?;
 
+    if let (Some(optional), false) = (optional, rest_optional) {
+        bail!(
+            "Optional parameter `{}` does not exist in the function signature.",
+            optional
+        );
+    }
+
     Ok(quote! {
         let parser = ::ext_php_rs::php::args::ArgParser::new(ex)
             #(#args)*
@@ -208,7 +219,7 @@ pub fn build_return_handler(output_type: &ReturnType) -> TokenStream {
         ReturnType::Type(_, ref ty) => match **ty {
             Type::Path(ref path) => match path.path.segments.last() {
                 Some(path_seg) => match path_seg.ident.to_string().as_ref() {
-                    "Result" => Some(quote! {
+                    "Result" | "PhpResult" => Some(quote! {
                         match result {
                             Ok(result) => match result.set_zval(retval, false) {
                                 Ok(_) => {}
@@ -290,29 +301,46 @@ impl Arg {
             Type::Path(TypePath { path, .. }) => {
                 let seg = path.segments.last()?;
                 let result = Some(seg)
-                    .filter(|seg| seg.ident == "Result")
+                    .filter(|seg| seg.ident == "Result" || seg.ident == "PhpResult")
                     .and_then(|seg| {
-                        if let PathArguments::AngleBracketed(args) = &seg.arguments {
-                            args.args
-                                .iter()
-                                .find(|arg| matches!(arg, GenericArgument::Type(_)))
-                                .map(|ty| ty.to_token_stream().to_string())
-                        } else {
-                            None
+                        let ty = match &seg.arguments {
+                            PathArguments::AngleBracketed(args) => {
+                                args.args.iter().find_map(|arg| match arg {
+                                    GenericArgument::Type(ty) => {
+                                        Some((ty.to_token_stream().to_string(), is_option(ty)))
+                                    }
+                                    _ => None,
+                                })
+                            }
+                            _ => None,
+                        };
+
+                        // `PhpResult` defaults to returning nothing, including when only a
+                        // lifetime is given, i.e. `PhpResult<'static>`.
+                        match ty {
+                            None if seg.ident == "PhpResult" => Some(("()".to_string(), false)),
+                            ty => ty,
                         }
                     });
-                let stringified = match result {
+                let (stringified, nullable) = match result {
+                    // `Result<Option<T>, E>` returns a nullable `T`.
                     Some(result) if is_return => result,
-                    _ => path.to_token_stream().to_string(),
+                    _ => (path.to_token_stream().to_string(), seg.ident == "Option"),
                 };
 
                 Some(Arg::new(
                     name,
                     &stringified,
-                    seg.ident == "Option" || default.is_some(),
+                    nullable || default.is_some(),
                     default,
                 ))
             }
+            Type::Tuple(tuple) if !tuple.elems.is_empty() => Some(Arg::new(
+                name,
+                &tuple.to_token_stream().to_string(),
+                default.is_some(),
+                default,
+            )),
             Type::Reference(ref_) => {
                 // Returning references is invalid, so let's just create our arg
                 Some(Arg::new(
@@ -378,6 +406,10 @@ impl Arg {
 
         let null = self.nullable.then(|| quote! { .allow_null() });
         let default = self.default.as_ref().map(|val| {
+            // Defaults are parsed from literals, so this will always succeed.
+            let val = syn::parse_str::<Lit>(val)
+                .map(|lit| lit.to_token_stream())
+                .unwrap_or_else(|_| quote! { () });
             quote! {
                 .default(#val)
             }
@@ -416,9 +448,13 @@ impl Function {
         let output = self.output.as_ref().map(|(ty, nullable)| {
             let ty: Type = syn::parse_str(ty).unwrap();
 
+            let ty = quote! { <#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE };
+
             // TODO allow reference returns?
-            quote! {
-                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE, false, #nullable)
+            if *nullable {
+                quote! { .returns_nullable(#ty) }
+            } else {
+                quote! { .returns(#ty) }
             }
         });
 
@@ -430,3 +466,32 @@ impl Function {
         }
     }
 }
+
+/// Returns whether the given type is an `Option`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) => {
+            matches!(path.segments.last(), Some(seg) if seg.ident == "Option")
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arg;
+
+    fn return_type(ty: &str) -> String {
+        let ty = syn::parse_str(ty).unwrap();
+        Arg::from_type("", &ty, None, true).unwrap().ty
+    }
+
+    #[test]
+    fn test_result_return_type() {
+        assert_eq!(return_type("PhpResult"), "()");
+        assert_eq!(return_type("PhpResult<'static>"), "()");
+        assert_eq!(return_type("PhpResult<i64>"), "i64");
+        assert_eq!(return_type("PhpResult<'static, String>"), "String");
+        assert_eq!(return_type("Result<String, PhpException>"), "String");
+    }
+}
@@ -111,10 +111,21 @@ pub fn parser(args: AttributeArgs, input: ItemImpl) -> Result<TokenStream> {
         .into_iter()
         .map(|item| {
             Ok(match item {
-                syn::ImplItem::Const(constant) => {
+                syn::ImplItem::Const(mut constant) => {
+                    let mut visibility = Visibility::Public;
+
+                    for attr in constant.attrs.iter() {
+                        match parse_attribute(attr)? {
+                            ParsedAttribute::Visibility(vis) => visibility = vis,
+                            _ => bail!("Only visibility attributes are valid on constants."),
+                        }
+                    }
+
+                    constant.attrs.clear();
+
                     class.constants.push(Constant {
                         name: constant.ident.to_string(),
-                        // visibility: Visibility::Public,
+                        visibility,
                         value: constant.expr.to_token_stream().to_string(),
                     });
 
@@ -221,9 +221,13 @@ impl Method {
         let output = self.output.as_ref().map(|(ty, nullable)| {
             let ty: Type = syn::parse_str(ty).unwrap();
 
+            let ty = quote! { <#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE };
+
             // TODO allow reference returns?
-            quote! {
-                .returns(<#ty as ::ext_php_rs::php::types::zval::IntoZval>::TYPE, false, #nullable)
+            if *nullable {
+                quote! { .returns_nullable(#ty) }
+            } else {
+                quote! { .returns(#ty) }
             }
         });
 
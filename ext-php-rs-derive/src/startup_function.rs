@@ -59,7 +59,8 @@ fn build_classes(classes: &HashMap<String, Class>) -> Result<Vec<TokenStream>> {
             let constants = class.constants.iter().map(|constant| {
                 let name = &constant.name;
                 let val = constant.val_tokens();
-                quote! { .constant(#name, #val).unwrap() }
+                let flags = constant.get_flags();
+                quote! { .constant_flags(#name, #val, #flags).unwrap() }
             });
             let parent = {
                 if let Some(parent) = &class.parent {
@@ -1,6 +1,6 @@
 //! Error and result types returned from the library functions.
 
-use std::{error::Error as ErrorTrait, ffi::NulError, fmt::Display};
+use std::{convert::Infallible, error::Error as ErrorTrait, ffi::NulError, fmt::Display};
 
 use crate::php::{
     enums::DataType,
@@ -18,9 +18,10 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     /// An incorrect number of arguments was given to a PHP function.
     ///
-    /// The enum carries two integers - the first representing the minimum
-    /// number of arguments expected, and the second representing the number of
-    /// arguments that were received.
+    /// The enum carries two integers - the first representing the number of
+    /// arguments that were received, and the second representing the bound
+    /// that was not met: the minimum number of arguments expected if too few
+    /// were received, or the maximum if too many were received.
     IncorrectArguments(u32, u32),
     /// There was an error converting a Zval into a primitive type.
     ///
@@ -50,6 +51,53 @@ pub enum Error {
     InvalidException(ClassFlags),
     /// Converting integer arguments resulted in an overflow.
     IntegerOverflow,
+    /// The INI entries could not be registered.
+    IniRegistration,
+    /// An exception was thrown inside a called PHP function and has not been caught.
+    Exception,
+    /// The class could not be instantiated, as it is abstract, an interface or a trait.
+    ///
+    /// The enum carries the flags of the class.
+    Instantiation(ClassFlags),
+    /// An interface was built with an object override, which interfaces cannot have as they
+    /// cannot be instantiated.
+    InterfaceObjectOverride,
+    /// A method with a body was added to an interface, which can only declare abstract methods.
+    ///
+    /// The enum carries the name of the method.
+    InterfaceMethodBody(String),
+    /// The method called on an object does not exist.
+    ///
+    /// The enum carries the name of the class and the name of the method.
+    InvalidMethod(String, String),
+    /// A Zval was not of the type required to convert it into a Rust type.
+    ///
+    /// The enum carries the data type of the Zval, followed by the expected data type.
+    ZvalTypeMismatch(DataType, DataType),
+    /// A value could not be serialized into or deserialized from a Zval.
+    ///
+    /// The enum carries the error message.
+    Serialization(String),
+    /// A variadic argument was added to a function before another argument. Variadic arguments
+    /// must be the last argument of a function.
+    ///
+    /// The enum carries the name of the function.
+    VariadicArgument(String),
+    /// A string was not valid UTF-8.
+    ///
+    /// The enum carries the offset of the first byte of the string which is not valid UTF-8.
+    InvalidUtf8(usize),
+    /// Arguments were passed by name to a variadic function which do not match any of its
+    /// parameters.
+    UnknownNamedArgument,
+    /// A Zval did not hold one of the values accepted by the type it was converted into.
+    ///
+    /// The enum carries the given value, followed by the accepted values.
+    InvalidValue(String, Vec<String>),
+    /// A class could not be found, i.e. as the extension declaring it is not loaded.
+    ///
+    /// The enum carries the name of the class.
+    UnknownClass(String),
 }
 
 impl Display for Error {
@@ -57,8 +105,10 @@ impl Display for Error {
         match self {
             Error::IncorrectArguments(n, expected) => write!(
                 f,
-                "Expected at least {} arguments, got {} arguments.",
-                expected, n
+                "Expected {} {} arguments, got {} arguments.",
+                if n < expected { "at least" } else { "at most" },
+                expected,
+                n
             ),
             Error::ZvalConversion(ty) => write!(
                 f,
@@ -83,6 +133,42 @@ impl Display for Error {
             Error::IntegerOverflow => {
                 write!(f, "Converting integer arguments resulted in an overflow.")
             }
+            Error::IniRegistration => write!(f, "Failed to register INI entries."),
+            Error::Exception => write!(f, "An exception was thrown by the called function."),
+            Error::Instantiation(flags) => {
+                write!(f, "Class could not be instantiated: {:?}", flags)
+            }
+            Error::InterfaceObjectOverride => {
+                write!(f, "Interfaces cannot override the creation of objects.")
+            }
+            Error::InterfaceMethodBody(name) => {
+                write!(f, "Interface method `{}` cannot have a body.", name)
+            }
+            Error::InvalidMethod(class, method) => {
+                write!(f, "Call to undefined method {}::{}().", class, method)
+            }
+            Error::ZvalTypeMismatch(actual, expected) => {
+                write!(f, "Expected Zval of type {}, got {}.", expected, actual)
+            }
+            Error::Serialization(msg) => write!(f, "{}", msg),
+            Error::VariadicArgument(name) => write!(
+                f,
+                "Only the last argument of function `{}` can be variadic.",
+                name
+            ),
+            Error::InvalidUtf8(offset) => write!(
+                f,
+                "String is not valid UTF-8, with an invalid byte at offset {}.",
+                offset
+            ),
+            Error::UnknownNamedArgument => write!(f, "Unknown named argument given."),
+            Error::InvalidValue(value, valid) => write!(
+                f,
+                "Invalid value {}, expected one of: {}.",
+                value,
+                valid.join(", ")
+            ),
+            Error::UnknownClass(name) => write!(f, "Class {} does not exist.", name),
         }
     }
 }
@@ -95,6 +181,12 @@ impl From<NulError> for Error {
     }
 }
 
+impl From<Infallible> for Error {
+    fn from(err: Infallible) -> Self {
+        match err {}
+    }
+}
+
 impl<'a> From<Error> for PhpException<'a> {
     fn from(err: Error) -> Self {
         Self::default(err.to_string())
@@ -215,6 +215,22 @@ pub use ext_php_rs_derive::php_extern;
 /// # }
 /// ```
 ///
+/// The function can be registered under a different name, which may be namespaced, by passing
+/// the `name` option. The name must not start with a backslash. This function is called as
+/// `\Acme\Math\gcd()` from PHP, and reflection reports its name as `Acme\Math\gcd`:
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// #[php_function(name = "Acme\\Math\\gcd")]
+/// pub fn gcd(a: i64, b: i64) -> i64 {
+///     if b == 0 { a.abs() } else { gcd(b, a % b) }
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
 /// [`Result<T, E>`]: std::result::Result
 /// [`FunctionBuilder`]: crate::php::function::FunctionBuilder
 /// [`FromZval`]: crate::php::types::zval::FromZval
@@ -341,6 +357,11 @@ pub use ext_php_rs_derive::php_module;
 ///
 /// * `name` - The name of the exported class, if it is different from the Rust struct name. This
 ///    can be useful for namespaced classes, as you cannot place backslashes in Rust struct names.
+/// * `property_handler` - Intercepts property access on objects of the class through the
+///    [`PropertyHandler`] trait, which must be implemented on the struct.
+/// * `clone` - Allows objects of the class to be cloned with `clone $obj`, cloning the Rust
+///    struct into the new object. The struct must implement [`Clone`]. Without this argument,
+///    cloning an object of the class throws an `Error`.
 ///
 /// Any struct that uses this attribute can also provide an optional set of extra attributes, used
 /// to modify the class. These attributes must be used **underneath** this attribute, as they are
@@ -378,6 +399,34 @@ pub use ext_php_rs_derive::php_module;
 /// }
 /// ```
 ///
+/// Compute the `$obj->answer` property from Rust:
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// use ext_php_rs::php::types::{
+///     object::PropertyHandler,
+///     zval::{IntoZval, Zval},
+/// };
+///
+/// #[php_class(property_handler)]
+/// #[derive(Default)]
+/// pub struct Computed;
+///
+/// impl PropertyHandler for Computed {
+///     fn read_property(&mut self, name: &str) -> Option<Zval> {
+///         match name {
+///             "answer" => 42.into_zval(false).ok(),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// #[php_module]
+/// pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+///     module
+/// }
+/// ```
+///
 /// Create a custom exception `RedisException` inside the namespace `Redis\Exception`:
 ///
 /// ```
@@ -400,6 +449,8 @@ pub use ext_php_rs_derive::php_module;
 ///     module
 /// }
 /// ```
+///
+/// [`PropertyHandler`]: crate::php::types::object::PropertyHandler
 pub use ext_php_rs_derive::php_class;
 
 /// Annotates a function that will be called by PHP when the module starts up. Generally used to
@@ -430,6 +481,63 @@ pub use ext_php_rs_derive::php_class;
 /// ```
 pub use ext_php_rs_derive::php_startup;
 
+/// Derives [`FromZval`] and [`TryFrom<&Zval>`] on an enum whose variants have no fields, allowing
+/// the enum to be used as a parameter which only accepts a fixed set of values.
+///
+/// By default, the enum is backed by strings, and each variant is parsed from its name in
+/// `snake_case`. The string of a variant can be changed with the `#[rename("...")]` attribute.
+/// If any of the variants is given an explicit discriminant, the enum is backed by integers
+/// instead, and each variant is parsed from its discriminant.
+///
+/// Parameters of a function exported with [`macro@php_function`] which are not one of the
+/// accepted values throw an exception. Converting the zval with [`TryFrom`] returns an
+/// [`Error::InvalidValue`] instead, whose message lists the accepted values, i.e.
+/// `Invalid value "append", expected one of: "read", "write".`.
+///
+/// # Example
+///
+/// ```
+/// # use ext_php_rs::prelude::*;
+/// use std::convert::TryFrom;
+///
+/// use ext_php_rs::php::{exceptions::PhpResult, types::zval::Zval};
+///
+/// #[derive(FromZval)]
+/// pub enum Mode {
+///     Read,
+///     Write,
+///     #[rename("rw")]
+///     ReadWrite,
+/// }
+///
+/// #[derive(FromZval)]
+/// pub enum Level {
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// #[php_function]
+/// pub fn open(mode: Mode, level: Level) -> bool {
+///     matches!(mode, Mode::Write | Mode::ReadWrite) && matches!(level, Level::High)
+/// }
+///
+/// #[php_function]
+/// pub fn open_checked(mode: &Zval) -> PhpResult<'static, bool> {
+///     // Throws an exception listing the accepted values.
+///     let mode = Mode::try_from(mode)?;
+///     Ok(matches!(mode, Mode::Read))
+/// }
+/// # #[php_module]
+/// # pub fn module(module: ModuleBuilder) -> ModuleBuilder {
+/// #     module
+/// # }
+/// ```
+///
+/// [`FromZval`]: crate::php::types::zval::FromZval
+/// [`TryFrom<&Zval>`]: std::convert::TryFrom
+/// [`Error::InvalidValue`]: crate::errors::Error::InvalidValue
+pub use ext_php_rs_derive::FromZval;
+
 /// A module typically glob-imported containing the typically required macros and imports.
 pub mod prelude {
     pub use crate::php::module::ModuleBuilder;
@@ -437,6 +545,9 @@ pub mod prelude {
     #[cfg(any(docs, feature = "closure"))]
     #[cfg_attr(docs, doc(cfg(feature = "closure")))]
     pub use crate::php::types::closure::Closure;
+    #[cfg(any(docs, feature = "iterator"))]
+    #[cfg_attr(docs, doc(cfg(feature = "iterator")))]
+    pub use crate::php::types::iterator::PhpIterator;
     pub use crate::php_class;
     pub use crate::php_const;
     pub use crate::php_extern;
@@ -444,4 +555,5 @@ pub mod prelude {
     pub use crate::php_impl;
     pub use crate::php_module;
     pub use crate::php_startup;
+    pub use crate::FromZval;
 }
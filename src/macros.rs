@@ -19,25 +19,121 @@ macro_rules! info_table_end {
     };
 }
 
-/// Sets the header for the PHP extension information table. Takes as many string arguments as required.
+/// Sets the header for the PHP extension information table. Takes as many string arguments as
+/// required, which can be any value convertible into a [`CString`](std::ffi::CString), such as a
+/// [`String`] computed at runtime.
+///
+/// # Panics
+///
+/// Panics if any of the values contain a NUL byte. Use [`try_info_table_header!`] to handle the
+/// error instead.
 #[macro_export]
 macro_rules! info_table_header {
+    ($($element:expr),*) => {
+        $crate::try_info_table_header!($($element),*)
+            .expect("Info table values must not contain NUL bytes.")
+    };
+}
+
+/// Sets the header for the PHP extension information table, in the same way as
+/// [`info_table_header!`].
+///
+/// Returns a result, which is an error if any of the values contain a NUL byte. In that case,
+/// nothing is printed.
+#[macro_export]
+macro_rules! try_info_table_header {
     ($($element:expr),*) => {$crate::_info_table_row!(php_info_print_table_header, $($element),*)};
 }
 
-/// Adds a row to the PHP extension information table. Takes as many string arguments as required.
+/// Adds a row to the PHP extension information table. Takes as many string arguments as
+/// required, which can be any value convertible into a [`CString`](std::ffi::CString), such as a
+/// [`String`] computed at runtime.
+///
+/// # Panics
+///
+/// Panics if any of the values contain a NUL byte. Use [`try_info_table_row!`] to handle the
+/// error instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::{info_table_end, info_table_row, info_table_start, php::module::ModuleEntry};
+///
+/// fn library_version() -> String {
+///     format!("{}.{}.{}", 1, 1, 1)
+/// }
+///
+/// pub extern "C" fn php_module_info(_module: *mut ModuleEntry) {
+///     info_table_start!();
+///     info_table_row!("library version", library_version());
+///     info_table_end!();
+/// }
+/// ```
 #[macro_export]
 macro_rules! info_table_row {
+    ($($element:expr),*) => {
+        $crate::try_info_table_row!($($element),*)
+            .expect("Info table values must not contain NUL bytes.")
+    };
+}
+
+/// Adds a row to the PHP extension information table, in the same way as [`info_table_row!`].
+///
+/// Returns a result, which is an error if any of the values contain a NUL byte. In that case,
+/// the row is not printed, rather than being cut off at the NUL byte.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::{
+///     info_table_end, info_table_row, info_table_start, php::module::ModuleEntry,
+///     try_info_table_row,
+/// };
+///
+/// fn linked_version() -> String {
+///     // May contain any bytes, as it is read from a C library.
+///     String::from("1.1.1")
+/// }
+///
+/// pub extern "C" fn php_module_info(_module: *mut ModuleEntry) {
+///     info_table_start!();
+///     if try_info_table_row!("library version", linked_version()).is_err() {
+///         info_table_row!("library version", "unknown");
+///     }
+///     info_table_end!();
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_info_table_row {
     ($($element:expr),*) => {$crate::_info_table_row!(php_info_print_table_row, $($element),*)};
 }
 
 /// INTERNAL: Calls a variadic C function with the number of parameters, then following with the parameters.
+///
+/// Each value is converted into a C string and bound to a variable before the function is called,
+/// so that each value is only evaluated once and the C strings outlive the call.
 #[doc(hidden)]
 #[macro_export]
 macro_rules! _info_table_row {
     ($fn: ident, $($element: expr),*) => {
+        (|| -> $crate::errors::Result<()> {
+            $crate::_info_table_row!(@BIND $fn; []; $($element),*);
+            Ok(())
+        })()
+    };
+
+    // Every expansion introduces a new `value` variable, which is distinct from the variables of
+    // the other expansions due to macro hygiene.
+    (@BIND $fn: ident; [$($bound: ident)*]; $head: expr $(, $tail: expr)*) => {
+        let value = ::std::ffi::CString::new($head)?;
+        $crate::_info_table_row!(@BIND $fn; [$($bound)* value]; $($tail),*);
+    };
+    (@BIND $fn: ident; [$($bound: ident)*];) => {
         unsafe {
-            $crate::bindings::$fn($crate::_info_table_row!(@COUNT; $($element),*) as i32, $(::std::ffi::CString::new($element).unwrap().as_ptr()),*);
+            $crate::bindings::$fn(
+                $crate::_info_table_row!(@COUNT; $($bound),*) as i32,
+                $($bound.as_ptr()),*
+            );
         }
     };
 
@@ -68,6 +164,11 @@ macro_rules! call_user_func {
 
 /// Parses a given list of arguments using the [`ArgParser`](crate::php::args::ArgParser) class.
 ///
+/// Scalar arguments follow the `strict_types` declaration of the calling file, matching PHP's
+/// own functions. Without it, values are coerced into the type of the argument where possible,
+/// i.e. `"5"` is accepted for an integer argument. Use [`parse_args_strict!`] to always reject
+/// values of the wrong type.
+///
 /// # Examples
 ///
 /// This example parses all of the arguments. If one is invalid, execution of the function will
@@ -109,8 +210,103 @@ macro_rules! call_user_func {
 ///     parse_args!(execute_data, x, y; z);
 /// }
 /// ```
+///
+/// Variadic arguments can be collected into a vector of [`Zval`] references by prefixing the
+/// vector with `...` after the semicolon. The vector will be empty if no trailing arguments were
+/// passed to the function.
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args,
+///    php::{args::Arg, enums::DataType, execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, _: &mut Zval) {
+///     let mut fmt = Arg::new("fmt", DataType::String);
+///     let mut rest = vec![];
+///
+///     parse_args!(execute_data, fmt; ...rest);
+/// }
+/// ```
+///
+/// Arguments passed by reference can be modified in place, with the changes reflected in the
+/// caller's variable. This differs from setting the return value, which leaves the caller's
+/// variable untouched. The example below sorts an array of integers in place.
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args,
+///    php::{args::Arg, enums::DataType, execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, _: &mut Zval) {
+///     let mut arr = Arg::new("arr", DataType::Array).by_ref();
+///
+///     parse_args!(execute_data, arr);
+///
+///     if let Some(mut arr) = arr.zval_mut().and_then(|zv| zv.array_mut()) {
+///         let mut values: Vec<i64> = arr.iter().filter_map(|(_, v)| v.long()).collect();
+///         values.sort_unstable();
+///
+///         arr.clear();
+///         for val in values {
+///             let _ = arr.push(val);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Arguments of the `mixed` type are not coerced, so the zval passed to the argument can be
+/// retrieved as-is with [`Arg::zval`] and inspected. The example below returns the type of its
+/// argument, similar to `gettype()`. Arguments passed by reference (see [`Arg::by_ref`]) are
+/// retrieved as the reference itself, which can be detected with [`Zval::is_reference`]; the
+/// other `is_*` predicates check the type of the referenced value.
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args,
+///    php::{args::Arg, enums::DataType, execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, retval: &mut Zval) {
+///     let mut value = Arg::new("value", DataType::Mixed);
+///
+///     parse_args!(execute_data, value);
+///
+///     let name = match value.zval() {
+///         Some(zv) if zv.is_null() => "NULL",
+///         Some(zv) if zv.is_bool() => "boolean",
+///         Some(zv) if zv.is_long() => "integer",
+///         Some(zv) if zv.is_double() => "double",
+///         Some(zv) if zv.is_string() => "string",
+///         Some(zv) if zv.is_array() => "array",
+///         Some(zv) if zv.is_object() => "object",
+///         Some(zv) if zv.is_resource() => "resource",
+///         _ => "unknown type",
+///     };
+///
+///     let _ = retval.set_string(name, false);
+/// }
+/// ```
+///
+/// [`Zval`]: crate::php::types::zval::Zval
+/// [`Zval::is_reference`]: crate::php::types::zval::Zval::is_reference
+/// [`Arg::zval`]: crate::php::args::Arg::zval
+/// [`Arg::by_ref`]: crate::php::args::Arg::by_ref
 #[macro_export]
 macro_rules! parse_args {
+    ($ed: expr, $($arg: expr),* ; ... $rest: expr) => {{
+        use $crate::php::args::ArgParser;
+
+        let parser = ArgParser::new($ed)
+            $(.arg(&mut $arg))*
+            .variadic(&mut $rest)
+            .parse();
+        if parser.is_err() {
+            return;
+        }
+    }};
+
     ($ed: expr, $($arg: expr),*) => {{
         use $crate::php::args::ArgParser;
 
@@ -136,11 +332,173 @@ macro_rules! parse_args {
     }};
 }
 
+/// Parses a given list of arguments in strict mode, regardless of the `strict_types` declaration
+/// of the calling file. Scalar arguments are not coerced, so passing `"5"` or `5.5` to an integer
+/// argument throws a `TypeError`. The only exception is integers, which are accepted for float
+/// arguments, as in PHP.
+///
+/// Takes the same arguments as [`parse_args!`].
+///
+/// # Examples
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args_strict,
+///    php::{args::Arg, enums::DataType, execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, _: &mut Zval) {
+///     let mut count = Arg::new("count", DataType::Long);
+///
+///     // `example_fn(1.5)` throws a `TypeError` instead of truncating to `1`.
+///     parse_args_strict!(execute_data, count);
+/// }
+/// ```
+#[macro_export]
+macro_rules! parse_args_strict {
+    ($ed: expr, $($arg: expr),* ; ... $rest: expr) => {{
+        use $crate::php::args::ArgParser;
+
+        let parser = ArgParser::new($ed)
+            .strict(true)
+            $(.arg(&mut $arg))*
+            .variadic(&mut $rest)
+            .parse();
+        if parser.is_err() {
+            return;
+        }
+    }};
+
+    ($ed: expr, $($arg: expr),*) => {{
+        use $crate::php::args::ArgParser;
+
+        let parser = ArgParser::new($ed)
+            .strict(true)
+            $(.arg(&mut $arg))*
+            .parse();
+        if parser.is_err() {
+            return;
+        }
+    }};
+
+    ($ed: expr, $($arg: expr),* ; $($opt: expr),*) => {{
+        use $crate::php::args::ArgParser;
+
+        let parser = ArgParser::new($ed)
+            .strict(true)
+            $(.arg(&mut $arg))*
+            .not_required()
+            $(.arg(&mut $opt))*
+            .parse();
+        if parser.is_err() {
+            return;
+        }
+    }};
+}
+
+/// Parses a given list of arguments into Rust values, returning a [`PhpResult`] rather than
+/// throwing when the arguments are invalid. Each argument is given as `name: Type`, where the
+/// type implements [`FromZval`] and determines the PHP type of the argument (see
+/// [`Arg::typed`]). Arguments following a semicolon are optional, and are returned as an
+/// [`Option`] which is [`None`] when the argument was not passed.
+///
+/// The macro evaluates to a `PhpResult<(T1, T2, ...)>`. On failure, the error is the exception
+/// PHP would have thrown, i.e. a `TypeError` naming the function and the argument which could
+/// not be parsed, which can be propagated with `?` or thrown with [`PhpException::throw`].
+///
+/// [`PhpResult`]: crate::php::exceptions::PhpResult
+/// [`PhpException::throw`]: crate::php::exceptions::PhpException::throw
+/// [`FromZval`]: crate::php::types::zval::FromZval
+/// [`Arg::typed`]: crate::php::args::Arg::typed
+///
+/// # Examples
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args_checked,
+///    php::{execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, retval: &mut Zval) {
+///     match parse_args_checked!(execute_data, x: i64, y: i64; z: i64) {
+///         Ok((x, y, z)) => retval.set_long(x + y + z.unwrap_or(0)),
+///         // i.e. `example_fn(): Argument #2 ($y) must be of type int, array given`
+///         Err(e) => {
+///             let _ = e.throw();
+///         }
+///     }
+/// }
+/// ```
+///
+/// The exception can also be replaced with one describing the failure in terms of the function:
+///
+/// ```
+/// use ext_php_rs::{
+///    parse_args_checked,
+///    php::{class::ClassEntry, exceptions::PhpException, execution_data::ExecutionData, types::zval::Zval},
+/// };
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, retval: &mut Zval) {
+///     let name = match parse_args_checked!(execute_data, name: String) {
+///         Ok((name,)) => name,
+///         Err(_) => {
+///             let ex = PhpException::new("A name must be given".into(), 0, ClassEntry::type_error());
+///             let _ = ex.throw();
+///             return;
+///         }
+///     };
+///
+///     let _ = retval.set_string(&format!("Hello, {}!", name), false);
+/// }
+/// ```
+#[macro_export]
+macro_rules! parse_args_checked {
+    ($ed: expr, $($arg: ident: $ty: ty),*) => {{
+        use $crate::php::args::{Arg, ArgParser};
+
+        $(let mut $arg = Arg::typed::<$ty>(stringify!($arg));)*
+
+        ArgParser::new($ed)
+            $(.arg(&mut $arg))*
+            .try_parse()
+            .and_then(|_| Ok(($($arg.try_val::<$ty>()?,)*)))
+    }};
+
+    ($ed: expr, $($arg: ident: $ty: ty),* ; $($opt: ident: $opt_ty: ty),*) => {{
+        use $crate::php::args::{Arg, ArgParser};
+
+        $(let mut $arg = Arg::typed::<$ty>(stringify!($arg));)*
+        $(let mut $opt = Arg::typed::<$opt_ty>(stringify!($opt));)*
+
+        ArgParser::new($ed)
+            $(.arg(&mut $arg))*
+            .not_required()
+            $(.arg(&mut $opt))*
+            .try_parse()
+            .and_then(|_| {
+                Ok((
+                    $($arg.try_val::<$ty>()?,)*
+                    $(match $opt.zval() {
+                        Some(_) => Some($opt.try_val::<$opt_ty>()?),
+                        None => None,
+                    },)*
+                ))
+            })
+    }};
+}
+
 /// Throws an exception and returns from the current function.
 ///
-/// Wraps the [`throw`] function by inserting a `return` statement after throwing the exception.
+/// Wraps the [`throw`] and [`throw_with_code`] functions by inserting a `return` statement after
+/// throwing the exception. The macro expands to a statement, so it can be used inside functions
+/// returning `()`, such as raw `extern "C"` function handlers.
+///
+/// The exception class can be omitted, in which case a plain `Exception` is thrown. When the
+/// message is a string literal followed by arguments, it is formatted in the same way as
+/// [`format!`]. An integer code can be given with `code = ...` before the message.
 ///
 /// [`throw`]: crate::php::exceptions::throw
+/// [`throw_with_code`]: crate::php::exceptions::throw_with_code
 ///
 /// # Examples
 ///
@@ -156,10 +514,49 @@ macro_rules! parse_args {
 ///     assert!(false); // This will not run.
 /// }
 /// ```
+///
+/// Formatting the message, and setting the class and code of the exception:
+///
+/// ```
+/// use ext_php_rs::{throw, php::{class::ClassEntry, execution_data::ExecutionData, types::zval::Zval}};
+///
+/// pub extern "C" fn example_fn(execute_data: &mut ExecutionData, _: &mut Zval) {
+///     let name = "size";
+///     let size = 100;
+///
+///     if size > 64 {
+///         throw!(ClassEntry::value_error(), code = 2, "{} must be at most 64, got {}", name, size);
+///     }
+///
+///     throw!("invalid argument: {}", name);
+/// }
+/// ```
 #[macro_export]
 macro_rules! throw {
-    ($ex: expr, $reason: expr) => {
-        $crate::php::exceptions::throw($ex, $reason);
-        return;
+    (code = $code: expr, $fmt: literal $(, $arg: expr)* $(,)?) => {
+        $crate::throw!($crate::php::class::ClassEntry::exception(), code = $code, $fmt $(, $arg)*)
+    };
+
+    ($fmt: literal $(, $arg: expr)* $(,)?) => {
+        $crate::throw!($crate::php::class::ClassEntry::exception(), code = 0, $fmt $(, $arg)*)
     };
+
+    ($ex: expr, code = $code: expr, $fmt: literal $(, $arg: expr)* $(,)?) => {{
+        let _ = $crate::php::exceptions::throw_with_code(
+            $ex,
+            $code,
+            &::std::format!($fmt $(, $arg)*),
+        );
+        return;
+    }};
+
+    ($ex: expr, $reason: expr $(,)?) => {{
+        let _ = $crate::php::exceptions::throw($ex, $reason);
+        return;
+    }};
+
+    ($ex: expr, $fmt: literal, $($arg: expr),+ $(,)?) => {{
+        let _ = $crate::php::exceptions::throw($ex, &::std::format!($fmt, $($arg),+));
+        return;
+    }};
 }
@@ -1,7 +1,13 @@
 //! Functions relating to the Zend Memory Manager, used to allocate request-bound memory.
 
-use crate::bindings::{_efree, _emalloc};
-use std::{alloc::Layout, ffi::c_void};
+use crate::bindings::{_efree, _emalloc, _erealloc};
+use std::{
+    alloc::Layout,
+    ffi::c_void,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
 
 /// Uses the PHP memory allocator to allocate request-bound memory.
 ///
@@ -28,6 +34,40 @@ pub fn emalloc(layout: Layout) -> *mut u8 {
     }) as *mut u8
 }
 
+/// Resizes a block of memory which was allocated through the PHP memory manager, returning a
+/// pointer to the resized memory. The contents of the memory are preserved up to the smaller of
+/// the old and new sizes.
+///
+/// # Parameters
+///
+/// * `ptr` - The pointer to the memory to resize.
+/// * `size` - The new size of the memory, in bytes.
+///
+/// # Safety
+///
+/// Caller must guarantee that the given pointer is valid (aligned and non-null) and
+/// was originally allocated through the Zend memory manager. The pointer must not be used after
+/// calling this function, as the memory may have been moved.
+pub unsafe fn erealloc(ptr: *mut u8, size: usize) -> *mut u8 {
+    ({
+        #[cfg(php_debug)]
+        {
+            _erealloc(
+                ptr as *mut c_void,
+                size as _,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                0,
+            )
+        }
+        #[cfg(not(php_debug))]
+        {
+            _erealloc(ptr as *mut c_void, size as _)
+        }
+    }) as *mut u8
+}
+
 /// Frees a given memory pointer which was allocated through the PHP memory manager.
 ///
 /// # Parameters
@@ -54,3 +94,254 @@ pub unsafe fn efree(ptr: *mut u8) {
         _efree(ptr as *mut c_void)
     }
 }
+
+/// A growable byte buffer allocated through the Zend memory manager, intended for scratch space
+/// used while handling a single request.
+///
+/// Allocating through the Zend memory manager is cheaper than going through the system allocator,
+/// and PHP enforces its `memory_limit` on the memory. The buffer is freed when it is dropped, and
+/// PHP frees any memory that is left over at the end of the request.
+///
+/// # Lifetime
+///
+/// The buffer **must not** outlive the request it was created in. The Zend memory manager
+/// releases all request-bound memory when the request ends, so a buffer stored in a static or
+/// carried over to another request points to freed memory, and dropping it frees it a second
+/// time. Create the buffer inside a function called by PHP and drop it before returning, or
+/// store it in an object which is destroyed with the request.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::alloc::ZBuf;
+///
+/// let mut buf = ZBuf::with_capacity(1024 * 1024);
+/// for chunk in 0..1024u32 {
+///     buf.extend_from_slice(&chunk.to_le_bytes());
+/// }
+/// assert_eq!(buf.len(), 4096);
+/// ```
+pub struct ZBuf {
+    ptr: *mut u8,
+    len: usize,
+    cap: usize,
+}
+
+impl ZBuf {
+    /// Creates a new, empty buffer. No memory is allocated until bytes are added to the buffer.
+    pub fn new() -> Self {
+        Self {
+            ptr: ptr::null_mut(),
+            len: 0,
+            cap: 0,
+        }
+    }
+
+    /// Creates a new, empty buffer with space for at least `capacity` bytes.
+    ///
+    /// # Parameters
+    ///
+    /// * `capacity` - The number of bytes to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut buf = Self::new();
+        buf.reserve(capacity);
+        buf
+    }
+
+    /// Returns the number of bytes in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the buffer contains no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Reserves space for at least `additional` more bytes, reallocating the buffer if required.
+    /// The capacity at least doubles when the buffer grows, so repeatedly appending to the buffer
+    /// takes amortized constant time.
+    ///
+    /// # Parameters
+    ///
+    /// * `additional` - The number of additional bytes to reserve space for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    pub fn reserve(&mut self, additional: usize) {
+        let cap = match grown_capacity(self.len, self.cap, additional) {
+            Some(cap) => cap,
+            None => return,
+        };
+
+        // The Zend memory manager bails out of the request when it runs out of memory, rather
+        // than returning a null pointer.
+        self.ptr = if self.ptr.is_null() {
+            emalloc(Layout::from_size_align(cap, 1).expect("ZBuf capacity overflow"))
+        } else {
+            // SAFETY: The pointer is not null, so it was allocated by `emalloc` above.
+            unsafe { erealloc(self.ptr, cap) }
+        };
+        self.cap = cap;
+    }
+
+    /// Appends a byte to the end of the buffer.
+    ///
+    /// # Parameters
+    ///
+    /// * `byte` - The byte to append.
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends a slice of bytes to the end of the buffer.
+    ///
+    /// # Parameters
+    ///
+    /// * `bytes` - The bytes to append.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        // An empty buffer has no memory to copy into.
+        if bytes.is_empty() {
+            return;
+        }
+
+        self.reserve(bytes.len());
+
+        // SAFETY: Space for the bytes was reserved above, and the slice cannot overlap with the
+        // buffer as the buffer is borrowed mutably.
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr.add(self.len), bytes.len()) };
+        self.len += bytes.len();
+    }
+
+    /// Shortens the buffer to `len` bytes. Has no effect if the buffer is already shorter. The
+    /// capacity of the buffer is unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `len` - The length to shorten the buffer to.
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Removes all bytes from the buffer, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for ZBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for ZBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        if self.ptr.is_null() {
+            &[]
+        } else {
+            // SAFETY: The first `len` bytes of the buffer are initialized.
+            unsafe { slice::from_raw_parts(self.ptr, self.len) }
+        }
+    }
+}
+
+impl DerefMut for ZBuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        if self.ptr.is_null() {
+            &mut []
+        } else {
+            // SAFETY: The first `len` bytes of the buffer are initialized.
+            unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+}
+
+impl Extend<u8> for ZBuf {
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl Debug for ZBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Drop for ZBuf {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { efree(self.ptr) };
+        }
+    }
+}
+
+/// Returns the capacity a buffer of length `len` and capacity `cap` must grow to in order to hold
+/// `additional` more bytes, or [`None`] if the buffer is large enough. The capacity at least
+/// doubles, and is at least 8 bytes.
+///
+/// # Panics
+///
+/// Panics if the required capacity overflows `usize`.
+fn grown_capacity(len: usize, cap: usize, additional: usize) -> Option<usize> {
+    let required = len.checked_add(additional).expect("ZBuf capacity overflow");
+
+    if required <= cap {
+        return None;
+    }
+
+    Some(required.max(cap.saturating_mul(2)).max(8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grown_capacity, ZBuf};
+
+    #[test]
+    fn test_grown_capacity() {
+        assert_eq!(grown_capacity(0, 0, 0), None);
+        assert_eq!(grown_capacity(0, 0, 3), Some(8));
+        assert_eq!(grown_capacity(5, 8, 3), None);
+        assert_eq!(grown_capacity(8, 8, 1), Some(16));
+        assert_eq!(grown_capacity(8, 16, 100), Some(108));
+        assert_eq!(grown_capacity(0, 0, 100), Some(100));
+        assert_eq!(
+            grown_capacity(0, usize::MAX / 2 + 1, usize::MAX),
+            Some(usize::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ZBuf capacity overflow")]
+    fn test_grown_capacity_overflow() {
+        grown_capacity(2, 2, usize::MAX);
+    }
+
+    #[test]
+    fn test_empty() {
+        // None of these allocate, as the buffer stays empty.
+        let mut buf = ZBuf::new();
+        buf.reserve(0);
+        buf.extend_from_slice(b"");
+        buf.truncate(4);
+        buf.clear();
+
+        assert!(buf.is_empty());
+        assert_eq!(buf.capacity(), 0);
+        assert_eq!(&*buf, b"");
+    }
+}
@@ -3,7 +3,10 @@
 use std::{ffi::CString, ptr};
 
 use super::{
+    class::ClassEntry,
+    constants::IntoConst,
     enums::DataType,
+    exceptions::{PhpException, PhpResult},
     execution_data::ExecutionData,
     types::{
         zval::{FromZval, IntoZvalDyn, Zval},
@@ -15,9 +18,13 @@ use crate::{
     bindings::{
         _zend_expected_type, _zend_expected_type_Z_EXPECTED_ARRAY,
         _zend_expected_type_Z_EXPECTED_BOOL, _zend_expected_type_Z_EXPECTED_DOUBLE,
-        _zend_expected_type_Z_EXPECTED_LONG, _zend_expected_type_Z_EXPECTED_OBJECT,
-        _zend_expected_type_Z_EXPECTED_RESOURCE, _zend_expected_type_Z_EXPECTED_STRING,
-        zend_internal_arg_info, zend_wrong_parameters_count_error,
+        _zend_expected_type_Z_EXPECTED_ITERABLE, _zend_expected_type_Z_EXPECTED_LONG,
+        _zend_expected_type_Z_EXPECTED_OBJECT, _zend_expected_type_Z_EXPECTED_RESOURCE,
+        _zend_expected_type_Z_EXPECTED_STRING, zend_internal_arg_info, zend_parse_arg_bool_weak,
+        zend_parse_arg_double_weak, zend_parse_arg_long_weak, zend_parse_arg_str_weak,
+        zend_unexpected_extra_named_error, zend_wrong_parameter_class_error,
+        zend_wrong_parameter_class_or_null_error, zend_wrong_parameter_type_error,
+        zend_wrong_parameters_count_error,
     },
     errors::{Error, Result},
 };
@@ -39,7 +46,8 @@ impl<'a> Arg<'a> {
     ///
     /// # Parameters
     ///
-    /// * `name` - The name of the parameter.
+    /// * `name` - The name of the parameter, which is also used to pass the argument by name,
+    /// i.e. `greet(name: "Bo")`.
     /// * `_type` - The type of the parameter.
     pub fn new<T: Into<String>>(name: T, _type: DataType) -> Self {
         Arg {
@@ -53,9 +61,52 @@ impl<'a> Arg<'a> {
         }
     }
 
+    /// Creates a new argument with the type of the Rust value it is converted into, taken from the
+    /// [`FromZval`] implementation of the type. This keeps the type declared to PHP in sync with
+    /// the value retrieved with [`val()`](#method.val). Types which do not correspond to a single
+    /// PHP type, such as `&Zval`, are declared as `mixed`.
+    ///
+    /// Types can declare their PHP type by implementing [`FromZval`], which is done for classes
+    /// exported with the [`macro@crate::php_class`] macro. Note that [`Option<T>`] has the same
+    /// type as `T`, so [`allow_null()`](#method.allow_null) must be called to accept `null`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ext_php_rs::php::args::Arg;
+    ///
+    /// // Declared as `array $ids`.
+    /// let ids = Arg::typed::<Vec<i64>>("ids");
+    /// ```
+    pub fn typed<T: FromZval<'a>>(name: &str) -> Self {
+        Self::new(name, T::TYPE)
+    }
+
+    /// Creates a new variadic argument. A variadic argument collects all of the remaining
+    /// arguments passed to the function, and must be the last argument of the function.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the parameter.
+    /// * `_type` - The type of each of the values passed to the parameter.
+    pub fn new_variadic<T: Into<String>>(name: T, _type: DataType) -> Self {
+        Self::new(name, _type).is_variadic()
+    }
+
     /// Sets the argument as a reference.
     #[allow(clippy::wrong_self_convention)]
-    pub fn as_ref(mut self) -> Self {
+    pub fn as_ref(self) -> Self {
+        self.by_ref()
+    }
+
+    /// Sets the argument as passed by reference. The argument can then be modified through
+    /// [`zval_mut()`](#method.zval_mut), with the changes reflected in the variable passed by the
+    /// caller.
+    pub fn by_ref(mut self) -> Self {
         self.as_ref = true;
         self
     }
@@ -72,9 +123,68 @@ impl<'a> Arg<'a> {
         self
     }
 
-    /// Sets the default value for the argument.
-    pub fn default<T: Into<String>>(mut self, default: T) -> Self {
-        self.default_value = Some(default.into());
+    /// Restricts the argument to objects which are instances of the given class, i.e. the
+    /// `DateTime` in `function f(DateTime $d)`. The class is used in the type of the argument
+    /// shown through reflection, and the argument parser throws a `TypeError` when an object of
+    /// another class is passed. Combine with [`allow_null()`](#method.allow_null) to also accept
+    /// `null`.
+    ///
+    /// # Parameters
+    ///
+    /// * `class` - The name of the class or interface, which can be namespaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ext_php_rs::php::{args::Arg, enums::DataType};
+    ///
+    /// let date = Arg::new("d", DataType::Object(None)).class("DateTimeInterface");
+    /// ```
+    pub fn class(mut self, class: &'static str) -> Self {
+        self._type = DataType::Object(Some(class));
+        self
+    }
+
+    /// Sets the default value for the argument. The default is shown through reflection and
+    /// should match the value used when the argument is omitted. Note that the argument must also
+    /// be marked as not required (see [`FunctionBuilder::not_required()`]) to be omittable.
+    ///
+    /// # Parameters
+    ///
+    /// * `default` - The default value of the argument, i.e. an integer, float, boolean, string
+    /// or `()` for `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ext_php_rs::{
+    ///     parse_args,
+    ///     php::{
+    ///         args::Arg, enums::DataType, execution_data::ExecutionData,
+    ///         function::FunctionBuilder, types::zval::Zval,
+    ///     },
+    /// };
+    ///
+    /// pub extern "C" fn greet(ex: &mut ExecutionData, retval: &mut Zval) {
+    ///     let mut name = Arg::new("name", DataType::String);
+    ///     let mut title = Arg::new("title", DataType::String).default("none");
+    ///
+    ///     parse_args!(ex, name; title);
+    ///
+    ///     let title: String = title.val().unwrap_or_else(|| "none".into());
+    ///     // ...
+    /// }
+    ///
+    /// let function = FunctionBuilder::new("greet", greet)
+    ///     .arg(Arg::new("name", DataType::String))
+    ///     .not_required()
+    ///     .arg(Arg::new("title", DataType::String).default("none"))
+    ///     .build();
+    /// ```
+    ///
+    /// [`FunctionBuilder::not_required()`]: crate::php::function::FunctionBuilder::not_required
+    pub fn default<T: IntoConst>(mut self, default: T) -> Self {
+        self.default_value = Some(default.as_default());
         self
     }
 
@@ -85,7 +195,53 @@ impl<'a> Arg<'a> {
         self.zval.and_then(|zv| T::from_zval(zv))
     }
 
-    /// Attempts to return a reference to the arguments internal Zval.
+    /// Retrieves the value of the argument, returning an exception describing why the value could
+    /// not be retrieved rather than [`None`]. The exception can be propagated with `?` from
+    /// functions returning a [`PhpResult`], where it is thrown.
+    ///
+    /// # Errors
+    ///
+    /// * `ArgumentCountError` - The argument was not passed, or the arguments have not been
+    /// parsed.
+    /// * `TypeError` - The argument is not of the type of `T`.
+    /// * `ValueError` - The argument is of the type of `T`, but its value could not be converted,
+    /// i.e. an integer which does not fit into a `u8`.
+    pub fn try_val<T: FromZval<'a>>(&self) -> PhpResult<'static, T> {
+        let zval = self.zval.ok_or_else(|| {
+            PhpException::new(
+                format!("Argument ${} was not passed", self.name),
+                0,
+                ClassEntry::argument_count_error(),
+            )
+        })?;
+
+        T::from_zval(zval).ok_or_else(|| {
+            let expected = Self::new("", T::TYPE).type_name();
+
+            if zval.dereference().get_type().ok() == Some(T::TYPE) {
+                PhpException::new(
+                    format!("Argument ${} must be a valid {}", self.name, expected),
+                    0,
+                    ClassEntry::value_error(),
+                )
+            } else {
+                PhpException::new(
+                    format!(
+                        "Argument ${} must be of type {}, {} given",
+                        self.name,
+                        expected,
+                        zval_type_name(zval)
+                    ),
+                    0,
+                    ClassEntry::type_error(),
+                )
+            }
+        })
+    }
+
+    /// Attempts to return a reference to the arguments internal Zval. The zval is returned as
+    /// passed, without converting it, so arguments passed by reference are returned as the
+    /// reference itself.
     ///
     /// # Returns
     ///
@@ -95,10 +251,22 @@ impl<'a> Arg<'a> {
         self.zval
     }
 
+    /// Attempts to return a mutable reference to the variable passed to an argument that was
+    /// passed by reference (see [`by_ref()`](#method.by_ref)). Any modifications made to the zval
+    /// are visible to the caller once the function returns.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&mut Zval)` - The variable referenced by the argument.
+    /// * `None` - The argument was empty or was not passed by reference.
+    pub fn zval_mut(&mut self) -> Option<&'a mut Zval> {
+        self.zval.and_then(|zv| zv.reference())
+    }
+
     /// Attempts to call the argument as a callable with a list of arguments to pass to the function.
-    /// Note that a thrown exception inside the callable is not detectable, therefore you should
-    /// check if the return value is valid rather than unwrapping. Returns a result containing the
-    /// return value of the function, or an error.
+    /// Returns a result containing the return value of the function, or an error. See
+    /// [`Callable::try_call`](crate::php::types::callable::Callable::try_call) for how thrown
+    /// exceptions are handled.
     ///
     /// You should not call this function directly, rather through the [`call_user_func`] macro.
     ///
@@ -109,8 +277,130 @@ impl<'a> Arg<'a> {
         self.zval().ok_or(Error::Callable)?.try_call(params)
     }
 
-    /// Returns the internal PHP argument info.
-    pub(crate) fn as_arg_info(&self) -> Result<ArgInfo> {
+    /// Coerces the value passed to the argument into the type of the argument, following PHP's
+    /// rules for scalar parameters. In coercive mode, scalar values are converted into each other
+    /// using the same rules as PHP's own functions. In strict mode, the only conversion allowed
+    /// is from an integer to a float.
+    ///
+    /// Objects passed to an argument restricted to a class are checked to be an instance of the
+    /// class, but are otherwise left untouched.
+    ///
+    /// Returns `false` if the value is a scalar of the wrong type which could not be coerced, or
+    /// is not an instance of the class of the argument.
+    ///
+    /// # Parameters
+    ///
+    /// * `zval` - The value passed to the argument, which is replaced by the coerced value.
+    /// * `strict` - Whether the argument is parsed in strict mode.
+    fn coerce(&self, zval: &mut Zval, strict: bool) -> bool {
+        if self.as_ref || (self.allow_null && zval.is_null()) {
+            return true;
+        }
+
+        // Assigning the coerced value drops the previous value, releasing it if it was a string.
+        let mut coerced = Zval::new();
+
+        match self._type {
+            DataType::Long if !zval.is_long() => {
+                let mut val = 0;
+                if strict || !unsafe { zend_parse_arg_long_weak(zval, &mut val) } {
+                    return false;
+                }
+                coerced.set_long(val);
+            }
+            DataType::Double if !zval.is_double() => {
+                let mut val = 0.0;
+                match zval.long() {
+                    Some(long) => val = long as f64,
+                    None if strict => return false,
+                    None => {
+                        if !unsafe { zend_parse_arg_double_weak(zval, &mut val) } {
+                            return false;
+                        }
+                    }
+                }
+                coerced.set_double(val);
+            }
+            DataType::Bool | DataType::False | DataType::True if !zval.is_bool() => {
+                let mut val = false;
+                if strict || !unsafe { zend_parse_arg_bool_weak(zval, &mut val) } {
+                    return false;
+                }
+                coerced.set_bool(val);
+            }
+            DataType::String if !zval.is_string() => {
+                // Strings are converted in place.
+                let mut val = ptr::null_mut();
+                return !strict && unsafe { zend_parse_arg_str_weak(zval, &mut val) };
+            }
+            DataType::Object(Some(class)) => {
+                return match (zval.object(), ClassEntry::try_find(class)) {
+                    (Some(obj), Some(ce)) => {
+                        unsafe { obj.ce.as_ref() }.map_or(false, |obj_ce| obj_ce.instance_of(ce))
+                    }
+                    _ => false,
+                };
+            }
+            DataType::Iterable => {
+                return zval.is_array()
+                    || zval
+                        .object()
+                        .zip(ClassEntry::traversable())
+                        .map_or(false, |(obj, ce)| obj.instance_of(ce));
+            }
+            _ => return true,
+        }
+
+        *zval = coerced;
+        true
+    }
+
+    /// Returns the type of the argument as written in a PHP type declaration, i.e. `?int`.
+    fn type_name(&self) -> String {
+        let name = match &self._type {
+            DataType::Null | DataType::Undef => "null",
+            DataType::False => "false",
+            DataType::True | DataType::Bool => "bool",
+            DataType::Long => "int",
+            DataType::Double => "float",
+            DataType::String => "string",
+            DataType::Array => "array",
+            DataType::Object(Some(class)) => *class,
+            DataType::Object(None) => "object",
+            DataType::Resource => "resource",
+            DataType::Callable => "callable",
+            DataType::Iterable => "iterable",
+            DataType::Void => "void",
+            _ => "mixed",
+        };
+
+        if self.allow_null && name != "mixed" && name != "null" {
+            format!("?{}", name)
+        } else {
+            name.into()
+        }
+    }
+
+    /// Returns whether the argument is variadic.
+    pub(crate) fn variadic(&self) -> bool {
+        self.variadic
+    }
+
+    /// Converts the argument into the argument information stored in the function entry.
+    ///
+    /// Optional nullable arguments without a default value are given a default of `null`. When
+    /// arguments are passed by name, PHP fills the optional arguments which were skipped with
+    /// their default value, and refuses to call the function if the default value is not known.
+    ///
+    /// # Parameters
+    ///
+    /// * `optional` - Whether the argument is optional.
+    pub(crate) fn as_arg_info(&self, optional: bool) -> Result<ArgInfo> {
+        let default_value = match &self.default_value {
+            None if optional && self.allow_null && !self.variadic => Some("null"),
+            default => default.as_deref(),
+        };
+
         Ok(ArgInfo {
             name: CString::new(self.name.as_str())?.into_raw(),
             type_: ZendType::empty_from_type(
@@ -120,8 +410,8 @@ impl<'a> Arg<'a> {
                 self.allow_null,
             )
             .ok_or(Error::InvalidCString)?,
-            default_value: match &self.default_value {
-                Some(val) => CString::new(val.as_str())?.into_raw(),
+            default_value: match default_value {
+                Some(val) => CString::new(val)?.into_raw(),
                 None => ptr::null(),
             },
         })
@@ -131,13 +421,16 @@ impl<'a> Arg<'a> {
 impl From<Arg<'_>> for _zend_expected_type {
     fn from(arg: Arg) -> Self {
         let err = match arg._type {
-            DataType::False | DataType::True => _zend_expected_type_Z_EXPECTED_BOOL,
+            DataType::False | DataType::True | DataType::Bool => {
+                _zend_expected_type_Z_EXPECTED_BOOL
+            }
             DataType::Long => _zend_expected_type_Z_EXPECTED_LONG,
             DataType::Double => _zend_expected_type_Z_EXPECTED_DOUBLE,
             DataType::String => _zend_expected_type_Z_EXPECTED_STRING,
             DataType::Array => _zend_expected_type_Z_EXPECTED_ARRAY,
             DataType::Object(_) => _zend_expected_type_Z_EXPECTED_OBJECT,
             DataType::Resource => _zend_expected_type_Z_EXPECTED_RESOURCE,
+            DataType::Iterable => _zend_expected_type_Z_EXPECTED_ITERABLE,
             _ => unreachable!(),
         };
 
@@ -149,13 +442,36 @@ impl From<Arg<'_>> for _zend_expected_type {
     }
 }
 
+/// Returns the type of a value passed to an argument, as shown by PHP in a `TypeError`, i.e. `int`
+/// or the class name of an object.
+fn zval_type_name(zval: &Zval) -> String {
+    let zval = zval.dereference();
+
+    match zval.object() {
+        Some(obj) => obj.class_name().into(),
+        None => Arg::new("", zval.get_type().unwrap_or(DataType::Mixed)).type_name(),
+    }
+}
+
 /// Internal argument information used by Zend.
 pub type ArgInfo = zend_internal_arg_info;
 
+/// The reason the arguments passed to a function are invalid.
+enum Invalid {
+    /// The number of arguments given was outside of the bounds.
+    Count { given: u32, min: u32, max: u32 },
+    /// The argument at the index was of the wrong type.
+    Type(usize),
+    /// Arguments were passed by name which do not match any of the arguments.
+    ExtraNamed,
+}
+
 /// Parses the arguments of a function.
 pub struct ArgParser<'a, 'arg, 'zval> {
     args: Vec<&'arg mut Arg<'zval>>,
+    variadic: Option<&'arg mut Vec<&'zval Zval>>,
     min_num_args: Option<u32>,
+    strict: Option<bool>,
     execute_data: &'a ExecutionData,
 }
 
@@ -164,7 +480,9 @@ impl<'a, 'arg, 'zval> ArgParser<'a, 'arg, 'zval> {
     pub fn new(execute_data: &'a ExecutionData) -> Self {
         ArgParser {
             args: vec![],
+            variadic: None,
             min_num_args: None,
+            strict: None,
             execute_data,
         }
     }
@@ -185,6 +503,30 @@ impl<'a, 'arg, 'zval> ArgParser<'a, 'arg, 'zval> {
         self
     }
 
+    /// Sets whether scalar arguments are parsed in strict mode, overriding the `strict_types`
+    /// declaration of the calling file which is used by default. In strict mode, a value of the
+    /// wrong type is rejected rather than coerced, apart from integers passed to float arguments.
+    ///
+    /// # Parameters
+    ///
+    /// * `strict` - Whether to parse the arguments in strict mode.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = Some(strict);
+        self
+    }
+
+    /// Collects all arguments passed after the arguments that have been added to the parser into
+    /// the given vector. Must be called after all other arguments have been added. If no trailing
+    /// arguments are passed, the vector will be empty.
+    ///
+    /// # Parameters
+    ///
+    /// * `rest` - The vector to collect the remaining arguments into.
+    pub fn variadic(mut self, rest: &'arg mut Vec<&'zval Zval>) -> Self {
+        self.variadic = Some(rest);
+        self
+    }
+
     /// Uses the argument parser to parse the arguments contained in the given
     /// `ExecutionData` object. Returns successfully if the arguments were parsed.
     ///
@@ -197,25 +539,183 @@ impl<'a, 'arg, 'zval> ArgParser<'a, 'arg, 'zval> {
     /// # Errors
     ///
     /// Returns an [`Error`] type if there were too many or too little arguments passed to the
-    /// function. The user has already been notified so you should break execution after seeing an
-    /// error type.
+    /// function, in which case an `ArgumentCountError` is thrown, or if a scalar argument was of
+    /// the wrong type and could not be coerced. The user has already been notified so you should
+    /// break execution after seeing an error type.
     pub fn parse(mut self) -> Result<()> {
-        let num_args = unsafe { self.execute_data.This.u2.num_args };
-        let max_num_args = self.args.len() as u32;
+        match self.check() {
+            Ok(()) => Ok(()),
+            Err(Invalid::Count { given, min, max }) => {
+                // Throws an `ArgumentCountError` naming the function, matching the message of
+                // PHP's own functions, i.e. `add() expects exactly 2 arguments, 1 given`.
+                // SAFETY: Exported C function is safe, return value is unused and parameters are
+                // copied.
+                unsafe { zend_wrong_parameters_count_error(min, max) };
+
+                let expected = if given < min { min } else { max };
+                Err(Error::IncorrectArguments(given, expected))
+            }
+            Err(Invalid::Type(i)) => {
+                let arg = &self.args[i];
+                let num = i as u32 + 1;
+                let zval = unsafe { self.execute_data.zend_call_arg_mut(i) }
+                    .ok_or(Error::ZvalConversion(DataType::Undef))?;
+
+                // SAFETY: Exported C functions are safe, the zval is valid for the call.
+                match arg._type {
+                    DataType::Object(Some(class)) => {
+                        let class = CString::new(class)?;
+                        if arg.allow_null {
+                            unsafe {
+                                zend_wrong_parameter_class_or_null_error(num, class.as_ptr(), zval)
+                            };
+                        } else {
+                            unsafe { zend_wrong_parameter_class_error(num, class.as_ptr(), zval) };
+                        }
+                    }
+                    _ => unsafe {
+                        zend_wrong_parameter_type_error(num, (*arg).clone().into(), zval)
+                    },
+                }
+
+                Err(Error::ZvalConversion(zval.get_type()?))
+            }
+            Err(Invalid::ExtraNamed) => {
+                // SAFETY: Exported C function is safe and takes no parameters.
+                unsafe { zend_unexpected_extra_named_error() };
+                Err(Error::UnknownNamedArgument)
+            }
+        }
+    }
+
+    /// Parses the arguments in the same way as [`parse()`](#method.parse), but returns the
+    /// failure as an exception rather than throwing it. The exception carries the same message
+    /// PHP would have used, i.e. `add(): Argument #2 ($y) must be of type int, string given`, and
+    /// can be inspected, replaced or thrown by the caller.
+    ///
+    /// This function can only be safely called from within an exported PHP function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ArgumentCountError` if there were too many or too little arguments passed to
+    /// the function, or a `TypeError` if an argument was of the wrong type and could not be
+    /// coerced.
+    pub fn try_parse(mut self) -> PhpResult<'static> {
+        let invalid = match self.check() {
+            Ok(()) => return Ok(()),
+            Err(invalid) => invalid,
+        };
+        let function = self.execute_data.function_name().unwrap_or_default();
+
+        Err(match invalid {
+            Invalid::Count { given, min, max } => {
+                let (bound, expected) = if min == max {
+                    ("exactly", min)
+                } else if given < min {
+                    ("at least", min)
+                } else {
+                    ("at most", max)
+                };
+
+                PhpException::new(
+                    format!(
+                        "{}() expects {} {} argument{}, {} given",
+                        function,
+                        bound,
+                        expected,
+                        if expected == 1 { "" } else { "s" },
+                        given
+                    ),
+                    0,
+                    ClassEntry::argument_count_error(),
+                )
+            }
+            Invalid::Type(i) => {
+                let arg = &self.args[i];
+                let given = unsafe { self.execute_data.zend_call_arg(i) }
+                    .map_or_else(|| "null".into(), zval_type_name);
+
+                PhpException::new(
+                    format!(
+                        "{}(): Argument #{} (${}) must be of type {}, {} given",
+                        function,
+                        i + 1,
+                        arg.name,
+                        arg.type_name(),
+                        given
+                    ),
+                    0,
+                    ClassEntry::type_error(),
+                )
+            }
+            Invalid::ExtraNamed => PhpException::new(
+                format!("{}() does not accept unknown named parameters", function),
+                0,
+                ClassEntry::argument_count_error(),
+            ),
+        })
+    }
+
+    /// Checks the number of arguments passed to the function and coerces each of the arguments
+    /// into its type, without notifying the user of any failure.
+    fn check(&mut self) -> std::result::Result<(), Invalid> {
+        let num_args = self.execute_data.num_args() as u32;
+        let max_num_args = if self.variadic.is_some() {
+            u32::MAX
+        } else {
+            self.args.len() as u32
+        };
         let min_num_args = match self.min_num_args {
             Some(n) => n,
-            None => max_num_args,
+            None => self.args.len() as u32,
         };
 
         if num_args < min_num_args || num_args > max_num_args {
-            // SAFETY: Exported C function is safe, return value is unused and parameters are copied.
-            unsafe { zend_wrong_parameters_count_error(min_num_args, max_num_args) };
+            return Err(Invalid::Count {
+                given: num_args,
+                min: min_num_args,
+                max: max_num_args,
+            });
+        }
 
-            return Err(Error::IncorrectArguments(num_args, min_num_args));
+        // Arguments passed by name are mapped to their position by PHP before the function is
+        // called. Names which do not match an argument are only let through to variadic
+        // functions, which cannot collect them.
+        if self.execute_data.has_extra_named_args() {
+            return Err(Invalid::ExtraNamed);
         }
 
+        let strict = self
+            .strict
+            .unwrap_or_else(|| self.execute_data.uses_strict_types());
+
         for (i, arg) in self.args.iter_mut().enumerate() {
-            arg.zval = unsafe { self.execute_data.zend_call_arg(i) };
+            // Optional arguments which were not passed have no slot in the call frame.
+            let zval = if (i as u32) < num_args {
+                unsafe { self.execute_data.zend_call_arg_mut(i) }
+            } else {
+                None
+            };
+
+            if let Some(zval) = zval {
+                if !arg.coerce(zval, strict) {
+                    return Err(Invalid::Type(i));
+                }
+
+                arg.zval = Some(&*zval);
+            } else {
+                arg.zval = None;
+            }
+        }
+
+        if let Some(rest) = self.variadic.take() {
+            rest.clear();
+
+            for i in self.args.len()..num_args as usize {
+                if let Some(zv) = unsafe { self.execute_data.zend_call_arg(i) } {
+                    rest.push(zv);
+                }
+            }
         }
 
         Ok(())
@@ -4,21 +4,28 @@ use crate::{
     errors::{Error, Result},
     php::types::object::{ZendClassObject, ZendObject},
 };
-use std::{alloc::Layout, convert::TryInto, ffi::CString, fmt::Debug};
+use std::{
+    alloc::Layout,
+    convert::TryInto,
+    ffi::{CStr, CString},
+    fmt::Debug,
+};
 
 use crate::bindings::{
-    zend_class_entry, zend_declare_class_constant, zend_declare_property,
-    zend_do_implement_interface, zend_register_internal_class_ex,
+    zend_ce_aggregate, zend_ce_arrayaccess, zend_ce_countable, zend_ce_iterator,
+    zend_ce_serializable, zend_ce_stringable, zend_ce_traversable, zend_class_entry,
+    zend_class_implements, zend_declare_class_constant_ex, zend_declare_property,
+    zend_read_static_property, zend_register_internal_class_ex, zend_update_static_property,
 };
 
 use super::{
-    flags::{ClassFlags, MethodFlags, PropertyFlags},
+    flags::{ClassFlags, ConstantFlags, MethodFlags, PropertyFlags, ZendResult},
     function::FunctionEntry,
     globals::ExecutorGlobals,
     types::{
         object::RegisteredClass,
         string::ZendString,
-        zval::{IntoZval, Zval},
+        zval::{FromZval, IntoZval, Zval},
     },
 };
 
@@ -32,10 +39,21 @@ impl PartialEq for ClassEntry {
 }
 
 impl ClassEntry {
-    /// Attempts to find a reference to a class in the global class table.
+    /// Attempts to find a reference to a class in the global class table. As with PHP, the name
+    /// is case-insensitive, and namespaced names must not start with a backslash.
     ///
     /// Returns a reference to the class if found, or [`None`] if the class could
     /// not be found or the class table has not been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::class::ClassEntry;
+    ///
+    /// let ce = ClassEntry::try_find("stdclass").expect("stdClass does not exist");
+    /// let obj = ce.new_object().unwrap();
+    /// obj.object().unwrap().set_property("hello", "world").unwrap();
+    /// ```
     pub fn try_find(name: &str) -> Option<&'static Self> {
         ExecutorGlobals::get().class_table()?;
         let name = ZendString::new(name, false).ok()?;
@@ -46,6 +64,99 @@ impl ClassEntry {
         }
     }
 
+    /// Creates a new instance of the class and its default properties, returning a zval
+    /// containing the object. Note that the constructor of the class is not called.
+    ///
+    /// Returns an error if the class cannot be instantiated, in which case PHP will also have
+    /// thrown an `Error`.
+    pub fn new_object(&self) -> Result<Zval> {
+        let mut zv = Zval::new();
+        zv.init_object(self)?;
+        Ok(zv)
+    }
+
+    /// Attempts to read the value of a static property of the class, converting it into the type
+    /// `T`. Static properties are declared by passing [`PropertyFlags::Static`] when adding the
+    /// property to the class builder. If the property holds a reference, the reference is
+    /// followed.
+    ///
+    /// Returns an error if the property does not exist or is not accessible from the current
+    /// scope, or if the value could not be converted into `T`.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the static property, without the leading `$`.
+    pub fn get_static_property<'a, T: FromZval<'a>>(&'a self, name: &str) -> Result<T> {
+        let name_c = CString::new(name)?;
+        let zv = unsafe {
+            zend_read_static_property(
+                (self as *const Self) as *mut Self,
+                name_c.as_ptr(),
+                name.len() as _,
+                true,
+            )
+            .as_ref()
+        }
+        .ok_or(Error::InvalidProperty)?;
+        let zv: &Zval = match zv.reference() {
+            Some(zv) => zv,
+            None => zv,
+        };
+
+        T::from_zval(zv).ok_or(Error::ZvalConversion(zv.get_type()?))
+    }
+
+    /// Sets the value of a static property of the class. The value is shared by all instances
+    /// of the class, and persists until the end of the request.
+    ///
+    /// Returns an error if the property does not exist or is not accessible from the current
+    /// scope, or if the value could not be converted into a zval.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the static property, without the leading `$`.
+    /// * `value` - The value to set the property to.
+    ///
+    /// # Examples
+    ///
+    /// A static factory method which counts the number of instances it has created in a static
+    /// property:
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     class::ClassEntry, execution_data::ExecutionData, types::zval::Zval,
+    /// };
+    ///
+    /// pub extern "C" fn create(ex: &mut ExecutionData, retval: &mut Zval) {
+    ///     // Static methods have no object bound to `$this`.
+    ///     assert!(ex.get_this().is_none());
+    ///
+    ///     let ce = ClassEntry::try_find("MyClass").unwrap();
+    ///     let instances: i64 = ce.get_static_property("instances").unwrap_or(0);
+    ///     ce.set_static_property("instances", instances + 1).unwrap();
+    ///
+    ///     *retval = ce.new_object().unwrap();
+    /// }
+    /// ```
+    pub fn set_static_property(&self, name: &str, value: impl IntoZval) -> Result<()> {
+        let name_c = CString::new(name)?;
+        let mut value = value.into_zval(false)?;
+        let result = unsafe {
+            zend_update_static_property(
+                (self as *const Self) as *mut Self,
+                name_c.as_ptr(),
+                name.len() as _,
+                &mut value,
+            )
+        };
+
+        if result == ZendResult::Success.bits() {
+            Ok(())
+        } else {
+            Err(Error::InvalidProperty)
+        }
+    }
+
     /// Returns the class flags.
     pub fn flags(&self) -> ClassFlags {
         ClassFlags::from_bits_truncate(self.ce_flags)
@@ -78,15 +189,14 @@ impl ClassEntry {
                 }
             }
         } else {
-            loop {
-                let parent = match self.parent() {
-                    Some(parent) => parent,
-                    None => return false,
-                };
+            let mut parent = self.parent();
 
-                if parent == ce {
+            while let Some(class) = parent {
+                if class == ce {
                     return true;
                 }
+
+                parent = class.parent();
             }
         }
 
@@ -122,6 +232,46 @@ impl ClassEntry {
     }
 }
 
+// SAFETY: The built-in interfaces are registered by the engine before any extensions are
+// started, so by the time these are used (in the module startup function) the pointers are valid.
+// If one is used before this, the pointer is null and the function returns `None`.
+impl ClassEntry {
+    /// Returns the base `Traversable` interface, or [`None`] before the engine has started.
+    pub fn traversable() -> Option<&'static Self> {
+        unsafe { zend_ce_traversable.as_ref() }
+    }
+
+    /// Returns the base `IteratorAggregate` interface, or [`None`] before the engine has started.
+    pub fn iterator_aggregate() -> Option<&'static Self> {
+        unsafe { zend_ce_aggregate.as_ref() }
+    }
+
+    /// Returns the base `Iterator` interface, or [`None`] before the engine has started.
+    pub fn iterator() -> Option<&'static Self> {
+        unsafe { zend_ce_iterator.as_ref() }
+    }
+
+    /// Returns the base `ArrayAccess` interface, or [`None`] before the engine has started.
+    pub fn array_access() -> Option<&'static Self> {
+        unsafe { zend_ce_arrayaccess.as_ref() }
+    }
+
+    /// Returns the base `Serializable` interface, or [`None`] before the engine has started.
+    pub fn serializable() -> Option<&'static Self> {
+        unsafe { zend_ce_serializable.as_ref() }
+    }
+
+    /// Returns the base `Countable` interface, or [`None`] before the engine has started.
+    pub fn countable() -> Option<&'static Self> {
+        unsafe { zend_ce_countable.as_ref() }
+    }
+
+    /// Returns the base `Stringable` interface, or [`None`] before the engine has started.
+    pub fn stringable() -> Option<&'static Self> {
+        unsafe { zend_ce_stringable.as_ref() }
+    }
+}
+
 /// Builds a class to be exported as a PHP class.
 pub struct ClassBuilder {
     name: String,
@@ -131,7 +281,7 @@ pub struct ClassBuilder {
     methods: Vec<FunctionEntry>,
     object_override: Option<unsafe extern "C" fn(class_type: *mut ClassEntry) -> *mut ZendObject>,
     properties: Vec<(String, Zval, PropertyFlags)>,
-    constants: Vec<(String, Zval)>,
+    constants: Vec<(String, Zval, ConstantFlags)>,
 }
 
 impl ClassBuilder {
@@ -166,15 +316,30 @@ impl ClassBuilder {
 
     /// Sets the class builder to extend another class.
     ///
+    /// The class inherits the methods, properties and constants of the parent class when it is
+    /// registered, including the constructor if the class does not declare its own. This is
+    /// commonly used to declare custom exceptions by extending [`ClassEntry::exception()`].
+    ///
     /// # Parameters
     ///
     /// * `parent` - The parent class to extend.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the given class entry `parent` is an interface.
     pub fn extends(mut self, parent: &'static ClassEntry) -> Self {
+        if parent.is_interface() {
+            panic!("Given class entry was an interface, which cannot be extended.");
+        }
+
         self.extends = Some(parent);
         self
     }
 
-    /// Implements an interface on the class.
+    /// Implements an interface on the class. The interface is attached to the class when the
+    /// class is built. See [`ClassEntry`] for the built-in interfaces, such as
+    /// [`ClassEntry::countable()`] and [`ClassEntry::array_access()`], or use
+    /// [`ClassEntry::try_find()`] to look up an interface by name.
     ///
     /// # Parameters
     ///
@@ -192,21 +357,117 @@ impl ClassBuilder {
         self
     }
 
-    /// Adds a method to the class.
+    /// Adds a method to the class. The flags are combined with any flags already set on the
+    /// function entry.
+    ///
+    /// Abstract methods are built with [`FunctionBuilder::new_abstract()`] and added with the
+    /// [`MethodFlags::Abstract`] flag. PHP marks a class which declares an abstract method as
+    /// abstract itself, so it cannot be instantiated. Static methods cannot be abstract.
     ///
     /// # Parameters
     ///
     /// * `func` - The function entry to add to the class.
     /// * `flags` - Flags relating to the function. See [`MethodFlags`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     class::ClassBuilder, enums::DataType, flags::MethodFlags, function::FunctionBuilder,
+    /// };
+    ///
+    /// let area = FunctionBuilder::new_abstract("area")
+    ///     .returns(DataType::Double)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let shape = ClassBuilder::new("Shape")
+    ///     .method(area, MethodFlags::Public | MethodFlags::Abstract)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`FunctionBuilder::new_abstract()`]: crate::php::function::FunctionBuilder::new_abstract
     pub fn method(mut self, mut func: FunctionEntry, flags: MethodFlags) -> Self {
-        func.flags = flags.bits();
+        func.flags |= flags.bits();
         self.methods.push(func);
         self
     }
 
+    /// Adds a constructor to the class. The constructor is registered as the public `__construct`
+    /// method, and should be built with [`FunctionBuilder::constructor()`].
+    ///
+    /// When the class overrides its object (see [`object_override()`](#method.object_override)),
+    /// the Rust struct is initialized with [`Default`] before the constructor is called. The
+    /// constructor can then parse its arguments and write them into the struct through
+    /// [`ZendObject::get_mut()`]. If the constructor throws an exception, it should return
+    /// immediately - PHP discards the object and the struct is dropped along with it.
+    ///
+    /// # Parameters
+    ///
+    /// * `func` - The function entry of the constructor.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{
+    ///     parse_args,
+    ///     php::{
+    ///         args::Arg, class::ClassBuilder, enums::DataType, execution_data::ExecutionData,
+    ///         exceptions::PhpException, function::FunctionBuilder, types::zval::Zval,
+    ///     },
+    ///     php_class,
+    /// };
+    ///
+    /// #[php_class]
+    /// #[derive(Default)]
+    /// pub struct MyClass {
+    ///     a: i64,
+    ///     c: String,
+    /// }
+    ///
+    /// pub extern "C" fn my_class_construct(ex: &mut ExecutionData, _: &mut Zval) {
+    ///     let mut a = Arg::new("a", DataType::Long);
+    ///     let mut c = Arg::new("c", DataType::String);
+    ///     parse_args!(ex, a, c);
+    ///
+    ///     let this = match ex.get_this().and_then(|this| this.get_mut::<MyClass>()) {
+    ///         Some(this) => this,
+    ///         None => {
+    ///             let _ = PhpException::default("Invalid object.".into()).throw();
+    ///             return;
+    ///         }
+    ///     };
+    ///
+    ///     this.a = a.val().unwrap_or_default();
+    ///     this.c = c.val().unwrap_or_default();
+    /// }
+    ///
+    /// let ctor = FunctionBuilder::constructor(my_class_construct)
+    ///     .arg(Arg::new("a", DataType::Long))
+    ///     .arg(Arg::new("c", DataType::String))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let ce = ClassBuilder::new("MyClass")
+    ///     .constructor(ctor)
+    ///     .object_override::<MyClass>()
+    ///     .build();
+    /// ```
+    ///
+    /// [`FunctionBuilder::constructor()`]: crate::php::function::FunctionBuilder::constructor
+    /// [`ZendObject::get_mut()`]: crate::php::types::object::ZendObject::get_mut
+    pub fn constructor(self, func: FunctionEntry) -> Self {
+        self.method(func, MethodFlags::Public | MethodFlags::IsConstructor)
+    }
+
     /// Adds a property to the class. The initial type of the property is given by the type
     /// of the given default. Note that the user can change the type.
     ///
+    /// The default can be any type that implements [`IntoZval`], including `()` or [`None`] for a
+    /// property which defaults to null. Static properties can be declared by including
+    /// [`PropertyFlags::Static`] in the given flags.
+    ///
     /// # Parameters
     ///
     /// * `name` - The name of the property to add to the class.
@@ -231,8 +492,8 @@ impl ClassBuilder {
         self
     }
 
-    /// Adds a constant to the class. The type of the constant is defined by the type of the given
-    /// default.
+    /// Adds a public constant to the class. The type of the constant is defined by the type of
+    /// the given default.
     ///
     /// Returns a result containing the class builder if the constant was successfully added.
     ///
@@ -240,10 +501,44 @@ impl ClassBuilder {
     ///
     /// * `name` - The name of the constant to add to the class.
     /// * `value` - The value of the constant.
-    pub fn constant<T: Into<String>>(mut self, name: T, value: impl IntoZval) -> Result<Self> {
+    pub fn constant<T: Into<String>>(self, name: T, value: impl IntoZval) -> Result<Self> {
+        self.constant_flags(name, value, ConstantFlags::Public)
+    }
+
+    /// Adds a constant to the class with the given visibility. The type of the constant is
+    /// defined by the type of the given default, and can be an integer, float, boolean, string or
+    /// null.
+    ///
+    /// Returns a result containing the class builder if the constant was successfully added.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the constant to add to the class.
+    /// * `value` - The value of the constant.
+    /// * `flags` - The visibility of the constant. See [`ConstantFlags`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{class::ClassBuilder, flags::ConstantFlags};
+    ///
+    /// let ce = ClassBuilder::new("MyClass")
+    ///     .constant("MODE_READ", 1)
+    ///     .and_then(|builder| builder.constant("MODE_WRITE", 2))
+    ///     .and_then(|builder| {
+    ///         builder.constant_flags("PRIVATE_CONST", "secret", ConstantFlags::Private)
+    ///     })
+    ///     .and_then(|builder| builder.build());
+    /// ```
+    pub fn constant_flags<T: Into<String>>(
+        mut self,
+        name: T,
+        value: impl IntoZval,
+        flags: ConstantFlags,
+    ) -> Result<Self> {
         let value = value.into_zval(true)?;
 
-        self.constants.push((name.into(), value));
+        self.constants.push((name.into(), value, flags));
         Ok(self)
     }
 
@@ -252,13 +547,62 @@ impl ClassBuilder {
     /// # Parameters
     ///
     /// * `flags` - Flags relating to the class. See [`ClassFlags`].
-    pub fn flags(mut self, flags: ClassFlags) -> Self {
+    pub fn flags(self, flags: ClassFlags) -> Self {
         self.ptr.ce_flags = flags.bits();
         self
     }
 
+    /// Declares the class as abstract, preventing it from being instantiated. Abstract classes
+    /// are usually extended by other classes, and may declare abstract methods.
+    pub fn abstract_class(self) -> Self {
+        self.ptr.ce_flags |= ClassFlags::Abstract.bits();
+        self
+    }
+
+    /// Declares the class as an interface. Interfaces can only declare methods and constants,
+    /// and every method added to the interface is made abstract. Interfaces cannot be
+    /// instantiated, and therefore cannot override their objects with
+    /// [`object_override()`](#method.object_override).
+    ///
+    /// Methods must be built with [`FunctionBuilder::new_abstract()`], as they have no body.
+    /// The arguments and return type of each method are registered with PHP, which checks that
+    /// the methods of implementing classes have compatible signatures. Building the interface
+    /// returns [`Error::InterfaceMethodBody`] if a method has a handler.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     args::Arg, class::ClassBuilder, enums::DataType, flags::MethodFlags,
+    ///     function::FunctionBuilder,
+    /// };
+    ///
+    /// // `public function process(string $input): int;`
+    /// let process = FunctionBuilder::new_abstract("process")
+    ///     .arg(Arg::new("input", DataType::String))
+    ///     .returns(DataType::Long)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let iface = ClassBuilder::new("Processor")
+    ///     .interface()
+    ///     .method(process, MethodFlags::Public)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // A concrete class can then implement the interface with `.implements(iface)`.
+    /// ```
+    ///
+    /// [`FunctionBuilder::new_abstract()`]: crate::php::function::FunctionBuilder::new_abstract
+    pub fn interface(self) -> Self {
+        self.ptr.ce_flags |= ClassFlags::Interface.bits();
+        self
+    }
+
     /// Overrides the creation of the Zend object which will represent an instance
-    /// of this class.
+    /// of this class. Objects are created with the default value of `T`, which the constructor
+    /// can then modify. See [`object_override_uninit()`](#method.object_override_uninit) for
+    /// types which do not implement [`Default`].
     ///
     /// # Parameters
     ///
@@ -269,7 +613,128 @@ impl ClassBuilder {
     ///
     /// Panics if the class name associated with `T` is not the same as the class name specified
     /// when creating the builder.
-    pub fn object_override<T: RegisteredClass>(mut self) -> Self {
+    pub fn object_override<T: RegisteredClass + Default>(mut self) -> Self {
+        unsafe extern "C" fn create_object<T: RegisteredClass + Default>(
+            _: *mut ClassEntry,
+        ) -> *mut ZendObject {
+            let ptr = ZendClassObject::<T>::new_ptr(Some(T::default()));
+            (*ptr).get_mut_zend_obj()
+        }
+
+        assert_eq!(self.name.as_str(), T::CLASS_NAME);
+        self.object_override = Some(create_object::<T>);
+        self
+    }
+
+    /// Overrides the creation of the Zend object which will represent an instance of this class,
+    /// without requiring `T` to implement [`Default`]. Objects are created without a value of
+    /// `T`, which must be stored by the constructor with [`ZendObject::initialize()`].
+    ///
+    /// Until the object is initialized, [`ZendObject::get()`], [`ZendObject::get_mut()`] and
+    /// [`ExecutionData::get_object()`] return [`None`] for the object, and it cannot be passed
+    /// to functions taking `&T`, so the value is never accessed before it is initialized.
+    /// Methods should throw an exception in this case, which can be caught by the script. This
+    /// covers objects created without calling the constructor, such as through
+    /// `ReflectionClass::newInstanceWithoutConstructor()`, or when the constructor throws before
+    /// initializing the object.
+    ///
+    /// # Parameters
+    ///
+    /// * `T` - The type which will override the Zend object. Must implement [`RegisteredClass`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class name associated with `T` is not the same as the class name specified
+    /// when creating the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{fs::File, path::Path};
+    ///
+    /// use ext_php_rs::{
+    ///     parse_args,
+    ///     php::{
+    ///         args::Arg,
+    ///         class::ClassBuilder,
+    ///         enums::DataType,
+    ///         exceptions::PhpException,
+    ///         execution_data::ExecutionData,
+    ///         flags::MethodFlags,
+    ///         function::FunctionBuilder,
+    ///         types::{
+    ///             object::{ClassMetadata, RegisteredClass},
+    ///             zval::Zval,
+    ///         },
+    ///     },
+    /// };
+    ///
+    /// // There is no meaningful default for the file handle.
+    /// pub struct Log {
+    ///     file: File,
+    /// }
+    ///
+    /// static LOG_META: ClassMetadata<Log> = ClassMetadata::new();
+    ///
+    /// impl RegisteredClass for Log {
+    ///     const CLASS_NAME: &'static str = "Log";
+    ///
+    ///     fn get_metadata() -> &'static ClassMetadata<Self> {
+    ///         &LOG_META
+    ///     }
+    /// }
+    ///
+    /// pub extern "C" fn log_construct(ex: &mut ExecutionData, _: &mut Zval) {
+    ///     let mut path = Arg::new("path", DataType::String);
+    ///     parse_args!(ex, path);
+    ///
+    ///     let path: String = path.val().unwrap_or_default();
+    ///     let file = match File::open(Path::new(&path)) {
+    ///         Ok(file) => file,
+    ///         Err(e) => {
+    ///             let _ = PhpException::default(e.to_string()).throw();
+    ///             return;
+    ///         }
+    ///     };
+    ///
+    ///     if let Some(this) = ex.get_this() {
+    ///         this.initialize(Log { file });
+    ///     }
+    /// }
+    ///
+    /// pub extern "C" fn log_size(ex: &mut ExecutionData, retval: &mut Zval) {
+    ///     let this = match ex.get_this().and_then(|this| this.get::<Log>()) {
+    ///         Some(this) => this,
+    ///         None => {
+    ///             let _ = PhpException::default("Log has not been initialized.".into()).throw();
+    ///             return;
+    ///         }
+    ///     };
+    ///
+    ///     retval.set_long(this.file.metadata().map_or(0, |m| m.len() as i64));
+    /// }
+    ///
+    /// let ctor = FunctionBuilder::constructor(log_construct)
+    ///     .arg(Arg::new("path", DataType::String))
+    ///     .build()
+    ///     .unwrap();
+    /// let size = FunctionBuilder::new("size", log_size)
+    ///     .returns(DataType::Long)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let ce = ClassBuilder::new("Log")
+    ///     .constructor(ctor)
+    ///     .method(size, MethodFlags::Public)
+    ///     .object_override_uninit::<Log>()
+    ///     .build();
+    /// ```
+    ///
+    /// [`ZendObject::initialize()`]: crate::php::types::object::ZendObject::initialize
+    /// [`ZendObject::get()`]: crate::php::types::object::ZendObject::get
+    /// [`ZendObject::get_mut()`]: crate::php::types::object::ZendObject::get_mut
+    /// [`ExecutionData::get_object()`]: crate::php::execution_data::ExecutionData::get_object
+    pub fn object_override_uninit<T: RegisteredClass>(mut self) -> Self {
         unsafe extern "C" fn create_object<T: RegisteredClass>(
             _: *mut ClassEntry,
         ) -> *mut ZendObject {
@@ -284,10 +749,89 @@ impl ClassBuilder {
 
     /// Builds the class, returning a reference to the class entry.
     ///
+    /// The class entry is registered as an internal class, which is owned by PHP and is not freed
+    /// until the engine shuts down. The reference can therefore be stored for the lifetime of the
+    /// extension and used to extend the class, check whether objects are instances of it, or
+    /// throw it when it is an exception.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{
+    ///     ptr,
+    ///     sync::atomic::{AtomicPtr, Ordering},
+    /// };
+    ///
+    /// use ext_php_rs::php::{class::{ClassBuilder, ClassEntry}, exceptions::PhpException};
+    ///
+    /// static FOO_CE: AtomicPtr<ClassEntry> = AtomicPtr::new(ptr::null_mut());
+    ///
+    /// pub extern "C" fn startup_function(_type: i32, _module_number: i32) -> i32 {
+    ///     let foo: &'static ClassEntry = ClassBuilder::new("FooException")
+    ///         .extends(ClassEntry::exception())
+    ///         .build()
+    ///         .expect("Failed to build `FooException`.");
+    ///     FOO_CE.store(foo as *const _ as *mut _, Ordering::Release);
+    ///
+    ///     ClassBuilder::new("BarException")
+    ///         .extends(foo)
+    ///         .build()
+    ///         .expect("Failed to build `BarException`.");
+    ///     0
+    /// }
+    ///
+    /// fn foo_error(message: &str) -> PhpException<'static> {
+    ///     let ce = unsafe { FOO_CE.load(Ordering::Acquire).as_ref() }
+    ///         .expect("`FooException` has not been registered.");
+    ///     PhpException::new(message.into(), 0, ce)
+    /// }
+    /// ```
+    ///
     /// # Errors
     ///
-    /// Returns an [`Error`] variant if the class could not be registered.
+    /// Returns an [`Error`] variant if the class could not be registered, or if the class is an
+    /// interface which overrides its objects or has a method with a body.
     pub fn build(mut self) -> Result<&'static mut ClassEntry> {
+        let is_interface =
+            ClassFlags::from_bits_truncate(self.ptr.ce_flags).contains(ClassFlags::Interface);
+
+        if is_interface {
+            if self.object_override.is_some() {
+                // SAFETY: We allocated memory for this pointer in `new`, and it has not been
+                // given to PHP.
+                unsafe {
+                    std::alloc::dealloc(
+                        (self.ptr as *mut _) as *mut u8,
+                        Layout::new::<ClassEntry>(),
+                    )
+                };
+                return Err(Error::InterfaceObjectOverride);
+            }
+
+            // Interface methods only declare a signature, which is checked against the methods of
+            // implementing classes, so a handler would never be called.
+            if let Some(method) = self.methods.iter().find(|method| method.handler.is_some()) {
+                let name = unsafe { CStr::from_ptr(method.fname) }
+                    .to_string_lossy()
+                    .into_owned();
+
+                // SAFETY: We allocated memory for this pointer in `new`, and it has not been
+                // given to PHP.
+                unsafe {
+                    std::alloc::dealloc(
+                        (self.ptr as *mut _) as *mut u8,
+                        Layout::new::<ClassEntry>(),
+                    )
+                };
+                return Err(Error::InterfaceMethodBody(name));
+            }
+
+            // PHP refuses to register interfaces containing non-abstract methods.
+            for method in self.methods.iter_mut() {
+                method.flags |= MethodFlags::Abstract.bits();
+            }
+        }
+
         self.ptr.name = ZendString::new_interned(&self.name)?.release();
 
         self.methods.push(FunctionEntry::end());
@@ -312,7 +856,9 @@ impl ClassBuilder {
         };
 
         for iface in self.interfaces {
-            unsafe { zend_do_implement_interface(class, std::mem::transmute(iface)) };
+            unsafe {
+                zend_class_implements(class, 1, (iface as *const ClassEntry) as *mut ClassEntry)
+            };
         }
 
         for (name, mut default, flags) in self.properties {
@@ -325,16 +871,20 @@ impl ClassBuilder {
                     flags.bits() as _,
                 );
             }
+
+            // The class entry now owns the contents of the default value.
+            default.release();
         }
 
-        for (name, value) in self.constants {
+        for (name, value, flags) in self.constants {
             let value = Box::into_raw(Box::new(value));
             unsafe {
-                zend_declare_class_constant(
+                zend_declare_class_constant_ex(
                     class,
-                    CString::new(name.as_str())?.as_ptr(),
-                    name.len() as u64,
+                    ZendString::new_interned(&name)?.release(),
                     value,
+                    flags.bits() as _,
+                    std::ptr::null_mut(),
                 )
             };
         }
@@ -5,11 +5,31 @@ use std::ffi::CString;
 use super::flags::GlobalConstantFlags;
 use crate::bindings::{
     zend_register_bool_constant, zend_register_double_constant, zend_register_long_constant,
-    zend_register_string_constant,
+    zend_register_null_constant, zend_register_string_constant,
 };
 use crate::errors::Result;
 
-pub trait IntoConst: Sized {
+/// Implemented on types which can be registered as a constant in PHP.
+///
+/// The trait is object safe, so values of different types can be registered together as
+/// `&dyn IntoConst`, see [`register_constants()`].
+pub trait IntoConst {
+    /// Returns the value as a PHP constant expression, used as the default value of a function
+    /// argument (see [`Arg::default()`](crate::php::args::Arg::default)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ext_php_rs::php::constants::IntoConst;
+    ///
+    /// assert_eq!(5.as_default(), "5");
+    /// assert_eq!(1.0.as_default(), "1.0");
+    /// assert_eq!(f64::NEG_INFINITY.as_default(), "-INF");
+    /// assert_eq!("it's".as_default(), "'it\\'s'");
+    /// assert_eq!(().as_default(), "null");
+    /// ```
+    fn as_default(&self) -> String;
+
     /// Registers a global module constant in PHP, with the value as the content of self.
     /// This function _must_ be called in the module startup function, which is called after
     /// the module is initialized. The second parameter of the startup function will be the
@@ -75,7 +95,26 @@ pub trait IntoConst: Sized {
     ) -> Result<()>;
 }
 
+impl IntoConst for &dyn IntoConst {
+    fn as_default(&self) -> String {
+        (**self).as_default()
+    }
+
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        (**self).register_constant_flags(name, module_number, flags)
+    }
+}
+
 impl IntoConst for String {
+    fn as_default(&self) -> String {
+        self.as_str().as_default()
+    }
+
     fn register_constant_flags(
         &self,
         name: &str,
@@ -88,6 +127,10 @@ impl IntoConst for String {
 }
 
 impl IntoConst for &str {
+    fn as_default(&self) -> String {
+        format!("'{}'", self.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+
     fn register_constant_flags(
         &self,
         name: &str,
@@ -108,6 +151,10 @@ impl IntoConst for &str {
 }
 
 impl IntoConst for bool {
+    fn as_default(&self) -> String {
+        self.to_string()
+    }
+
     fn register_constant_flags(
         &self,
         name: &str,
@@ -127,10 +174,47 @@ impl IntoConst for bool {
     }
 }
 
+impl IntoConst for () {
+    fn as_default(&self) -> String {
+        "null".into()
+    }
+
+    fn register_constant_flags(
+        &self,
+        name: &str,
+        module_number: i32,
+        flags: GlobalConstantFlags,
+    ) -> Result<()> {
+        unsafe {
+            zend_register_null_constant(
+                CString::new(name)?.as_ptr(),
+                name.len() as _,
+                flags.bits() as _,
+                module_number,
+            )
+        };
+        Ok(())
+    }
+}
+
 /// Implements the `IntoConst` trait for a given number type using a given function.
 macro_rules! into_const_num {
     ($type: ty, $fn: expr) => {
         impl IntoConst for $type {
+            fn as_default(&self) -> String {
+                // Infinity and NaN have no literal in PHP, and are written as constants instead.
+                // Integers are never infinite or NaN.
+                let val = *self as f64;
+                if val.is_nan() {
+                    "NAN".into()
+                } else if val.is_infinite() {
+                    format!("{}INF", if val < 0.0 { "-" } else { "" })
+                } else {
+                    // `Debug` keeps the decimal point on whole floats, e.g. `1.0`.
+                    format!("{:?}", self)
+                }
+            }
+
             fn register_constant_flags(
                 &self,
                 name: &str,
@@ -157,3 +241,49 @@ into_const_num!(i32, zend_register_long_constant);
 into_const_num!(i64, zend_register_long_constant);
 into_const_num!(f32, zend_register_double_constant);
 into_const_num!(f64, zend_register_double_constant);
+
+/// Registers a set of global module constants in PHP, prefixing each of the names with
+/// `prefix`. Like [`IntoConst::register_constant()`], this function _must_ be called in the
+/// module startup function, and the case-sensitive and persistent flags are set on each
+/// constant.
+///
+/// Registration stops at the first constant which could not be registered, returning the error.
+///
+/// # Parameters
+///
+/// * `prefix` - The prefix to add to the name of each constant, i.e. `MYEXT_`.
+/// * `items` - The names and values of the constants. Values of different types can be given as
+/// `&dyn IntoConst`.
+/// * `module_number` - The module number that we are registering the constants under.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::constants::{register_constants, IntoConst};
+///
+/// pub extern "C" fn startup_function(_type: i32, module_number: i32) -> i32 {
+///     // MYEXT_STATUS_OK == 200, MYEXT_STATUS_NOT_FOUND == 404
+///     let _ = register_constants(
+///         "MYEXT_",
+///         vec![("STATUS_OK", 200), ("STATUS_NOT_FOUND", 404)],
+///         module_number,
+///     );
+///
+///     // MYEXT_VERSION == "1.0.0", MYEXT_DEBUG == false
+///     let info: [(&str, &dyn IntoConst); 2] = [("VERSION", &"1.0.0"), ("DEBUG", &false)];
+///     let _ = register_constants("MYEXT_", info, module_number);
+///     0
+/// }
+/// ```
+pub fn register_constants<I, N, V>(prefix: &str, items: I, module_number: i32) -> Result<()>
+where
+    I: IntoIterator<Item = (N, V)>,
+    N: AsRef<str>,
+    V: IntoConst,
+{
+    for (name, value) in items {
+        value.register_constant(&format!("{}{}", prefix, name.as_ref()), module_number)?;
+    }
+
+    Ok(())
+}
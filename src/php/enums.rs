@@ -4,8 +4,9 @@ use std::{convert::TryFrom, fmt::Display};
 
 use crate::{
     bindings::{
-        IS_ARRAY, IS_CALLABLE, IS_CONSTANT_AST, IS_DOUBLE, IS_FALSE, IS_LONG, IS_MIXED, IS_NULL,
-        IS_OBJECT, IS_REFERENCE, IS_RESOURCE, IS_STRING, IS_TRUE, IS_UNDEF, IS_VOID, _IS_BOOL,
+        IS_ARRAY, IS_CALLABLE, IS_CONSTANT_AST, IS_DOUBLE, IS_FALSE, IS_ITERABLE, IS_LONG, IS_MIXED,
+        IS_NULL, IS_OBJECT, IS_REFERENCE, IS_RESOURCE, IS_STRING, IS_TRUE, IS_UNDEF, IS_VOID,
+        _IS_BOOL,
     },
     errors::{Error, Result},
     php::flags::ZvalTypeFlags,
@@ -26,6 +27,9 @@ pub enum DataType {
     Resource,
     Reference,
     Callable,
+    /// The `iterable` pseudo-type, accepting an array or a `Traversable` object. Only valid as
+    /// the type of an argument or return value.
+    Iterable,
     ConstantExpression,
     Void,
     Mixed,
@@ -52,8 +56,9 @@ impl DataType {
             DataType::Array => IS_ARRAY,
             DataType::Object(_) => IS_OBJECT,
             DataType::Resource => IS_RESOURCE,
-            DataType::Reference => IS_RESOURCE,
+            DataType::Reference => IS_REFERENCE,
             DataType::Callable => IS_CALLABLE,
+            DataType::Iterable => IS_ITERABLE,
             DataType::ConstantExpression => IS_CONSTANT_AST,
             DataType::Void => IS_VOID,
             DataType::Mixed => IS_MIXED,
@@ -121,6 +126,7 @@ impl TryFrom<u32> for DataType {
         }
 
         contains!(IS_VOID, Void);
+        contains!(IS_ITERABLE, Iterable);
         contains!(IS_CALLABLE, Callable);
         contains!(IS_CONSTANT_AST, ConstantExpression);
         contains!(IS_REFERENCE, Reference);
@@ -158,6 +164,7 @@ impl Display for DataType {
             DataType::Resource => write!(f, "Resource"),
             DataType::Reference => write!(f, "Reference"),
             DataType::Callable => write!(f, "Callable"),
+            DataType::Iterable => write!(f, "Iterable"),
             DataType::ConstantExpression => write!(f, "Constant Expression"),
             DataType::Void => write!(f, "Void"),
             DataType::Bool => write!(f, "Bool"),
@@ -171,9 +178,9 @@ mod tests {
     use super::DataType;
     use crate::bindings::{
         IS_ARRAY, IS_ARRAY_EX, IS_CALLABLE, IS_CONSTANT_AST, IS_CONSTANT_AST_EX, IS_DOUBLE,
-        IS_FALSE, IS_INTERNED_STRING_EX, IS_LONG, IS_NULL, IS_OBJECT, IS_OBJECT_EX, IS_REFERENCE,
-        IS_REFERENCE_EX, IS_RESOURCE, IS_RESOURCE_EX, IS_STRING, IS_STRING_EX, IS_TRUE, IS_UNDEF,
-        IS_VOID,
+        IS_FALSE, IS_INTERNED_STRING_EX, IS_ITERABLE, IS_LONG, IS_NULL, IS_OBJECT, IS_OBJECT_EX,
+        IS_REFERENCE, IS_REFERENCE_EX, IS_RESOURCE, IS_RESOURCE_EX, IS_STRING, IS_STRING_EX,
+        IS_TRUE, IS_UNDEF, IS_VOID,
     };
     use std::convert::TryFrom;
 
@@ -191,6 +198,7 @@ mod tests {
         test!(IS_TRUE, True);
         test!(IS_LONG, Long);
         test!(IS_DOUBLE, Double);
+        test!(IS_ITERABLE, Iterable);
         test!(IS_STRING, String);
         test!(IS_ARRAY, Array);
         assert_eq!(DataType::try_from(IS_OBJECT), Ok(DataType::Object(None)));
@@ -1,8 +1,19 @@
 //! Contains all the base PHP throwables, including `Throwable` and `Exception`.
 
-use std::ffi::CString;
+use std::{
+    char::ParseCharError,
+    error::Error as StdError,
+    ffi::{CString, NulError},
+    fmt, io,
+    num::{ParseFloatError, ParseIntError, TryFromIntError},
+    str::{ParseBoolError, Utf8Error},
+    string::FromUtf8Error,
+};
 
-use super::{class::ClassEntry, types::object::RegisteredClass};
+use super::{
+    class::{ClassBuilder, ClassEntry},
+    types::{long::ZendLong, object::RegisteredClass},
+};
 use crate::{
     bindings::{
         zend_ce_argument_count_error, zend_ce_arithmetic_error, zend_ce_compile_error,
@@ -14,17 +25,42 @@ use crate::{
     php::flags::ClassFlags,
 };
 
+/// Result type with the error variant as a [`PhpException`]. Can be returned from functions
+/// exported with the [`macro@crate::php_function`] macro, where an error is thrown as an
+/// exception.
+pub type PhpResult<'a, T = ()> = std::result::Result<T, PhpException<'a>>;
+
 /// Represents a PHP exception which can be thrown using the `throw()` function. Primarily used to
 /// return from a [`Result<T, PhpException>`] which can immediately be thrown by the `ext-php-rs`
 /// macro API.
 ///
-/// There are default [`From`] implementations for any type that implements [`ToString`], so these
-/// can also be returned from these functions. You can also implement [`From<T>`] for your custom
-/// error type.
+/// There are default [`From`] implementations for [`String`] and `&str`, boxed errors
+/// (`Box<dyn Error>`) and the errors returned by the standard library when parsing and
+/// converting values, such as [`ParseIntError`] and [`io::Error`]. Errors from the [`anyhow`]
+/// crate are also converted when the `anyhow` feature is enabled. These are all thrown as a
+/// regular `Exception`, with the [`Display`](std::fmt::Display) output of the error as its
+/// message, which allows the `?` operator to be used on fallible operations in functions
+/// returning a [`PhpResult`]. You can also implement [`From<T>`] for your custom error type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::{php::exceptions::PhpResult, php_function};
+///
+/// // `parse_int("abc")` throws an `Exception` with the message
+/// // `invalid digit found in string`.
+/// #[php_function]
+/// pub fn parse_int(s: String) -> PhpResult<'static, i64> {
+///     let n: i64 = s.parse()?;
+///     Ok(n)
+/// }
+/// ```
+///
+/// [`anyhow`]: https://docs.rs/anyhow
 #[derive(Debug)]
 pub struct PhpException<'a> {
     message: String,
-    code: i32,
+    code: ZendLong,
     ex: &'a ClassEntry,
 }
 
@@ -36,7 +72,7 @@ impl<'a> PhpException<'a> {
     /// * `message` - Message to contain in the exception.
     /// * `code` - Integer code to go inside the exception.
     /// * `ex` - Exception type to throw.
-    pub fn new(message: String, code: i32, ex: &'a ClassEntry) -> Self {
+    pub fn new(message: String, code: ZendLong, ex: &'a ClassEntry) -> Self {
         Self { message, code, ex }
     }
 
@@ -59,8 +95,34 @@ impl<'a> PhpException<'a> {
         Self::new(message, 0, T::get_metadata().ce())
     }
 
+    /// Sets the integer code of the exception.
+    ///
+    /// # Parameters
+    ///
+    /// * `code` - Integer code to go inside the exception.
+    pub fn with_code(mut self, code: ZendLong) -> Self {
+        self.code = code;
+        self
+    }
+
     /// Throws the exception, returning nothing inside a result if successful and an error
     /// otherwise.
+    ///
+    /// Throwing the exception does not stop the execution of the Rust function, so the function
+    /// should return as soon as possible after throwing. Any value set in the return value of the
+    /// function is discarded by PHP once the exception has been thrown.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     exceptions::PhpException, execution_data::ExecutionData, types::zval::Zval,
+    /// };
+    ///
+    /// pub extern "C" fn example_fn(_: &mut ExecutionData, _: &mut Zval) {
+    ///     let _ = PhpException::default("Bad input".into()).with_code(5).throw();
+    /// }
+    /// ```
     pub fn throw(self) -> Result<()> {
         throw_with_code(self.ex, self.code, &self.message)
     }
@@ -78,6 +140,96 @@ impl<'a> From<&str> for PhpException<'a> {
     }
 }
 
+impl<'a> From<Box<dyn StdError>> for PhpException<'a> {
+    fn from(err: Box<dyn StdError>) -> Self {
+        Self::default(err.to_string())
+    }
+}
+
+impl<'a> From<Box<dyn StdError + Send + Sync>> for PhpException<'a> {
+    fn from(err: Box<dyn StdError + Send + Sync>) -> Self {
+        Self::default(err.to_string())
+    }
+}
+
+#[cfg(feature = "anyhow")]
+#[cfg_attr(docs, doc(cfg(feature = "anyhow")))]
+impl<'a> From<anyhow::Error> for PhpException<'a> {
+    fn from(err: anyhow::Error) -> Self {
+        // The alternate format includes the context of the error, i.e. `outer: inner`.
+        Self::default(format!("{:#}", err))
+    }
+}
+
+/// Implements [`From`] for [`PhpException`] on error types from the standard library, throwing
+/// a regular `Exception` containing the error message.
+macro_rules! from_std_error {
+    ($type: ty) => {
+        impl<'a> From<$type> for PhpException<'a> {
+            fn from(err: $type) -> Self {
+                Self::default(err.to_string())
+            }
+        }
+    };
+}
+
+from_std_error!(ParseIntError);
+from_std_error!(ParseFloatError);
+from_std_error!(ParseBoolError);
+from_std_error!(ParseCharError);
+from_std_error!(TryFromIntError);
+from_std_error!(Utf8Error);
+from_std_error!(FromUtf8Error);
+from_std_error!(NulError);
+from_std_error!(io::Error);
+from_std_error!(fmt::Error);
+
+/// Registers a new exception class which extends a given exception class, inheriting its
+/// methods such as `getMessage()`. The returned class entry can be thrown with
+/// [`PhpException::new`] or [`throw`]. Use the [`macro@crate::php_class`] macro instead if the
+/// exception needs to store Rust data.
+///
+/// This function _must_ be called in the module startup function, as classes cannot be registered
+/// once the module has started. The built-in exception classes are available at this point.
+///
+/// Returns a result containing the registered class entry, or an error if the parent class is not
+/// throwable or the class could not be registered.
+///
+/// # Parameters
+///
+/// * `name` - The name of the exception class, which can be namespaced, i.e. `MyLib\ConfigError`.
+/// * `parent` - The exception class to extend, i.e. [`ClassEntry::exception()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     class::ClassEntry,
+///     exceptions::{register_exception, PhpException},
+/// };
+///
+/// pub extern "C" fn startup_function(_type: i32, _module_number: i32) -> i32 {
+///     match register_exception("MyLib\\ConfigError", ClassEntry::exception()) {
+///         Ok(_) => 0,
+///         Err(_) => -1,
+///     }
+/// }
+///
+/// // Later, when the configuration is invalid:
+/// let ce = ClassEntry::try_find("MyLib\\ConfigError").unwrap();
+/// let _ = PhpException::new("Invalid configuration.".into(), 0, ce).throw();
+/// ```
+pub fn register_exception(name: &str, parent: &'static ClassEntry) -> Result<&'static ClassEntry> {
+    if !parent.instance_of(ClassEntry::throwable()) {
+        return Err(Error::InvalidException(parent.flags()));
+    }
+
+    ClassBuilder::new(name)
+        .extends(parent)
+        .build()
+        .map(|ce| &*ce)
+}
+
 /// Throws an exception with a given message. See [`ClassEntry`] for some built-in exception
 /// types.
 ///
@@ -117,7 +269,7 @@ pub fn throw(ex: &ClassEntry, message: &str) -> Result<()> {
 ///
 /// throw_with_code(ClassEntry::compile_error(), 123, "This is a CompileError.");
 /// ```
-pub fn throw_with_code(ex: &ClassEntry, code: i32, message: &str) -> Result<()> {
+pub fn throw_with_code(ex: &ClassEntry, code: ZendLong, message: &str) -> Result<()> {
     let flags = ex.flags();
 
     // Can't throw an interface or abstract class.
@@ -130,7 +282,7 @@ pub fn throw_with_code(ex: &ClassEntry, code: i32, message: &str) -> Result<()>
     unsafe {
         zend_throw_exception_ex(
             (ex as *const _) as *mut _,
-            code as _,
+            code,
             CString::new("%s")?.as_ptr(),
             CString::new(message)?.as_ptr(),
         )
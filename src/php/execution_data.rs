@@ -2,13 +2,19 @@
 //! introduced in Rust.
 
 use crate::{
-    bindings::{zend_execute_data, ZEND_MM_ALIGNMENT, ZEND_MM_ALIGNMENT_MASK},
+    bindings::{
+        zend_execute_data, ZEND_CALL_HAS_EXTRA_NAMED_PARAMS, ZEND_INTERNAL_FUNCTION,
+        ZEND_MM_ALIGNMENT, ZEND_MM_ALIGNMENT_MASK,
+    },
     errors::{Error, Result},
 };
 
-use super::types::{
-    object::{ClassObject, RegisteredClass, ZendClassObject, ZendObject},
-    zval::Zval,
+use super::{
+    flags::MethodFlags,
+    types::{
+        object::{ClassObject, RegisteredClass, ZendClassObject, ZendObject},
+        zval::Zval,
+    },
 };
 
 /// Execution data passed when a function is called from Zend.
@@ -18,7 +24,8 @@ impl ExecutionData {
     /// Attempts to retrieve a reference to the underlying class object of the Zend object.
     ///
     /// Returns a [`ClassObject`] if the execution data contained a valid object, otherwise
-    /// returns [`None`].
+    /// returns [`None`]. [`None`] is also returned if the object has not been initialized by its
+    /// constructor yet, see [`ClassBuilder::object_override_uninit()`].
     ///
     /// # Safety
     ///
@@ -27,15 +34,171 @@ impl ExecutionData {
     ///
     /// 1. Contains an object.
     /// 2. The object was originally derived from `T`.
+    ///
+    /// [`ClassBuilder::object_override_uninit()`]: crate::php::class::ClassBuilder::object_override_uninit
     pub unsafe fn get_object<T: RegisteredClass>(&self) -> Option<ClassObject<'static, T>> {
-        let ptr = ZendClassObject::<T>::from_zend_obj_ptr(self.This.object()?)?;
+        let ptr = ZendClassObject::<T>::from_zend_obj_ptr(self.get_this()?)?;
+        ptr.obj()?;
         Some(ClassObject::from_zend_class_object(ptr, false))
     }
 
+    /// Attempts to retrieve the object bound to `$this`, which can be used in class methods to
+    /// retrieve the underlying Zend object. If the `This` slot holds a reference to an object, the
+    /// reference is followed.
+    ///
+    /// Returns [`None`] when called from a static method or a free function, as there is no object
+    /// bound to `$this`.
+    pub fn get_this(&self) -> Option<&mut ZendObject> {
+        self.This
+            .object()
+            .or_else(|| self.This.reference()?.object())
+    }
+
     /// Attempts to retrieve the 'this' object, which can be used in class methods
     /// to retrieve the underlying Zend object.
+    ///
+    /// Returns [`Error::InvalidScope`] if there is no object bound to `$this`. See
+    /// [`get_this()`](#method.get_this).
     pub fn get_self(&self) -> Result<&mut ZendObject> {
-        unsafe { self.This.value.obj.as_mut() }.ok_or(Error::InvalidScope)
+        self.get_this().ok_or(Error::InvalidScope)
+    }
+
+    /// Returns the name of the function executing in this frame, i.e. `strlen`, or
+    /// `MyClass::foo` for methods. Names which are not valid UTF-8 are converted lossily.
+    ///
+    /// Returns [`None`] if the frame is not executing a function, such as when it is executing
+    /// the top-level code of a file.
+    pub fn function_name(&self) -> Option<String> {
+        // SAFETY: The function of a frame is either null or a valid function, and all function
+        // types share the `common` layout.
+        let common = unsafe { &self.func.as_ref()?.common };
+        let name = unsafe { common.function_name.as_ref() }?;
+        let name = String::from_utf8_lossy(name.as_bytes());
+
+        match unsafe { common.scope.as_ref().and_then(|ce| ce.name.as_ref()) } {
+            Some(class) => Some(format!(
+                "{}::{}",
+                String::from_utf8_lossy(class.as_bytes()),
+                name
+            )),
+            None => Some(name.into_owned()),
+        }
+    }
+
+    /// Returns the number of arguments the caller passed to the function, including any arguments
+    /// passed after the declared parameters.
+    pub fn num_args(&self) -> usize {
+        unsafe { self.This.u2.num_args as usize }
+    }
+
+    /// Returns whether the caller passed arguments by name which do not match any of the
+    /// parameters of the function. PHP maps arguments passed by name to the position of the
+    /// parameter with the same name before the function is called, and throws an `Error` for
+    /// unknown names, unless the function is variadic. The unknown arguments passed to variadic
+    /// functions are not included in [`num_args()`](#method.num_args).
+    pub fn has_extra_named_args(&self) -> bool {
+        let call_info = unsafe { self.This.u1.type_info };
+        call_info & ZEND_CALL_HAS_EXTRA_NAMED_PARAMS != 0
+    }
+
+    /// Retrieves the argument at the given position, starting at zero, without parsing it.
+    /// Returns [`None`] if the caller did not pass an argument at the position. Arguments passed
+    /// to by-reference parameters are returned as references, and must be dereferenced with
+    /// [`Zval::reference()`].
+    ///
+    /// `$this` is not counted as an argument, as it is stored in a separate slot of the call
+    /// frame. Positions are the same for methods, static methods and functions.
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - The position of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{execution_data::ExecutionData, types::zval::Zval};
+    ///
+    /// // `area(float $width, ?float $height)`, where a square is assumed when only the width is
+    /// // given.
+    /// pub extern "C" fn area(ex: &mut ExecutionData, retval: &mut Zval) {
+    ///     let width = ex.get_arg(0).and_then(|zv| zv.double()).unwrap_or(0.0);
+    ///
+    ///     match ex.num_args() {
+    ///         1 => retval.set_double(width * width),
+    ///         _ => {
+    ///             let height = ex.get_arg(1).and_then(|zv| zv.double()).unwrap_or(0.0);
+    ///             retval.set_double(width * height);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn get_arg(&self, index: usize) -> Option<&Zval> {
+        if index < self.num_args() {
+            unsafe { self.zend_call_var_num(self.arg_slot(index)).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over all of the arguments the caller passed to the function, in order,
+    /// including any arguments passed after the declared parameters. Equivalent to
+    /// `func_get_args()` in PHP. Like [`get_arg()`](#method.get_arg), `$this` is not included and
+    /// arguments are not parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{execution_data::ExecutionData, types::zval::Zval};
+    ///
+    /// // `concat(string ...$parts)`, where arguments which are not strings are skipped.
+    /// pub extern "C" fn concat(ex: &mut ExecutionData, retval: &mut Zval) {
+    ///     let result: String = ex.args_iter().filter_map(|zv| zv.str()).collect();
+    ///     let _ = retval.set_string(&result, false);
+    /// }
+    /// ```
+    pub fn args_iter(&self) -> impl Iterator<Item = &Zval> + '_ {
+        (0..self.num_args()).filter_map(move |index| self.get_arg(index))
+    }
+
+    /// Returns the slot of the argument at the given position, relative to the first argument.
+    ///
+    /// The arguments of internal functions, including those passed after the declared parameters,
+    /// directly follow the call frame. For userland functions, the arguments passed after the
+    /// declared parameters are moved past the compiled and temporary variables of the function,
+    /// in the same way as `func_get_args()` reads them.
+    fn arg_slot(&self, index: usize) -> isize {
+        // SAFETY: The function of a frame is either null or a valid function.
+        if let Some(func) = unsafe { self.func.as_ref() } {
+            if unsafe { func.type_ } != ZEND_INTERNAL_FUNCTION as u8 {
+                let op_array = unsafe { &func.op_array };
+                let first_extra = op_array.num_args as usize;
+
+                if index >= first_extra {
+                    let vars = op_array.last_var as isize + op_array.T as isize;
+                    return vars + (index - first_extra) as isize;
+                }
+            }
+        }
+
+        index as isize
+    }
+
+    /// Returns `true` if the function was called from a file which declares `strict_types=1`.
+    /// In strict mode, scalar arguments must not be coerced into the type of the parameter.
+    ///
+    /// Translation of macro `ZEND_ARG_USES_STRICT_TYPES()`
+    /// zend_compile.h
+    pub fn uses_strict_types(&self) -> bool {
+        // SAFETY: The previous execution data and its function are either null or valid for the
+        // duration of the call.
+        let func =
+            unsafe { self.prev_execute_data.as_ref() }.and_then(|ex| unsafe { ex.func.as_ref() });
+
+        match func {
+            Some(func) => MethodFlags::from_bits_truncate(unsafe { func.common.fn_flags })
+                .contains(MethodFlags::StrictTypes),
+            None => false,
+        }
     }
 
     /// Translation of macro `ZEND_CALL_ARG(call, n)`
@@ -46,6 +209,14 @@ impl ExecutionData {
         ptr.as_ref()
     }
 
+    /// Mutable variant of [`zend_call_arg`](#method.zend_call_arg), used to coerce arguments in
+    /// place.
+    #[doc(hidden)]
+    pub(crate) unsafe fn zend_call_arg_mut(&self, n: usize) -> Option<&'static mut Zval> {
+        let ptr = self.zend_call_var_num(n as isize) as *mut Zval;
+        ptr.as_mut()
+    }
+
     /// Translation of macro `ZEND_CALL_VAR_NUM(call, n)`
     /// zend_compile.h: 575
     #[doc(hidden)]
@@ -73,7 +244,10 @@ impl ExecutionData {
 
 #[cfg(test)]
 mod tests {
+    use std::mem::MaybeUninit;
+
     use super::ExecutionData;
+    use crate::php::types::zval::Zval;
 
     #[test]
     fn test_zend_call_frame_slot() {
@@ -82,4 +256,31 @@ mod tests {
         // Zend Engine v4.0.2, Copyright (c) Zend Technologies
         assert_eq!(ExecutionData::zend_call_frame_slot(), 5);
     }
+
+    #[test]
+    fn test_get_arg() {
+        let slot = ExecutionData::zend_call_frame_slot() as usize;
+        let mut frame = (0..slot + 2).map(|_| Zval::new()).collect::<Vec<_>>();
+        frame[slot].set_long(1);
+        frame[slot + 1].set_long(2);
+
+        let ex = unsafe { &mut *(frame.as_mut_ptr() as *mut ExecutionData) };
+        ex.func = std::ptr::null_mut();
+        ex.This.u2.num_args = 1;
+
+        assert_eq!(ex.num_args(), 1);
+        assert_eq!(ex.get_arg(0).and_then(|zv| zv.long()), Some(1));
+        assert!(ex.get_arg(1).is_none());
+
+        ex.This.u2.num_args = 2;
+        let args = ex.args_iter().map(|zv| zv.long()).collect::<Vec<_>>();
+        assert_eq!(args, vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_function_name_without_function() {
+        // The `func` field of the frame is null.
+        let ex = unsafe { MaybeUninit::<ExecutionData>::zeroed().assume_init() };
+        assert!(ex.function_name().is_none());
+    }
 }
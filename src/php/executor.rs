@@ -0,0 +1,228 @@
+//! Functions for emitting PHP errors, warnings and notices through the executor. Unlike exceptions,
+//! warnings, notices and deprecations do not stop the execution of the script.
+//!
+//! Also contains functions for inspecting the function being executed, and for inspecting and
+//! clearing the exception thrown by PHP code called from Rust.
+
+use std::{
+    ffi::CString,
+    fmt::{self, Debug},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::{
+    bindings::{
+        ext_php_rs_zend_object_release, zend_clear_exception, zend_error,
+        zend_throw_exception_object, E_DEPRECATED, E_ERROR, E_NOTICE, E_WARNING,
+    },
+    errors::Result,
+    php::{
+        globals::ExecutorGlobals,
+        types::{object::ZendObject, zval::Zval},
+    },
+};
+
+/// Emits a warning (`E_WARNING`) with the given message. Execution continues after the warning
+/// has been emitted.
+///
+/// Returns an error if the message contains a NUL byte.
+///
+/// # Parameters
+///
+/// * `message` - The message to display.
+pub fn warning(message: &str) -> Result<()> {
+    emit(E_WARNING, message)
+}
+
+/// Emits a notice (`E_NOTICE`) with the given message. Execution continues after the notice has
+/// been emitted.
+///
+/// Returns an error if the message contains a NUL byte.
+///
+/// # Parameters
+///
+/// * `message` - The message to display.
+pub fn notice(message: &str) -> Result<()> {
+    emit(E_NOTICE, message)
+}
+
+/// Emits a deprecation notice (`E_DEPRECATED`) with the given message. Execution continues after
+/// the notice has been emitted.
+///
+/// Returns an error if the message contains a NUL byte.
+///
+/// # Parameters
+///
+/// * `message` - The message to display.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::{php::executor::deprecated, php_function};
+///
+/// #[php_function]
+/// pub fn legacy_hello() -> String {
+///     let _ = deprecated("legacy_hello() is deprecated, use hello() instead.");
+///     "Hello".into()
+/// }
+/// ```
+pub fn deprecated(message: &str) -> Result<()> {
+    emit(E_DEPRECATED, message)
+}
+
+/// Emits a fatal error (`E_ERROR`) with the given message, ending the request.
+///
+/// When called during a request, PHP bails out of the request by jumping straight back into the
+/// engine, so this function does not return. The destructors of values on the Rust stack are
+/// **not** run, so any values which need to be dropped (such as open files or held locks) must be
+/// dropped before calling this function. If the module has not finished starting up, PHP only
+/// reports the error and this function returns.
+///
+/// Returns an error if the message contains a NUL byte, in which case no error is emitted.
+///
+/// # Parameters
+///
+/// * `message` - The message to display.
+pub fn error(message: &str) -> Result<()> {
+    emit(E_ERROR, message)
+}
+
+/// Emits an error of the given type with the given message. The message is passed as an argument
+/// to a constant format string, so that it is never interpreted as a format string itself.
+fn emit(ty: u32, message: &str) -> Result<()> {
+    let format = CString::new("%s")?;
+    let message = CString::new(message)?;
+
+    unsafe { zend_error(ty as _, format.as_ptr(), message.as_ptr()) };
+    Ok(())
+}
+
+/// Returns the name of the function currently being executed, i.e. `MyClass::foo` when called
+/// from within the handler of the `foo` method. Useful for mentioning the caller in errors and
+/// warnings emitted by shared code. Returns [`None`] if PHP is not executing a function.
+///
+/// Shorthand for [`ExecutorGlobals::current_function_name`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::executor;
+///
+/// fn check_positive(value: i64) -> i64 {
+///     if value < 0 {
+///         let caller = executor::current_function_name().unwrap_or_else(|| "{main}".into());
+///         let _ = executor::warning(&format!("{}() expected a positive value", caller));
+///         return 0;
+///     }
+///
+///     value
+/// }
+/// ```
+pub fn current_function_name() -> Option<String> {
+    ExecutorGlobals::get().current_function_name()
+}
+
+/// Returns `true` if an exception has been thrown and not yet caught. Exceptions thrown by PHP
+/// code called from Rust, such as a [`Callable`], are left pending until control returns to PHP,
+/// where they are rethrown in the calling code.
+///
+/// [`Callable`]: crate::php::types::callable::Callable
+pub fn has_exception() -> bool {
+    ExecutorGlobals::get().exception().is_some()
+}
+
+/// Clears the pending exception, if any, releasing the exception object. Execution then continues
+/// as if the exception had not been thrown.
+pub fn clear_exception() {
+    // SAFETY: Exported C function is safe, and does nothing if no exception is pending.
+    unsafe { zend_clear_exception() };
+}
+
+/// Takes the pending exception out of the executor, clearing it in the same way as
+/// [`clear_exception()`]. Returns [`None`] if no exception is pending.
+///
+/// The returned [`Exception`] holds its own reference to the exception object, so it can be
+/// inspected after it has been cleared, and is released when dropped. It can be thrown again
+/// with [`Exception::rethrow()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     executor,
+///     types::{callable::Callable, zval::Zval},
+/// };
+///
+/// fn call(callback: &Callable) -> Result<Zval, String> {
+///     callback.try_call(vec![]).map_err(|_| match executor::take_exception() {
+///         Some(mut ex) => format!("{}: {}", ex.class_name(), ex.message().unwrap_or_default()),
+///         None => "Failed to call the callback".into(),
+///     })
+/// }
+/// ```
+pub fn take_exception() -> Option<Exception> {
+    let mut ptr = NonNull::new(ExecutorGlobals::get().exception)?;
+
+    // SAFETY: The pending exception is a valid object. A reference is added before clearing the
+    // exception, which releases the reference held by the executor.
+    unsafe {
+        ptr.as_mut().refcount_inc();
+        zend_clear_exception();
+    }
+
+    Some(Exception { ptr })
+}
+
+/// An exception taken from the executor with [`take_exception()`]. Holds a reference to the
+/// exception object, which is released when the exception is dropped.
+pub struct Exception {
+    ptr: NonNull<ZendObject>,
+}
+
+impl Exception {
+    /// Returns the message of the exception, as returned by `getMessage()`.
+    pub fn message(&mut self) -> Option<String> {
+        self.call_method("getMessage", &[]).ok()?.string()
+    }
+
+    /// Throws the exception again, making it the pending exception. The exception is rethrown by
+    /// PHP once control returns to PHP code.
+    pub fn rethrow(mut self) {
+        let mut zv = Zval::new();
+        zv.set_object(&mut self);
+
+        // SAFETY: The zval holds a reference to a valid object, which is taken over by PHP.
+        unsafe { zend_throw_exception_object(&mut zv) };
+    }
+}
+
+impl Deref for Exception {
+    type Target = ZendObject;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: The object is kept alive by the reference held by the exception.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl DerefMut for Exception {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: The object is kept alive by the reference held by the exception.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl Drop for Exception {
+    fn drop(&mut self) {
+        unsafe { ext_php_rs_zend_object_release(self.ptr.as_ptr()) };
+    }
+}
+
+impl Debug for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Exception")
+            .field(&self.class_name())
+            .finish()
+    }
+}
@@ -18,7 +18,7 @@ use crate::bindings::{
     ZEND_ACC_RETURN_REFERENCE, ZEND_ACC_REUSE_GET_ITERATOR, ZEND_ACC_STATIC, ZEND_ACC_STRICT_TYPES,
     ZEND_ACC_TOP_LEVEL, ZEND_ACC_TRAIT, ZEND_ACC_TRAIT_CLONE, ZEND_ACC_UNRESOLVED_VARIANCE,
     ZEND_ACC_USES_THIS, ZEND_ACC_USE_GUARDS, ZEND_ACC_VARIADIC, ZEND_HAS_STATIC_IN_METHODS,
-    Z_TYPE_FLAGS_SHIFT,
+    ZEND_INI_ALL, ZEND_INI_PERDIR, ZEND_INI_SYSTEM, ZEND_INI_USER, Z_TYPE_FLAGS_SHIFT,
 };
 
 bitflags! {
@@ -148,6 +148,20 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// Flags for setting where INI entries can be modified.
+    pub struct IniEntryPermission: u32 {
+        /// Can be set in user scripts through `ini_set()`.
+        const User = ZEND_INI_USER;
+        /// Can be set in `php.ini`, `.htaccess` or `httpd.conf`.
+        const PerDir = ZEND_INI_PERDIR;
+        /// Can be set in `php.ini` or `httpd.conf`.
+        const System = ZEND_INI_SYSTEM;
+        /// Can be set anywhere.
+        const All = ZEND_INI_ALL;
+    }
+}
+
 bitflags! {
     /// Represents the result of a function.
     pub struct ZendResult: i32 {
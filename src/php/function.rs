@@ -1,14 +1,18 @@
 //! Builder and objects used to create functions and methods in PHP.
 
-use std::{ffi::CString, mem, os::raw::c_char, ptr};
+use std::{ffi::CString, mem, os::raw::c_char, ptr, slice};
 
 use crate::errors::Result;
-use crate::{bindings::zend_function_entry, errors::Error};
+use crate::{
+    bindings::{zend_function_entry, _ZEND_TYPE_NAME_BIT},
+    errors::Error,
+};
 
 use super::{
     args::{Arg, ArgInfo},
     enums::DataType,
     execution_data::ExecutionData,
+    flags::MethodFlags,
     types::zval::Zval,
     types::ZendType,
 };
@@ -32,6 +36,44 @@ impl FunctionEntry {
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
+
+    /// Frees the name and argument information of a function entry built by [`FunctionBuilder`].
+    ///
+    /// # Safety
+    ///
+    /// The function entry must have been built by [`FunctionBuilder::build`], and must not have
+    /// been freed before. PHP must no longer reference the function entry.
+    pub(crate) unsafe fn free_members(&mut self) {
+        if !self.fname.is_null() {
+            drop(CString::from_raw(self.fname as *mut c_char));
+            self.fname = ptr::null();
+        }
+
+        if !self.arg_info.is_null() {
+            let len = self.num_args as usize + 1;
+            let args = Box::from_raw(slice::from_raw_parts_mut(
+                self.arg_info as *mut ArgInfo,
+                len,
+            ));
+
+            for (i, arg) in args.iter().enumerate() {
+                // The name of the first entry holds the number of required arguments.
+                if i > 0 && !arg.name.is_null() {
+                    drop(CString::from_raw(arg.name as *mut c_char));
+                }
+
+                if !arg.default_value.is_null() {
+                    drop(CString::from_raw(arg.default_value as *mut c_char));
+                }
+
+                if arg.type_.type_mask & _ZEND_TYPE_NAME_BIT != 0 && !arg.type_.ptr.is_null() {
+                    drop(CString::from_raw(arg.type_.ptr as *mut c_char));
+                }
+            }
+
+            self.arg_info = ptr::null();
+        }
+    }
 }
 
 /// Function representation in Rust.
@@ -41,6 +83,23 @@ pub type FunctionHandler = extern "C" fn(execute_data: &mut ExecutionData, retva
 type FunctionPointerHandler = extern "C" fn(execute_data: *mut ExecutionData, retval: *mut Zval);
 
 /// Builds a function to be exported as a PHP function.
+///
+/// # Performance
+///
+/// Functions exported by extensions are all called through the same internal call path, and
+/// PHP 8.0 has no flags which allow a function to opt into cheaper calls, such as marking it as
+/// pure or its return value as required to be used. The metadata which affects the optimizer is:
+///
+/// * The return type, set with [`returns()`](#method.returns). Opcache uses the declared return
+///   type of internal functions to infer the type of the result, which allows the JIT to
+///   specialise the code using the result. Without a return type, the result may be any type.
+/// * Passing by reference, set with [`Arg::by_ref()`] and
+///   [`returns_by_ref()`](#method.returns_by_ref). Variables passed by reference are turned into
+///   references, which the optimizer cannot reason about.
+///
+/// The types of arguments are used for reflection, named arguments and generated stubs, but are
+/// not checked by the engine when an internal function is called, as arguments are converted
+/// when they are parsed. They do not change the cost of a call.
 #[derive(Debug, Clone)]
 pub struct FunctionBuilder<'a> {
     name: String,
@@ -56,13 +115,18 @@ impl<'a> FunctionBuilder<'a> {
     /// Creates a new function builder, used to build functions
     /// to be exported to PHP.
     ///
+    /// Functions can be placed in a namespace by including it in the name, i.e. `Acme\Math\gcd`.
+    /// PHP registers the name as given, so a leading backslash is removed.
+    ///
     /// # Parameters
     ///
-    /// * `name` - The name of the function.
+    /// * `name` - The name of the function, which can be namespaced.
     /// * `handler` - The handler to be called when the function is invoked from PHP.
     pub fn new<T: Into<String>>(name: T, handler: FunctionHandler) -> Self {
+        let name: String = name.into();
+
         Self {
-            name: name.into(),
+            name: name.strip_prefix('\\').map(Into::into).unwrap_or(name),
             function: FunctionEntry {
                 fname: ptr::null(),
                 handler: Some(unsafe {
@@ -80,6 +144,33 @@ impl<'a> FunctionBuilder<'a> {
         }
     }
 
+    /// Creates a new abstract method builder. Abstract methods have no handler, and must be
+    /// implemented by classes extending the class which declares them. The method must be added
+    /// to a class with the [`MethodFlags::Abstract`] flag set.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the method.
+    ///
+    /// [`MethodFlags::Abstract`]: crate::php::flags::MethodFlags::Abstract
+    pub fn new_abstract<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            function: FunctionEntry {
+                fname: ptr::null(),
+                handler: None,
+                arg_info: ptr::null(),
+                num_args: 0,
+                flags: 0,
+            },
+            args: vec![],
+            n_req: None,
+            retval: None,
+            ret_as_ref: false,
+            ret_as_null: false,
+        }
+    }
+
     /// Creates a constructor builder, used to build the constructor
     /// for classes.
     ///
@@ -100,41 +191,143 @@ impl<'a> FunctionBuilder<'a> {
         self
     }
 
-    /// Sets the rest of the given arguments as not required.
+    /// Adds a variadic argument to the function, which collects all remaining arguments passed to
+    /// the function. This must be the last argument added to the function. If no arguments have
+    /// been set as not required, the variadic argument is set as not required.
+    ///
+    /// The function is marked as variadic, so it is reported as `...$args` by reflection and in
+    /// generated stubs. Arguments which are passed by reference (see [`Arg::by_ref()`]) are
+    /// declared as `&...$args`, and each of the remaining arguments is passed by reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `arg` - The argument to add to the function.
+    pub fn variadic_arg(mut self, arg: Arg<'a>) -> Self {
+        if self.n_req.is_none() {
+            self.n_req = Some(self.args.len());
+        }
+
+        self.args.push(arg.is_variadic());
+        self
+    }
+
+    /// Sets the rest of the given arguments as not required. The arguments added before the first
+    /// call are required, so calling this function again has no effect.
     pub fn not_required(mut self) -> Self {
-        self.n_req = Some(self.args.len());
+        if self.n_req.is_none() {
+            self.n_req = Some(self.args.len());
+        }
         self
     }
 
-    /// Sets the return value of the function.
+    /// Sets the return type of the function. See [`returns_nullable()`](#method.returns_nullable)
+    /// for nullable return types.
     ///
     /// # Parameters
     ///
     /// * `type_` - The return type of the function.
-    /// * `as_ref` - Whether the fucntion returns a reference.
-    /// * `allow_null` - Whether the function return value is nullable.
-    pub fn returns(mut self, type_: DataType, as_ref: bool, allow_null: bool) -> Self {
+    pub fn returns(mut self, type_: DataType) -> Self {
         self.retval = Some(type_);
-        self.ret_as_ref = as_ref;
-        self.ret_as_null = allow_null;
+        self.ret_as_null = false;
+        self
+    }
+
+    /// Sets the return type of the function as nullable, i.e. `?int`.
+    ///
+    /// # Parameters
+    ///
+    /// * `type_` - The return type of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     enums::DataType, execution_data::ExecutionData, function::FunctionBuilder,
+    ///     types::zval::Zval,
+    /// };
+    ///
+    /// pub extern "C" fn find_index(_: &mut ExecutionData, retval: &mut Zval) {
+    ///     retval.set_null();
+    /// }
+    ///
+    /// let function = FunctionBuilder::new("find_index", find_index)
+    ///     .returns_nullable(DataType::Long)
+    ///     .build();
+    /// ```
+    pub fn returns_nullable(mut self, type_: DataType) -> Self {
+        self.retval = Some(type_);
+        self.ret_as_null = true;
+        self
+    }
+
+    /// Sets the function as returning by reference, i.e. `function &foo()`. This can be combined
+    /// with [`returns()`](#method.returns) or [`returns_nullable()`](#method.returns_nullable) to
+    /// also set the return type.
+    pub fn returns_by_ref(mut self) -> Self {
+        self.ret_as_ref = true;
+        self
+    }
+
+    /// Marks the function as deprecated. PHP emits an `E_DEPRECATED` notice each time the
+    /// function is called, before the handler is invoked. Works for both functions and methods, as
+    /// the flags given to [`ClassBuilder::method`] are combined with the flags of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{
+    ///     execution_data::ExecutionData, function::FunctionBuilder, types::zval::Zval,
+    /// };
+    ///
+    /// pub extern "C" fn old_api(_: &mut ExecutionData, _: &mut Zval) {}
+    ///
+    /// // Calling `old_api()` emits "Function old_api() is deprecated".
+    /// let entry = FunctionBuilder::new("old_api", old_api)
+    ///     .deprecated()
+    ///     .build();
+    /// ```
+    ///
+    /// [`ClassBuilder::method`]: crate::php::class::ClassBuilder::method
+    pub fn deprecated(mut self) -> Self {
+        self.function.flags |= MethodFlags::Deprecated.bits();
         self
     }
 
     /// Builds the function converting it into a Zend function entry.
     ///
-    /// Returns a result containing the function entry if successful.
+    /// The number of required arguments is stored in the entry, which PHP uses to reject calls
+    /// with too few arguments and which is reported through reflection. All arguments added
+    /// before [`not_required()`](#method.not_required) was called are required, apart from a
+    /// variadic argument.
+    ///
+    /// Functions with a variadic argument are given the [`MethodFlags::Variadic`] flag.
+    ///
+    /// Returns a result containing the function entry if successful, or an error if a variadic
+    /// argument is not the last argument of the function.
     pub fn build(mut self) -> Result<FunctionEntry> {
-        let mut args = Vec::with_capacity(self.args.len() + 1);
+        let n_args = self.args.len();
+        let mut n_req = self.n_req.unwrap_or(n_args);
+
+        if let Some(pos) = self.args.iter().position(|arg| arg.variadic()) {
+            if pos != n_args - 1 {
+                return Err(Error::VariadicArgument(self.name));
+            }
+            n_req = n_req.min(pos);
+            self.function.flags |= MethodFlags::Variadic.bits();
+        }
 
-        // argument header, retval etc
+        let mut args = Vec::with_capacity(n_args + 1);
+
+        // argument header, retval etc. The name of the header holds the number of required
+        // arguments.
         args.push(ArgInfo {
-            name: self.n_req.unwrap_or(self.args.len()) as *const i8,
+            name: n_req as *const c_char,
             type_: match self.retval {
                 Some(retval) => {
                     ZendType::empty_from_type(retval, self.ret_as_ref, false, self.ret_as_null)
                         .ok_or(Error::InvalidCString)?
                 }
-                None => ZendType::empty(false, false),
+                None => ZendType::empty(self.ret_as_ref, false),
             },
             default_value: ptr::null(),
         });
@@ -143,10 +336,15 @@ impl<'a> FunctionBuilder<'a> {
         args.extend(
             self.args
                 .iter()
-                .map(|arg| arg.as_arg_info())
+                .enumerate()
+                .map(|(i, arg)| arg.as_arg_info(i >= n_req))
                 .collect::<Result<Vec<_>>>()?,
         );
 
+        if self.ret_as_ref {
+            self.function.flags |= MethodFlags::ReturnReference.bits();
+        }
+
         self.function.fname = CString::new(self.name)?.into_raw();
         self.function.num_args = (args.len() - 1) as u32;
         self.function.arg_info = Box::into_raw(args.into_boxed_slice()) as *const ArgInfo;
@@ -154,3 +352,95 @@ impl<'a> FunctionBuilder<'a> {
         Ok(self.function)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FunctionBuilder, FunctionEntry};
+    use crate::{
+        bindings::{_ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT},
+        errors::Error,
+        php::{
+            args::Arg, enums::DataType, execution_data::ExecutionData, flags::MethodFlags,
+            types::zval::Zval,
+        },
+    };
+
+    extern "C" fn handler(_: &mut ExecutionData, _: &mut Zval) {}
+
+    fn required_args(mut entry: FunctionEntry) -> (usize, u32) {
+        let n_req = unsafe { (*entry.arg_info).name } as usize;
+        let n_args = entry.num_args;
+        unsafe { entry.free_members() };
+        (n_req, n_args)
+    }
+
+    #[test]
+    fn test_required_args() {
+        let entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .arg(Arg::new("b", DataType::Long))
+            .not_required()
+            .arg(Arg::new("c", DataType::Long).default(5))
+            .build()
+            .expect("failed to build function");
+        assert_eq!(required_args(entry), (2, 3));
+
+        let entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .not_required()
+            .arg(Arg::new("b", DataType::Long))
+            .not_required()
+            .arg(Arg::new("c", DataType::Long))
+            .build()
+            .expect("failed to build function");
+        assert_eq!(required_args(entry), (1, 3));
+
+        let entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .arg(Arg::new_variadic("b", DataType::Long))
+            .build()
+            .expect("failed to build function");
+        assert_eq!(required_args(entry), (1, 2));
+
+        let entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .build()
+            .expect("failed to build function");
+        assert_eq!(required_args(entry), (1, 1));
+    }
+
+    #[test]
+    fn test_variadic_flags() {
+        let mut entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .variadic_arg(Arg::new("refs", DataType::Mixed).by_ref())
+            .build()
+            .expect("failed to build function");
+
+        assert!(MethodFlags::from_bits_truncate(entry.flags).contains(MethodFlags::Variadic));
+        let mask = |i: usize| unsafe { (*entry.arg_info.add(i)).type_.type_mask };
+        assert_eq!(mask(1) & _ZEND_IS_VARIADIC_BIT, 0);
+        assert_ne!(mask(2) & _ZEND_IS_VARIADIC_BIT, 0);
+        assert_eq!((mask(2) >> _ZEND_SEND_MODE_SHIFT) & 3, 1);
+        unsafe { entry.free_members() };
+
+        let mut entry = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new("a", DataType::Long))
+            .build()
+            .expect("failed to build function");
+        assert!(!MethodFlags::from_bits_truncate(entry.flags).contains(MethodFlags::Variadic));
+        unsafe { entry.free_members() };
+    }
+
+    #[test]
+    fn test_variadic_not_last() {
+        let result = FunctionBuilder::new("test_function", handler)
+            .arg(Arg::new_variadic("a", DataType::Long))
+            .arg(Arg::new("b", DataType::Long))
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(Error::VariadicArgument("test_function".into()))
+        );
+    }
+}
@@ -2,7 +2,10 @@
 
 use crate::bindings::{_zend_executor_globals, ext_php_rs_executor_globals};
 
-use super::types::array::ZendHashTable;
+use super::{
+    execution_data::ExecutionData,
+    types::{array::ZendHashTable, object::ZendObject},
+};
 
 /// Stores global variables used in the PHP executor.
 pub type ExecutorGlobals = _zend_executor_globals;
@@ -24,4 +27,46 @@ impl ExecutorGlobals {
 
         unsafe { ZendHashTable::from_ptr(self.class_table, false) }.ok()
     }
+
+    /// Returns the execution data of the frame currently being executed, or [`None`] if PHP is
+    /// not executing any code.
+    pub fn current_execute_data(&self) -> Option<&ExecutionData> {
+        unsafe { self.current_execute_data.as_ref() }
+    }
+
+    /// Returns the name of the function currently being executed, i.e. `MyClass::foo` when called
+    /// from within the handler of the `foo` method. Useful for mentioning the caller in error
+    /// messages emitted by shared code.
+    ///
+    /// Returns [`None`] if PHP is not executing a function. See
+    /// [`ExecutionData::function_name`], and [`executor::current_function_name`] for a shorthand.
+    ///
+    /// [`executor::current_function_name`]: crate::php::executor::current_function_name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{exceptions::PhpException, globals::ExecutorGlobals};
+    ///
+    /// fn non_empty(input: &str) -> Result<&str, PhpException<'static>> {
+    ///     if input.is_empty() {
+    ///         let caller = ExecutorGlobals::get()
+    ///             .current_function_name()
+    ///             .unwrap_or_else(|| "{main}".into());
+    ///
+    ///         return Err(format!("{}() expected non-empty string", caller).into());
+    ///     }
+    ///
+    ///     Ok(input)
+    /// }
+    /// ```
+    pub fn current_function_name(&self) -> Option<String> {
+        self.current_execute_data()?.function_name()
+    }
+
+    /// Returns the exception which has been thrown and not yet caught, if any. The exception is
+    /// rethrown by PHP once control returns to PHP code.
+    pub fn exception(&self) -> Option<&ZendObject> {
+        unsafe { self.exception.as_ref() }
+    }
 }
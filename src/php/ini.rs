@@ -0,0 +1,252 @@
+//! Builder and functions for registering and reading `php.ini` directives, known as INI entries.
+
+use std::{
+    convert::TryInto,
+    ffi::{c_void, CStr, CString},
+    mem,
+    os::raw::c_int,
+    ptr,
+};
+
+use crate::{
+    bindings::{
+        display_ini_entries as zend_display_ini_entries, zend_ini_entry, zend_ini_entry_def,
+        zend_ini_string_ex, zend_register_ini_entries, zend_string, zend_unregister_ini_entries,
+    },
+    errors::{Error, Result},
+    php::{
+        flags::{IniEntryPermission, ZendResult},
+        module::ModuleEntry,
+        types::string::ZendString,
+    },
+};
+
+/// A Zend INI entry definition. Alias.
+pub type IniEntryDef = zend_ini_entry_def;
+
+/// A function to be called when the value of an INI entry is changed, including when the value is
+/// first set on startup. Returns whether the new value is accepted. If the value is rejected, the
+/// entry keeps its previous value.
+pub type IniChangeHandler = fn(value: &str) -> bool;
+
+/// Builds an INI entry to be registered with [`register_ini_entries`].
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     flags::IniEntryPermission,
+///     ini::{ini_get, register_ini_entries, unregister_ini_entries, IniEntryBuilder},
+/// };
+///
+/// pub extern "C" fn startup_function(_type: i32, module_number: i32) -> i32 {
+///     let entries = vec![
+///         IniEntryBuilder::new("my_ext.enabled", "1").permission(IniEntryPermission::System),
+///         IniEntryBuilder::new("my_ext.level", "5")
+///             .on_change(|value| value.parse::<u8>().map_or(false, |level| level <= 10)),
+///     ];
+///
+///     register_ini_entries(entries, module_number).map_or(-1, |_| 0)
+/// }
+///
+/// pub extern "C" fn shutdown_function(_type: i32, module_number: i32) -> i32 {
+///     unregister_ini_entries(module_number);
+///     0
+/// }
+///
+/// // ...
+/// let level = ini_get("my_ext.level");
+/// ```
+#[derive(Debug, Clone)]
+pub struct IniEntryBuilder {
+    name: String,
+    default: String,
+    permission: IniEntryPermission,
+    on_change: Option<IniChangeHandler>,
+}
+
+impl IniEntryBuilder {
+    /// Creates a new INI entry builder. By default, the entry can be modified anywhere (see
+    /// [`IniEntryPermission::All`]).
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the INI entry, i.e. `my_ext.setting`.
+    /// * `default` - The default value of the entry, used when it is not set in `php.ini`.
+    pub fn new<T: Into<String>, U: Into<String>>(name: T, default: U) -> Self {
+        Self {
+            name: name.into(),
+            default: default.into(),
+            permission: IniEntryPermission::All,
+            on_change: None,
+        }
+    }
+
+    /// Sets where the INI entry can be modified.
+    ///
+    /// # Parameters
+    ///
+    /// * `permission` - Where the entry can be modified. See [`IniEntryPermission`].
+    pub fn permission(mut self, permission: IniEntryPermission) -> Self {
+        self.permission = permission;
+        self
+    }
+
+    /// Sets the function to be called when the value of the INI entry is changed.
+    ///
+    /// # Parameters
+    ///
+    /// * `handler` - The function to be called with the new value of the entry.
+    pub fn on_change(mut self, handler: IniChangeHandler) -> Self {
+        self.on_change = Some(handler);
+        self
+    }
+}
+
+/// Registers a list of INI entries with PHP. This function _must_ be called in the module startup
+/// function, which is given the module number as its second parameter. The entries should be
+/// unregistered in the module shutdown function with [`unregister_ini_entries`].
+///
+/// # Parameters
+///
+/// * `entries` - The INI entries to register.
+/// * `module_number` - The module number that we are registering the entries under.
+pub fn register_ini_entries(entries: Vec<IniEntryBuilder>, module_number: i32) -> Result<()> {
+    // PHP copies the name and value of each entry when registering them, so the strings only need
+    // to live until the entries have been registered.
+    let strings = entries
+        .iter()
+        .map(|entry| {
+            Ok((
+                CString::new(entry.name.as_str())?,
+                CString::new(entry.default.as_str())?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut defs = entries
+        .iter()
+        .zip(strings.iter())
+        .map(|(entry, (name, default))| {
+            Ok(IniEntryDef {
+                name: name.as_ptr(),
+                on_modify: entry.on_change.map(|_| on_modify as _),
+                mh_arg1: entry
+                    .on_change
+                    .map_or(ptr::null_mut(), |handler| handler as *mut c_void),
+                mh_arg2: ptr::null_mut(),
+                mh_arg3: ptr::null_mut(),
+                value: default.as_ptr(),
+                displayer: None,
+                value_length: entry
+                    .default
+                    .len()
+                    .try_into()
+                    .map_err(|_| Error::IntegerOverflow)?,
+                name_length: entry
+                    .name
+                    .len()
+                    .try_into()
+                    .map_err(|_| Error::IntegerOverflow)?,
+                modifiable: entry.permission.bits() as _,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The list of entries is terminated by an entry with a null name.
+    // SAFETY: All fields of the definition are either pointers, optional function pointers or
+    // integers, which are valid when zeroed.
+    defs.push(unsafe { mem::zeroed() });
+
+    let result = unsafe { zend_register_ini_entries(defs.as_ptr(), module_number) };
+
+    if result == ZendResult::Success.bits() {
+        Ok(())
+    } else {
+        Err(Error::IniRegistration)
+    }
+}
+
+/// Unregisters all INI entries registered under a module. This should be called in the module
+/// shutdown function, to prevent the entries from leaking when the extension is reloaded.
+///
+/// # Parameters
+///
+/// * `module_number` - The module number that the entries were registered under.
+pub fn unregister_ini_entries(module_number: i32) {
+    unsafe { zend_unregister_ini_entries(module_number) };
+}
+
+/// Retrieves the current value of an INI entry. Returns [`None`] if the entry does not exist or
+/// does not have a value.
+///
+/// # Parameters
+///
+/// * `name` - The name of the INI entry.
+pub fn ini_get(name: &str) -> Option<String> {
+    let cname = CString::new(name).ok()?;
+    let mut exists = false;
+
+    let value = unsafe { zend_ini_string_ex(cname.as_ptr(), name.len() as _, 0, &mut exists) };
+
+    if !exists || value.is_null() {
+        return None;
+    }
+
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .ok()
+        .map(|value| value.to_string())
+}
+
+/// Prints the INI entries registered under a module as a table in the output of `phpinfo()`,
+/// showing the local and master value of each entry.
+///
+/// [`ModuleBuilder::display_ini_entries`] calls this function after the info function of the
+/// extension. Otherwise, the function can be called from the info function given to
+/// [`ModuleBuilder::info_function`] to print the entries at a different position.
+///
+/// [`ModuleBuilder::display_ini_entries`]: crate::php::module::ModuleBuilder::display_ini_entries
+/// [`ModuleBuilder::info_function`]: crate::php::module::ModuleBuilder::info_function
+///
+/// ```no_run
+/// use ext_php_rs::{
+///     info_table_end, info_table_row, info_table_start,
+///     php::{ini::display_ini_entries, module::ModuleEntry},
+/// };
+///
+/// pub extern "C" fn php_module_info(module: *mut ModuleEntry) {
+///     info_table_start!();
+///     info_table_row!("my extension", "enabled");
+///     info_table_end!();
+///
+///     if let Some(module) = unsafe { module.as_ref() } {
+///         display_ini_entries(module);
+///     }
+/// }
+/// ```
+///
+/// # Parameters
+///
+/// * `module` - The module passed to the info function.
+pub fn display_ini_entries(module: &ModuleEntry) {
+    // PHP only reads the module number from the module, so the pointer is never written to.
+    unsafe { zend_display_ini_entries(module as *const ModuleEntry as *mut ModuleEntry) };
+}
+
+/// Called by PHP when the value of an INI entry with a change handler is modified. The change
+/// handler is stored in the first argument of the entry.
+unsafe extern "C" fn on_modify(
+    _: *mut zend_ini_entry,
+    new_value: *mut zend_string,
+    handler: *mut c_void,
+    _: *mut c_void,
+    _: *mut c_void,
+    _: c_int,
+) -> c_int {
+    let handler: IniChangeHandler = mem::transmute(handler);
+    let value: Result<String> = ZendString::from_ptr(new_value, false).and_then(|s| s.try_into());
+
+    match value {
+        Ok(value) if handler(&value) => ZendResult::Success.bits(),
+        _ => ZendResult::Failure.bits(),
+    }
+}
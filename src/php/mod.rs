@@ -10,9 +10,14 @@ pub mod constants;
 pub mod enums;
 pub mod exceptions;
 pub mod execution_data;
+pub mod executor;
 pub mod flags;
 pub mod function;
 pub mod globals;
+pub mod ini;
 pub mod module;
 pub mod pack;
+#[cfg(any(docs, feature = "stub"))]
+#[cfg_attr(docs, doc(cfg(feature = "stub")))]
+pub mod stub;
 pub mod types;
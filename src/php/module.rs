@@ -1,26 +1,49 @@
 //! Builder and objects for creating modules in PHP. A module is the base of a PHP extension.
 
 use std::{
+    cell::UnsafeCell,
     ffi::{c_void, CString},
-    mem, ptr,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    os::raw::c_char,
+    ptr, slice,
+    sync::atomic::{AtomicPtr, Ordering},
 };
 
 use crate::{
     bindings::{
-        ext_php_rs_php_build_id, zend_module_entry, USING_ZTS, ZEND_DEBUG, ZEND_MODULE_API_NO,
+        ext_php_rs_php_build_id, zend_module_dep, zend_module_entry, MODULE_DEP_CONFLICTS,
+        MODULE_DEP_OPTIONAL, MODULE_DEP_REQUIRED, USING_ZTS, ZEND_DEBUG, ZEND_MODULE_API_NO,
     },
     errors::Result,
 };
 
-use super::function::FunctionEntry;
+use super::{function::FunctionEntry, ini::display_ini_entries};
 
 /// A Zend module entry. Alias.
 pub type ModuleEntry = zend_module_entry;
+/// A Zend module dependency. Alias.
+pub type ModuleDep = zend_module_dep;
 /// A function to be called when the extension is starting up or shutting down.
 pub type StartupShutdownFunc = extern "C" fn(_type: i32, _module_number: i32) -> i32;
+/// A function to be called after a request has been deactivated. Unlike the other lifecycle
+/// functions, it takes no arguments.
+pub type PostDeactivateFunc = extern "C" fn() -> i32;
 /// A function to be called when `phpinfo();` is called.
 pub type InfoFunc = extern "C" fn(zend_module: *mut ModuleEntry);
 
+/// The kind of dependency an extension has on another extension.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    /// The other extension must be loaded, and is loaded before this extension.
+    Required = MODULE_DEP_REQUIRED as u8,
+    /// The other extension is loaded before this extension if it is available.
+    Optional = MODULE_DEP_OPTIONAL as u8,
+    /// The other extension cannot be loaded alongside this extension.
+    Conflicts = MODULE_DEP_CONFLICTS as u8,
+}
+
 /// Builds a Zend extension. Must be called from within an external function called `get_module`,
 /// returning a mutable pointer to a `ModuleEntry`.
 ///
@@ -52,6 +75,8 @@ pub struct ModuleBuilder {
     version: String,
     module: ModuleEntry,
     functions: Vec<FunctionEntry>,
+    dependencies: Vec<(String, DependencyKind)>,
+    display_ini_entries: bool,
 }
 
 impl ModuleBuilder {
@@ -95,6 +120,8 @@ impl ModuleBuilder {
                 build_id: unsafe { ext_php_rs_php_build_id() },
             },
             functions: vec![],
+            dependencies: vec![],
+            display_ini_entries: false,
         }
     }
 
@@ -124,7 +151,7 @@ impl ModuleBuilder {
     ///
     /// * `func` - The function to be called when startup is requested.
     pub fn request_startup_function(mut self, func: StartupShutdownFunc) -> Self {
-        self.module.module_startup_func = Some(func);
+        self.module.request_startup_func = Some(func);
         self
     }
 
@@ -134,7 +161,20 @@ impl ModuleBuilder {
     ///
     /// * `func` - The function to be called when shutdown is requested.
     pub fn request_shutdown_function(mut self, func: StartupShutdownFunc) -> Self {
-        self.module.module_shutdown_func = Some(func);
+        self.module.request_shutdown_func = Some(func);
+        self
+    }
+
+    /// Sets the post-deactivation function for the extension, which is called after every
+    /// extension has run its request shutdown function and the request has been deactivated.
+    /// Useful for cleanup which must run after any PHP code for the request, such as destructors,
+    /// has finished running.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to be called after the request has been deactivated.
+    pub fn post_deactivate_function(mut self, func: PostDeactivateFunc) -> Self {
+        self.module.post_deactivate_func = Some(func);
         self
     }
 
@@ -148,6 +188,44 @@ impl ModuleBuilder {
         self
     }
 
+    /// Displays the INI entries registered by the extension as a table in `phpinfo()`, showing
+    /// the local and master value of each entry, below the rows printed by the function given to
+    /// [`info_function`](#method.info_function).
+    ///
+    /// Without an info function, PHP prints the version of the extension followed by its INI
+    /// entries, so this only needs to be called when an info function is set. An extension only
+    /// has one module, so the info function is stored in a static variable when the module is
+    /// built.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{
+    ///     info_table_end, info_table_row, info_table_start,
+    ///     php::module::{ModuleBuilder, ModuleEntry},
+    /// };
+    ///
+    /// pub extern "C" fn php_module_info(_module: *mut ModuleEntry) {
+    ///     info_table_start!();
+    ///     info_table_row!("my extension", "enabled");
+    ///     info_table_end!();
+    /// }
+    ///
+    /// #[no_mangle]
+    /// pub extern "C" fn get_module() -> *mut ModuleEntry {
+    ///     ModuleBuilder::new("ext-name", "ext-version")
+    ///         .info_function(php_module_info)
+    ///         .display_ini_entries()
+    ///         .build()
+    ///         .unwrap()
+    ///         .into_raw()
+    /// }
+    /// ```
+    pub fn display_ini_entries(mut self) -> Self {
+        self.display_ini_entries = true;
+        self
+    }
+
     /// Adds a function to the extension.
     ///
     /// # Arguments
@@ -158,6 +236,54 @@ impl ModuleBuilder {
         self
     }
 
+    /// Adds a dependency on another extension. PHP ensures that required and optional
+    /// dependencies are loaded before the extension, and refuses to load the extension if a
+    /// required dependency is missing or a conflicting extension is loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the other extension, i.e. `json`.
+    /// * `kind` - The kind of dependency. See [`DependencyKind`].
+    pub fn dependency<T: Into<String>>(mut self, name: T, kind: DependencyKind) -> Self {
+        self.dependencies.push((name.into(), kind));
+        self
+    }
+
+    /// Declares the module globals of the extension, which are accessed through the given
+    /// [`ModuleGlobals`]. An extension can only have one set of module globals.
+    ///
+    /// # Arguments
+    ///
+    /// * `globals` - The statically declared module globals.
+    pub fn globals<T: Default>(mut self, globals: &'static ModuleGlobals<T>) -> Self {
+        unsafe extern "C" fn ctor<T: Default>(ptr: *mut c_void) {
+            let storage = &mut *(ptr as *mut GlobalsStorage<T>);
+            storage.value = MaybeUninit::new(T::default());
+            storage.init = true;
+        }
+
+        unsafe extern "C" fn dtor<T>(ptr: *mut c_void) {
+            let storage = &mut *(ptr as *mut GlobalsStorage<T>);
+            if storage.init {
+                storage.init = false;
+                ptr::drop_in_place(storage.value.as_mut_ptr());
+            }
+        }
+
+        self.module.globals_size = mem::size_of::<GlobalsStorage<T>>() as _;
+        #[cfg(not(php_zts))]
+        {
+            self.module.globals_ptr = globals.value.get() as *mut c_void;
+        }
+        #[cfg(php_zts)]
+        {
+            self.module.globals_id_ptr = globals.id.get();
+        }
+        self.module.globals_ctor = Some(ctor::<T>);
+        self.module.globals_dtor = Some(dtor::<T>);
+        self
+    }
+
     /// Builds the extension and returns a `ModuleEntry`.
     ///
     /// Returns a result containing the module entry if successful.
@@ -165,6 +291,29 @@ impl ModuleBuilder {
         self.functions.push(FunctionEntry::end());
         self.module.functions =
             Box::into_raw(self.functions.into_boxed_slice()) as *const FunctionEntry;
+
+        if !self.dependencies.is_empty() {
+            let mut deps = self
+                .dependencies
+                .into_iter()
+                .map(|(name, kind)| {
+                    Ok(ModuleDep {
+                        name: CString::new(name)?.into_raw(),
+                        rel: ptr::null(),
+                        version: ptr::null(),
+                        type_: kind as u8,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            deps.push(ModuleDep::end());
+            self.module.deps = Box::into_raw(deps.into_boxed_slice()) as *const ModuleDep;
+        }
+
+        if let (true, Some(func)) = (self.display_ini_entries, self.module.info_func) {
+            INFO_FUNCTION.store(func as *mut c_void, Ordering::Relaxed);
+            self.module.info_func = Some(info_with_ini_entries);
+        }
+
         self.module.name = CString::new(self.name)?.into_raw();
         self.module.version = CString::new(self.version)?.into_raw();
 
@@ -172,20 +321,373 @@ impl ModuleBuilder {
     }
 }
 
+/// The info function of the extension when it is wrapped by [`info_with_ini_entries`].
+static INFO_FUNCTION: AtomicPtr<c_void> = AtomicPtr::new(ptr::null_mut());
+
+/// Info function set by [`ModuleBuilder::display_ini_entries`], which calls the info function of
+/// the extension before displaying the INI entries of the module.
+extern "C" fn info_with_ini_entries(module: *mut ModuleEntry) {
+    let func = INFO_FUNCTION.load(Ordering::Relaxed);
+    if !func.is_null() {
+        // SAFETY: The pointer is only ever set to the info function of the module in
+        // `ModuleBuilder::build`, which is called with the module it was registered for.
+        unsafe {
+            let func: unsafe extern "C" fn(*mut ModuleEntry) = mem::transmute(func);
+            func(module);
+        }
+    }
+
+    // SAFETY: PHP passes the module entry of the extension to the info function.
+    if let Some(module) = unsafe { module.as_ref() } {
+        display_ini_entries(module);
+    }
+}
+
+/// Global variables of an extension, declared with [`ModuleBuilder::globals`].
+///
+/// PHP creates the globals with [`Default`] when the extension is started, and drops them when
+/// the extension is shut down. When PHP is built with thread safety (ZTS), each thread has its own
+/// copy of the globals. Globals live for as long as the extension is loaded, so values which
+/// should only last for a single request must be reset in the request startup or shutdown
+/// function.
+///
+/// The globals are only ever accessed by the threads PHP runs on, which is a single thread when
+/// PHP is built without thread safety. This allows types without synchronization, such as
+/// [`Cell`](std::cell::Cell), to be stored in the globals, but means the globals are not safe to
+/// access from threads spawned by the extension, which is why [`get()`](#method.get) is unsafe.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::cell::Cell;
+///
+/// use ext_php_rs::php::{
+///     execution_data::ExecutionData,
+///     module::{ModuleBuilder, ModuleEntry, ModuleGlobals},
+///     types::zval::{IntoZval, Zval},
+/// };
+///
+/// #[derive(Default)]
+/// struct Globals {
+///     calls: Cell<u64>,
+/// }
+///
+/// static GLOBALS: ModuleGlobals<Globals> = ModuleGlobals::new();
+///
+/// extern "C" fn request_startup(_type: i32, _module_number: i32) -> i32 {
+///     // SAFETY: Called by PHP on the thread handling the request.
+///     unsafe { GLOBALS.get() }.calls.set(0);
+///     0
+/// }
+///
+/// pub extern "C" fn count_calls(_: &mut ExecutionData, retval: &mut Zval) {
+///     // SAFETY: Called by PHP on the thread handling the request.
+///     let calls = &unsafe { GLOBALS.get() }.calls;
+///     calls.set(calls.get() + 1);
+///     let _ = calls.get().set_zval(retval, false);
+/// }
+///
+/// #[no_mangle]
+/// pub extern "C" fn get_module() -> *mut ModuleEntry {
+///     ModuleBuilder::new("ext-name", "ext-version")
+///         .globals(&GLOBALS)
+///         .request_startup_function(request_startup)
+///         .build()
+///         .unwrap()
+///         .into_raw()
+/// }
+/// ```
+pub struct ModuleGlobals<T> {
+    #[cfg(not(php_zts))]
+    value: UnsafeCell<GlobalsStorage<T>>,
+    #[cfg(php_zts)]
+    id: UnsafeCell<crate::bindings::ts_rsrc_id>,
+    phantom: PhantomData<T>,
+}
+
+/// The memory PHP allocates for the globals. The value is only initialized once PHP has called
+/// the constructor of the globals, which sets `init`.
+#[repr(C)]
+struct GlobalsStorage<T> {
+    init: bool,
+    value: MaybeUninit<T>,
+}
+
+// SAFETY: The globals can only be accessed through `get()` and `get_mut()`, which are unsafe and
+// require the caller to be on a thread PHP runs on. Without thread safety, that is a single
+// thread. With thread safety, each thread has its own copy of the globals.
+unsafe impl<T> Sync for ModuleGlobals<T> {}
+
+impl<T> ModuleGlobals<T> {
+    /// Creates a new set of module globals. The globals are not usable until they are declared
+    /// with [`ModuleBuilder::globals`] and PHP has constructed them, which happens when the
+    /// extension is started.
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(not(php_zts))]
+            value: UnsafeCell::new(GlobalsStorage {
+                init: false,
+                value: MaybeUninit::uninit(),
+            }),
+            #[cfg(php_zts)]
+            id: UnsafeCell::new(0),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the globals of the current thread. Use types with interior
+    /// mutability, such as [`Cell`](std::cell::Cell), for values which need to be modified, or
+    /// see [`get_mut`](#method.get_mut).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the globals have not been constructed by PHP, either because they were not
+    /// declared with [`ModuleBuilder::globals`] or because the extension has not been started.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be on a thread PHP runs on, i.e. in a function called by PHP, and the
+    /// returned reference must not be sent to or shared with other threads. The globals must not
+    /// be accessed from threads spawned by the extension.
+    pub unsafe fn get(&self) -> &T {
+        &*self.as_ptr()
+    }
+
+    /// Returns a mutable reference to the globals of the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the globals have not been constructed by PHP, see [`get`](#method.get).
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the requirements of [`get`](#method.get). The caller must also
+    /// ensure that there are no other references to the globals while the returned reference is
+    /// alive, including references held by functions further up the call stack, such as a
+    /// function which called back into PHP.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self) -> &mut T {
+        &mut *self.as_ptr()
+    }
+
+    /// Returns a pointer to the globals of the current thread.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be on a thread PHP runs on.
+    unsafe fn as_ptr(&self) -> *mut T {
+        #[cfg(not(php_zts))]
+        let storage = self.value.get();
+
+        #[cfg(php_zts)]
+        let storage = match *self.id.get() {
+            // The identifier is set by PHP when the globals are allocated.
+            0 => ptr::null_mut(),
+            id => crate::bindings::ts_resource_ex(id, ptr::null_mut()) as *mut GlobalsStorage<T>,
+        };
+
+        match storage.as_mut() {
+            Some(storage) if storage.init => storage.value.as_mut_ptr(),
+            _ => panic!("Module globals were accessed before being constructed by PHP."),
+        }
+    }
+}
+
+impl ModuleDep {
+    /// Returns an empty module dependency, signifying the end of a dependency list.
+    pub fn end() -> Self {
+        Self {
+            name: ptr::null(),
+            rel: ptr::null(),
+            version: ptr::null(),
+            type_: 0,
+        }
+    }
+}
+
 impl ModuleEntry {
     /// Converts the module entry into a raw pointer, releasing it to the C world.
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
     }
+
+    /// Frees a module entry which was released with [`into_raw`](#method.into_raw), along with
+    /// the name, version, functions and dependencies allocated by [`ModuleBuilder::build`].
+    ///
+    /// PHP reads the module entry until the engine has shut down, including after the module
+    /// shutdown function has been called, so extensions loaded by PHP should not free their
+    /// module entry. This function is intended for embedding SAPIs which register module entries
+    /// themselves, and can free them after shutting down PHP (i.e. after `php_module_shutdown()`)
+    /// to avoid leaking memory on each startup and shutdown cycle.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been returned by [`into_raw`](#method.into_raw) on a module entry
+    /// built by [`ModuleBuilder`], and must not have been freed before. PHP must no longer
+    /// reference the module entry.
+    pub unsafe fn free_raw(ptr: *mut Self) {
+        let module = Box::from_raw(ptr);
+
+        if !module.name.is_null() {
+            drop(CString::from_raw(module.name as *mut c_char));
+        }
+
+        if !module.version.is_null() {
+            drop(CString::from_raw(module.version as *mut c_char));
+        }
+
+        if !module.functions.is_null() {
+            let mut len = 0;
+            while !(*module.functions.add(len)).fname.is_null() {
+                len += 1;
+            }
+
+            // Includes the end marker.
+            let mut functions = Box::from_raw(slice::from_raw_parts_mut(
+                module.functions as *mut FunctionEntry,
+                len + 1,
+            ));
+
+            for func in functions.iter_mut().take(len) {
+                func.free_members();
+            }
+        }
+
+        if !module.deps.is_null() {
+            let mut len = 0;
+            while !(*module.deps.add(len)).name.is_null() {
+                len += 1;
+            }
+
+            // Includes the end marker.
+            let deps = Box::from_raw(slice::from_raw_parts_mut(
+                module.deps as *mut ModuleDep,
+                len + 1,
+            ));
+
+            for dep in deps.iter().take(len) {
+                drop(CString::from_raw(dep.name as *mut c_char));
+            }
+        }
+    }
 }
 
 /// Called by startup functions registered with the `#[php_startup]` macro. Initializes all
-/// classes that are defined by ext-php-rs (i.e. [`Closure`]).
+/// classes that are defined by ext-php-rs (i.e. [`Closure`] and [`PhpIterator`]).
 ///
 /// [`Closure`]: ext_php_rs::php::types::closure::Closure
+/// [`PhpIterator`]: ext_php_rs::php::types::iterator::PhpIterator
 #[doc(hidden)]
 #[inline(always)]
 pub fn ext_php_rs_startup() {
     #[cfg(feature = "closure")]
     crate::php::types::closure::Closure::build();
+
+    #[cfg(feature = "iterator")]
+    crate::php::types::iterator::PhpIterator::build();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::c_void, sync::atomic::Ordering};
+
+    use super::{GlobalsStorage, ModuleBuilder, ModuleEntry, ModuleGlobals, INFO_FUNCTION};
+
+    extern "C" fn module_startup(_: i32, _: i32) -> i32 {
+        0
+    }
+
+    extern "C" fn module_shutdown(_: i32, _: i32) -> i32 {
+        1
+    }
+
+    extern "C" fn request_startup(_: i32, _: i32) -> i32 {
+        2
+    }
+
+    extern "C" fn request_shutdown(_: i32, _: i32) -> i32 {
+        3
+    }
+
+    #[test]
+    fn test_lifecycle_functions() {
+        let module = ModuleBuilder::new("test", "0.1.0")
+            .startup_function(module_startup)
+            .shutdown_function(module_shutdown)
+            .request_startup_function(request_startup)
+            .request_shutdown_function(request_shutdown)
+            .build()
+            .expect("failed to build module");
+
+        let call = |func: Option<unsafe extern "C" fn(i32, i32) -> i32>| {
+            func.map(|func| unsafe { func(0, 0) })
+        };
+
+        assert_eq!(call(module.module_startup_func), Some(0));
+        assert_eq!(call(module.module_shutdown_func), Some(1));
+        assert_eq!(call(module.request_startup_func), Some(2));
+        assert_eq!(call(module.request_shutdown_func), Some(3));
+    }
+
+    extern "C" fn module_info(_: *mut ModuleEntry) {}
+
+    #[test]
+    fn test_display_ini_entries() {
+        // Without an info function, PHP displays the INI entries itself.
+        let module = ModuleBuilder::new("test", "0.1.0")
+            .display_ini_entries()
+            .build()
+            .expect("failed to build module");
+        assert!(module.info_func.is_none());
+        assert!(INFO_FUNCTION.load(Ordering::Relaxed).is_null());
+
+        let module = ModuleBuilder::new("test", "0.1.0")
+            .info_function(module_info)
+            .display_ini_entries()
+            .build()
+            .expect("failed to build module");
+        assert!(module.info_func.is_some());
+        assert_eq!(
+            INFO_FUNCTION.load(Ordering::Relaxed),
+            module_info as *mut c_void
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u64);
+
+    impl Default for Counter {
+        fn default() -> Self {
+            Self(5)
+        }
+    }
+
+    static COUNTER: ModuleGlobals<Counter> = ModuleGlobals::new();
+
+    #[test]
+    #[cfg(not(php_zts))]
+    fn test_globals() {
+        let module = ModuleBuilder::new("test", "0.1.0")
+            .globals(&COUNTER)
+            .build()
+            .expect("failed to build module");
+
+        assert_eq!(
+            module.globals_size,
+            std::mem::size_of::<GlobalsStorage<Counter>>() as _
+        );
+
+        // Declaring the globals does not construct them.
+        assert!(std::panic::catch_unwind(|| unsafe { COUNTER.get() }.0).is_err());
+
+        // Performed by PHP when the module is started.
+        unsafe { module.globals_ctor.unwrap()(module.globals_ptr) };
+        assert_eq!(unsafe { COUNTER.get() }, &Counter(5));
+
+        unsafe { COUNTER.get_mut().0 += 1 };
+        assert_eq!(unsafe { COUNTER.get() }, &Counter(6));
+
+        unsafe { module.globals_dtor.unwrap()(module.globals_ptr) };
+        assert!(std::panic::catch_unwind(|| unsafe { COUNTER.get() }.0).is_err());
+    }
 }
@@ -1,10 +1,125 @@
 //! Provides implementations for converting to and from Zend binary strings, commonly returned
 //! from functions such as [`pack`] and [`unpack`].
 //!
+//! Also provides the [`pack()`] and [`unpack()`] functions, which call the functions of the same
+//! name built-in to PHP, for packing values with a format string.
+//!
 //! [`pack`]: https://www.php.net/manual/en/function.pack.php
 //! [`unpack`]: https://www.php.net/manual/en/function.unpack.php
 
-use crate::bindings::{ext_php_rs_zend_string_init, zend_string};
+use crate::{
+    bindings::{ext_php_rs_zend_string_init, zend_string},
+    errors::{Error, Result},
+    php::{
+        enums::DataType,
+        types::{array::ZendHashTable, callable::Callable, zval::IntoZvalDyn},
+    },
+};
+
+/// Packs values into a binary string according to a format string, by calling the [`pack`]
+/// function built-in to PHP. All format codes supported by PHP are supported, with the same
+/// layout and byte order, i.e. `n` packs an unsigned 16-bit integer in big endian byte order.
+///
+/// If the format string contains an unknown format code, or does not match the number of
+/// values given, PHP throws a `ValueError` and [`Error::Exception`] is returned. The exception
+/// is left in place and is rethrown once control returns to PHP, unless it is cleared with
+/// [`take_exception()`](crate::php::executor::take_exception).
+///
+/// # Parameters
+///
+/// * `format` - The format string, i.e. `Nd*`.
+/// * `args` - The values to pack.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::pack::pack;
+///
+/// let bytes = pack("n", vec![&0x0102]).unwrap();
+/// assert_eq!(bytes, vec![0x01, 0x02]);
+/// ```
+///
+/// [`pack`]: https://www.php.net/manual/en/function.pack.php
+pub fn pack(format: &str, args: Vec<&dyn IntoZvalDyn>) -> Result<Vec<u8>> {
+    let mut params: Vec<&dyn IntoZvalDyn> = Vec::with_capacity(args.len() + 1);
+    params.push(&format);
+    params.extend(args);
+
+    let packed = Callable::try_from_name("pack")?.try_call(params)?;
+    packed.binary().ok_or(Error::ZvalTypeMismatch(
+        packed.get_type()?,
+        DataType::String,
+    ))
+}
+
+/// Unpacks a binary string according to a format string, by calling the [`unpack`] function
+/// built-in to PHP. Returns an array containing the unpacked values, keyed in the same way as in
+/// PHP, i.e. `Nlength/a*data` returns an array with the keys `length` and `data`.
+///
+/// If the format string contains an unknown format code, PHP throws a `ValueError` and
+/// [`Error::Exception`] is returned. The exception is left in place and is rethrown once control
+/// returns to PHP, unless it is cleared with
+/// [`take_exception()`](crate::php::executor::take_exception). If the data is too short for the
+/// format string, PHP emits a warning and [`Error::ZvalTypeMismatch`] is returned.
+///
+/// # Parameters
+///
+/// * `format` - The format string, i.e. `Nlength/a*data`.
+/// * `data` - The binary string to unpack.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::pack::unpack;
+///
+/// let values = unpack("nvalue", &[0x01, 0x02]).unwrap();
+/// assert_eq!(values.get("value").and_then(|value| value.long()), Some(0x0102));
+/// ```
+///
+/// [`unpack`]: https://www.php.net/manual/en/function.unpack.php
+pub fn unpack(format: &str, data: &[u8]) -> Result<ZendHashTable<'static>> {
+    let unpacked = Callable::try_from_name("unpack")?.try_call(vec![&format, &data])?;
+    if !unpacked.is_array() {
+        return Err(Error::ZvalTypeMismatch(
+            unpacked.get_type()?,
+            DataType::Array,
+        ));
+    }
+
+    // The array returned by `unpack()` is only referenced by the return value, so ownership of it
+    // is taken over by the hash table, which frees it when dropped.
+    let ptr = unsafe { unpacked.value.arr };
+    unpacked.release();
+
+    unsafe { ZendHashTable::from_ptr(ptr, true) }
+}
+
+/// The byte order of packed binary data. Corresponds to the byte order of the format codes given
+/// to the [`pack`] and [`unpack`] functions.
+///
+/// [`pack`]: https://www.php.net/manual/en/function.pack.php
+/// [`unpack`]: https://www.php.net/manual/en/function.unpack.php
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// The byte order of the machine, used by the `s`, `S`, `l`, `L`, `q`, `Q`, `f` and `d` codes.
+    Machine,
+    /// Little endian byte order, used by the `v`, `V`, `P`, `g` and `e` codes.
+    Little,
+    /// Big endian byte order, used by the `n`, `N`, `J`, `G` and `E` codes.
+    Big,
+}
+
+impl ByteOrder {
+    /// Returns whether values in this byte order must have their bytes swapped to be read in the
+    /// byte order of the machine.
+    fn is_swapped(self) -> bool {
+        match self {
+            ByteOrder::Machine => false,
+            ByteOrder::Little => cfg!(target_endian = "big"),
+            ByteOrder::Big => cfg!(target_endian = "little"),
+        }
+    }
+}
 
 /// Used to convert between Zend binary strings and vectors. Useful in conjunction with the
 /// [`pack`] and [`unpack`] functions built-in to PHP.
@@ -49,20 +164,38 @@ pub unsafe trait Pack: Clone {
     ///
     /// [`pack`]: https://www.php.net/manual/en/function.pack.php
     fn unpack_into(s: &zend_string) -> Vec<Self>;
+
+    /// Reverses the byte order of the value.
+    fn swap_bytes(self) -> Self;
+
+    /// Converts the value between the byte order of the machine and the given byte order. As the
+    /// conversion is symmetric, the same function is used to convert in either direction.
+    ///
+    /// # Parameters
+    ///
+    /// * `order` - The byte order to convert to or from.
+    fn convert_byte_order(self, order: ByteOrder) -> Self {
+        if order.is_swapped() {
+            self.swap_bytes()
+        } else {
+            self
+        }
+    }
 }
 
 /// Implements the [`Pack`] trait for a given type.
 macro_rules! pack_impl {
     ($t: ty) => {
-        pack_impl!($t, <$t>::BITS);
+        pack_impl!($t, <$t>::BITS, |v: $t| v.swap_bytes());
     };
 
-    ($t: ty, $d: expr) => {
+    ($t: ty, $d: expr, $swap: expr) => {
         unsafe impl Pack for $t {
             fn pack_into(vec: Vec<Self>) -> *mut zend_string {
                 let len = vec.len() * ($d as usize / 8);
-                let ptr = Box::into_raw(vec.into_boxed_slice());
-                unsafe { ext_php_rs_zend_string_init(ptr as *mut i8, len as _, false) }
+                // The string is initialized with a copy of the data, so the vector can be dropped
+                // afterwards.
+                unsafe { ext_php_rs_zend_string_init(vec.as_ptr() as *mut i8, len as _, false) }
             }
 
             fn unpack_into(s: &zend_string) -> Vec<Self> {
@@ -79,6 +212,10 @@ macro_rules! pack_impl {
 
                 result
             }
+
+            fn swap_bytes(self) -> Self {
+                $swap(self)
+            }
         }
     };
 }
@@ -98,5 +235,5 @@ pack_impl!(i64);
 pack_impl!(isize);
 pack_impl!(usize);
 
-pack_impl!(f32, 32);
-pack_impl!(f64, 64);
+pack_impl!(f32, 32, |v: f32| f32::from_bits(v.to_bits().swap_bytes()));
+pack_impl!(f64, 64, |v: f64| f64::from_bits(v.to_bits().swap_bytes()));
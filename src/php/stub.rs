@@ -0,0 +1,558 @@
+//! Generates PHP stub files from the functions, classes and constants registered by an extension.
+//! Stubs contain the declarations of an extension without their implementations, and are read by
+//! IDEs and static analysers which cannot load the extension themselves.
+//!
+//! The declarations are read from the engine, so stubs can only be generated once the extension
+//! has started up, i.e. from a function called from PHP. A common approach is to export a function
+//! returning the stub when a feature is enabled, and write its output to a file:
+//!
+//! ```no_run
+//! use ext_php_rs::php::{execution_data::ExecutionData, stub, types::zval::Zval};
+//!
+//! pub extern "C" fn hello_stub(_: &mut ExecutionData, retval: &mut Zval) {
+//!     if let Some(stub) = stub::generate("hello") {
+//!         let _ = retval.set_string(&stub, false);
+//!     }
+//! }
+//! ```
+//!
+//! ```sh
+//! php -r 'echo hello_stub();' > hello.stub.php
+//! ```
+//!
+//! A function registered with the arguments and return type of the
+//! [`php_function`](crate::php_function) below is declared as
+//! `function hello_world(string $name): string {}`.
+//!
+//! ```ignore
+//! #[php_function]
+//! pub fn hello_world(name: String) -> String {
+//!     format!("Hello, {}!", name)
+//! }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    ffi::CStr,
+    mem::{self, MaybeUninit},
+    os::raw::c_char,
+    ptr, slice,
+};
+
+use crate::bindings::{
+    module_registry, zend_class_constant, zend_constant, zend_function, zend_hash_str_find_ptr_lc,
+    _zend_property_info, zend_internal_arg_info, zend_internal_function, zend_object, HashTable,
+    IS_ARRAY, IS_CALLABLE, IS_DOUBLE, IS_FALSE, IS_ITERABLE, IS_LONG, IS_OBJECT, IS_STRING,
+    IS_VOID, MAY_BE_ANY, MAY_BE_BOOL, ZEND_INTERNAL_CLASS, ZEND_INTERNAL_FUNCTION,
+    _ZEND_IS_VARIADIC_BIT, _ZEND_SEND_MODE_SHIFT, _ZEND_TYPE_NAME_BIT, _ZEND_TYPE_NULLABLE_BIT,
+};
+
+use super::{
+    class::ClassEntry,
+    constants::IntoConst,
+    enums::DataType,
+    flags::{ClassFlags, ConstantFlags, MethodFlags, PropertyFlags},
+    globals::ExecutorGlobals,
+    module::ModuleEntry,
+    types::{
+        array::{ArrayKey, ZendHashTable},
+        string::ZendStr,
+        zval::Zval,
+        ZendType,
+    },
+};
+
+/// Generates a PHP stub file declaring the functions, classes and constants registered by the
+/// loaded extension with the given name. Classes and functions are placed in a `namespace` block
+/// for each namespace they are declared in.
+///
+/// Returns [`None`] if no extension with the given name has been loaded.
+///
+/// # Parameters
+///
+/// * `module` - The name of the extension, as given to the module builder.
+pub fn generate(module: &str) -> Option<String> {
+    // SAFETY: The module registry is only modified while PHP is starting up or shutting down.
+    let entry = unsafe {
+        (zend_hash_str_find_ptr_lc(
+            ptr::addr_of!(module_registry),
+            module.as_ptr() as *const c_char,
+            module.len() as _,
+        ) as *const ModuleEntry)
+            .as_ref()
+    }?;
+
+    let globals = ExecutorGlobals::get();
+    let mut namespaces = BTreeMap::<String, String>::new();
+
+    for (_, zv) in table(globals.zend_constants).iter().flatten() {
+        // SAFETY: The constant table contains pointers to constants.
+        let constant = unsafe { &*(zv.value.ptr as *const zend_constant) };
+
+        // Translation of the `ZEND_CONSTANT_MODULE_NUMBER` macro from zend_constants.h.
+        if unsafe { constant.value.u2.constant_flags } >> 8 != entry.module_number as u32 {
+            continue;
+        }
+
+        let (namespace, name) = split_name(string(constant.name));
+        let out = namespaces.entry(namespace).or_default();
+        *out += &format!("const {} = {};\n\n", name, value(&constant.value));
+    }
+
+    for (_, zv) in table(globals.function_table).iter().flatten() {
+        // SAFETY: The function table contains pointers to functions.
+        let func = unsafe { &*(zv.value.ptr as *const zend_function) };
+
+        if unsafe { func.type_ } as u32 != ZEND_INTERNAL_FUNCTION {
+            continue;
+        }
+
+        let func = unsafe { &func.internal_function };
+        if !ptr::eq(func.module as *const ModuleEntry, entry) {
+            continue;
+        }
+
+        let (namespace, name) = split_name(string(func.function_name));
+        let out = namespaces.entry(namespace).or_default();
+        *out += &format!("{} {{}}\n\n", signature(func, &name));
+    }
+
+    for (key, zv) in table(globals.class_table).iter().flatten() {
+        // SAFETY: The class table contains pointers to class entries.
+        let ce = unsafe { &*(zv.value.ptr as *const ClassEntry) };
+
+        if ce.type_ as u32 != ZEND_INTERNAL_CLASS
+            || !ptr::eq(
+                unsafe { ce.info.internal.module } as *const ModuleEntry,
+                entry,
+            )
+        {
+            continue;
+        }
+
+        let full_name = string(ce.name);
+
+        // Class aliases are stored under the name of the alias.
+        if key != ArrayKey::String(full_name.to_lowercase()) {
+            continue;
+        }
+
+        let (namespace, name) = split_name(full_name);
+        let out = namespaces.entry(namespace).or_default();
+        class(out, ce, &name);
+    }
+
+    let mut stub = String::from("<?php\n\n// Stubs for ");
+    stub += &string_from_ptr(entry.name);
+    stub += "\n\n";
+
+    for (namespace, body) in namespaces {
+        if namespace.is_empty() {
+            stub += "namespace {\n\n";
+        } else {
+            stub += &format!("namespace {} {{\n\n", namespace);
+        }
+
+        stub += &body;
+        stub += "}\n\n";
+    }
+
+    stub.truncate(stub.trim_end().len());
+    stub.push('\n');
+    Some(stub)
+}
+
+/// Writes the declaration of a class or interface and its members.
+fn class(out: &mut String, ce: &ClassEntry, name: &str) {
+    let flags = ce.flags();
+    let parent = ce.parent();
+
+    if flags.contains(ClassFlags::Interface) {
+        *out += &format!("interface {}", name);
+    } else {
+        if flags.contains(ClassFlags::Final) {
+            *out += "final ";
+        }
+        if flags.contains(ClassFlags::Abstract) {
+            *out += "abstract ";
+        }
+
+        *out += &format!("class {}", name);
+
+        if let Some(parent) = parent {
+            *out += &format!(" extends \\{}", string(parent.name));
+        }
+    }
+
+    // Interfaces implemented by the parent class are inherited.
+    let interfaces = ce
+        .interfaces()
+        .into_iter()
+        .flatten()
+        .filter(|iface| parent.map_or(true, |parent| !parent.instance_of(iface)))
+        .map(|iface| format!("\\{}", string(iface.name)))
+        .collect::<Vec<_>>();
+
+    if !interfaces.is_empty() {
+        let keyword = if ce.is_interface() {
+            "extends"
+        } else {
+            "implements"
+        };
+        *out += &format!(" {} {}", keyword, interfaces.join(", "));
+    }
+
+    *out += "\n{\n";
+    let mut members = vec![];
+
+    for (key, zv) in table(&ce.constants_table as *const _ as *mut _)
+        .iter()
+        .flatten()
+    {
+        // SAFETY: The constant table contains pointers to class constants.
+        let constant = unsafe { &*(zv.value.ptr as *const zend_class_constant) };
+
+        if !ptr::eq(constant.ce as *const ClassEntry, ce) {
+            continue;
+        }
+
+        // Translation of the `ZEND_CLASS_CONST_FLAGS` macro from zend_compile.h.
+        let flags = ConstantFlags::from_bits_truncate(unsafe { constant.value.u2.access_flags });
+        members.push(format!(
+            "{} const {} = {};",
+            visibility(flags.bits()),
+            key,
+            value(&constant.value)
+        ));
+    }
+
+    for (key, zv) in table(&ce.properties_info as *const _ as *mut _)
+        .iter()
+        .flatten()
+    {
+        // SAFETY: The property table contains pointers to property information.
+        let info = unsafe { &*(zv.value.ptr as *const _zend_property_info) };
+
+        if !ptr::eq(info.ce as *const ClassEntry, ce) {
+            continue;
+        }
+
+        // The table is keyed by the declared name, while `info.name` is mangled with the class
+        // name for private and protected properties.
+        let name = match key {
+            ArrayKey::String(name) => name,
+            ArrayKey::Long(_) => continue,
+        };
+
+        let flags = PropertyFlags::from_bits_truncate(info.flags);
+        let mut property = visibility(flags.bits()).to_string();
+        let default = if flags.contains(PropertyFlags::Static) {
+            property += " static";
+            unsafe {
+                ce.default_static_members_table
+                    .add(info.offset as usize)
+                    .as_ref()
+            }
+        } else {
+            unsafe {
+                ce.default_properties_table
+                    .add(property_num(info.offset))
+                    .as_ref()
+            }
+        };
+
+        if let Some(ty) = type_name(&info.type_) {
+            property += &format!(" {}", ty);
+        }
+
+        property += &format!(" ${}", name);
+
+        if let Some(default) = default.filter(|zv| !matches!(zv.get_type(), Ok(DataType::Undef))) {
+            property += &format!(" = {}", value(default));
+        }
+
+        members.push(property + ";");
+    }
+
+    for (_, zv) in table(&ce.function_table as *const _ as *mut _)
+        .iter()
+        .flatten()
+    {
+        // SAFETY: The function table contains pointers to functions.
+        let func = unsafe { &*(zv.value.ptr as *const zend_function) };
+
+        if unsafe { func.type_ } as u32 != ZEND_INTERNAL_FUNCTION {
+            continue;
+        }
+
+        let func = unsafe { &func.internal_function };
+        if !ptr::eq(func.scope as *const ClassEntry, ce) {
+            continue;
+        }
+
+        let flags = MethodFlags::from_bits_truncate(func.fn_flags);
+        let mut method = String::new();
+
+        if flags.contains(MethodFlags::Abstract) && !ce.is_interface() {
+            method += "abstract ";
+        }
+        if flags.contains(MethodFlags::Final) {
+            method += "final ";
+        }
+
+        method += visibility(flags.bits());
+
+        if flags.contains(MethodFlags::Static) {
+            method += " static";
+        }
+
+        method += &format!(" {}", signature(func, &string(func.function_name)));
+        method += if flags.contains(MethodFlags::Abstract) {
+            ";"
+        } else {
+            " {}"
+        };
+
+        members.push(method);
+    }
+
+    for member in members {
+        *out += &format!("    {}\n", member);
+    }
+
+    *out += "}\n\n";
+}
+
+/// Returns the declaration of a function, i.e. `function foo(int $x): int`.
+fn signature(func: &zend_internal_function, name: &str) -> String {
+    let mut signature = String::from("function ");
+    let flags = MethodFlags::from_bits_truncate(func.fn_flags);
+
+    if flags.contains(MethodFlags::ReturnReference) {
+        signature.push('&');
+    }
+
+    signature += name;
+
+    // The variadic argument is not counted in the number of arguments.
+    let num_args = func.num_args as usize + flags.contains(MethodFlags::Variadic) as usize;
+    let args: &[zend_internal_arg_info] = if func.arg_info.is_null() {
+        &[]
+    } else {
+        // SAFETY: The argument information contains an entry for each argument.
+        unsafe { slice::from_raw_parts(func.arg_info, num_args) }
+    };
+
+    let args = args.iter().map(arg).collect::<Vec<_>>();
+    signature += &format!("({})", args.join(", "));
+
+    // The return type is stored before the first argument.
+    if !func.arg_info.is_null() {
+        if let Some(ty) = type_name(unsafe { &(*func.arg_info.offset(-1)).type_ }) {
+            signature += &format!(": {}", ty);
+        }
+    }
+
+    signature
+}
+
+/// Returns the declaration of an argument, i.e. `?int &$x = null`.
+fn arg(arg: &zend_internal_arg_info) -> String {
+    let mut out = String::new();
+
+    if let Some(ty) = type_name(&arg.type_) {
+        out += &ty;
+        out.push(' ');
+    }
+
+    if (arg.type_.type_mask >> _ZEND_SEND_MODE_SHIFT) & 3 != 0 {
+        out.push('&');
+    }
+
+    if arg.type_.type_mask & _ZEND_IS_VARIADIC_BIT != 0 {
+        out += "...";
+    }
+
+    out += &format!("${}", string_from_ptr(arg.name));
+
+    if !arg.default_value.is_null() {
+        out += &format!(" = {}", string_from_ptr(arg.default_value));
+    }
+
+    out
+}
+
+/// Returns the declaration of a type, i.e. `?int` or `string|array|null`. Returns [`None`] if
+/// the type is not set.
+fn type_name(ty: &ZendType) -> Option<String> {
+    let mask = ty.type_mask;
+
+    if mask & MAY_BE_ANY == MAY_BE_ANY {
+        return Some("mixed".into());
+    }
+
+    let mut types = vec![];
+
+    if mask & _ZEND_TYPE_NAME_BIT != 0 {
+        // Class names are converted into Zend strings when the function is registered.
+        types.push(format!("\\{}", string(ty.ptr as *const ZendStr)));
+    }
+
+    for (bit, name) in &[
+        (IS_OBJECT, "object"),
+        (IS_ARRAY, "array"),
+        (IS_STRING, "string"),
+        (IS_LONG, "int"),
+        (IS_DOUBLE, "float"),
+        (IS_CALLABLE, "callable"),
+        (IS_ITERABLE, "iterable"),
+        (IS_VOID, "void"),
+    ] {
+        if mask & (1u32 << bit) != 0 {
+            types.push(name.to_string());
+        }
+    }
+
+    if mask & MAY_BE_BOOL == MAY_BE_BOOL {
+        types.push("bool".into());
+    } else if mask & (1u32 << IS_FALSE) != 0 {
+        types.push("false".into());
+    }
+
+    if mask & _ZEND_TYPE_NULLABLE_BIT != 0 {
+        if types.len() == 1 {
+            return Some(format!("?{}", types[0]));
+        }
+
+        types.push("null".into());
+    }
+
+    if types.is_empty() {
+        None
+    } else {
+        Some(types.join("|"))
+    }
+}
+
+/// Returns the PHP literal for the value of a constant or default property value. Scalars are
+/// written in the same way as the default values of arguments, see [`IntoConst::as_default`].
+fn value(zv: &Zval) -> String {
+    match zv.get_type() {
+        Ok(DataType::True) => true.as_default(),
+        Ok(DataType::False) => false.as_default(),
+        Ok(DataType::Long) => zv.long().unwrap_or_default().as_default(),
+        Ok(DataType::Double) => zv.double().unwrap_or_default().as_default(),
+        Ok(DataType::String) => {
+            let bytes = zv.zend_str().map(ZendStr::as_bytes).unwrap_or_default();
+            let str: &str = &String::from_utf8_lossy(bytes);
+            str.as_default()
+        }
+        Ok(DataType::Array) => {
+            let arr = match zv.array() {
+                Some(arr) => arr,
+                None => return "[]".into(),
+            };
+
+            let is_list = arr
+                .iter()
+                .enumerate()
+                .all(|(i, (key, _))| key == ArrayKey::Long(i as _));
+
+            let entries = arr
+                .iter()
+                .map(|(key, val)| match key {
+                    _ if is_list => value(val),
+                    ArrayKey::Long(key) => format!("{} => {}", key, value(val)),
+                    ArrayKey::String(key) => format!("{} => {}", key.as_default(), value(val)),
+                })
+                .collect::<Vec<_>>();
+
+            format!("[{}]", entries.join(", "))
+        }
+        _ => ().as_default(),
+    }
+}
+
+/// Returns the visibility keyword for the given member flags.
+fn visibility(flags: u32) -> &'static str {
+    let flags = MethodFlags::from_bits_truncate(flags);
+
+    if flags.contains(MethodFlags::Private) {
+        "private"
+    } else if flags.contains(MethodFlags::Protected) {
+        "protected"
+    } else {
+        "public"
+    }
+}
+
+/// Converts the offset of a property into its index in the default properties table.
+/// Translation of the `OBJ_PROP_TO_NUM` macro from zend_object_handlers.h.
+fn property_num(offset: u32) -> usize {
+    let obj = MaybeUninit::<zend_object>::uninit();
+    let base = obj.as_ptr() as usize;
+    // SAFETY: Only the address of the field is taken, the object is not read.
+    let table = unsafe { ptr::addr_of!((*obj.as_ptr()).properties_table) } as usize;
+
+    (offset as usize - (table - base)) / mem::size_of::<Zval>()
+}
+
+/// Splits a name into its namespace and unqualified name.
+fn split_name(name: String) -> (String, String) {
+    match name.rfind('\\') {
+        Some(i) => (name[..i].to_string(), name[i + 1..].to_string()),
+        None => (String::new(), name),
+    }
+}
+
+/// Wraps a hash table owned by the engine, returning [`None`] if the pointer is null.
+fn table<'a>(ht: *mut HashTable) -> Option<ZendHashTable<'a>> {
+    unsafe { ZendHashTable::from_ptr(ht, false) }.ok()
+}
+
+/// Converts a Zend string into a Rust string, replacing invalid UTF-8.
+fn string(s: *const ZendStr) -> String {
+    match unsafe { s.as_ref() } {
+        Some(s) => String::from_utf8_lossy(s.as_bytes()).into_owned(),
+        None => String::new(),
+    }
+}
+
+/// Converts a C string into a Rust string, replacing invalid UTF-8.
+fn string_from_ptr(s: *const c_char) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+
+    unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_name, type_name};
+    use crate::php::{enums::DataType, types::ZendType};
+
+    #[test]
+    fn test_type_name() {
+        let name = |ty, nullable| {
+            type_name(&ZendType::empty_from_type(ty, false, false, nullable).unwrap())
+        };
+
+        assert_eq!(name(DataType::Long, false).as_deref(), Some("int"));
+        assert_eq!(name(DataType::String, true).as_deref(), Some("?string"));
+        assert_eq!(name(DataType::Bool, false).as_deref(), Some("bool"));
+        assert_eq!(name(DataType::Mixed, true).as_deref(), Some("mixed"));
+        assert_eq!(name(DataType::Void, false).as_deref(), Some("void"));
+        assert_eq!(name(DataType::Iterable, false).as_deref(), Some("iterable"));
+        assert_eq!(type_name(&ZendType::empty(false, false)), None);
+    }
+
+    #[test]
+    fn test_split_name() {
+        assert_eq!(
+            split_name("Acme\\Math\\gcd".into()),
+            ("Acme\\Math".into(), "gcd".into())
+        );
+        assert_eq!(split_name("gcd".into()), (String::new(), "gcd".into()));
+    }
+}
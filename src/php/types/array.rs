@@ -5,7 +5,7 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     ffi::CString,
-    fmt::Debug,
+    fmt::{Debug, Display},
     marker::PhantomData,
     u64,
 };
@@ -18,10 +18,11 @@ use crate::{
         HT_MIN_SIZE,
     },
     errors::{Error, Result},
+    php::enums::DataType,
 };
 
 use super::{
-    string::ZendString,
+    long::ZendLong,
     zval::{FromZval, IntoZval, Zval},
 };
 
@@ -34,6 +35,25 @@ pub enum HashTableInsertResult<'a> {
     OkWithOverwrite(&'a Zval),
 }
 
+/// The key of an element in a PHP array. PHP arrays can be keyed by either integers or strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArrayKey {
+    /// An integer key.
+    Long(ZendLong),
+    /// A string key. Keys which are not valid UTF-8 are converted lossily, replacing invalid
+    /// sequences with [`U+FFFD REPLACEMENT CHARACTER`](std::char::REPLACEMENT_CHARACTER).
+    String(String),
+}
+
+impl Display for ArrayKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Long(key) => write!(f, "{}", key),
+            Self::String(key) => write!(f, "{}", key),
+        }
+    }
+}
+
 /// A PHP array, which internally is a hash table.
 pub struct ZendHashTable<'a> {
     ptr: *mut HashTable,
@@ -105,6 +125,9 @@ impl<'a> ZendHashTable<'a> {
 
     /// Attempts to retrieve a value from the hash table with a string key.
     ///
+    /// Keys which are integer strings, such as `"5"`, are looked up as integer keys, in the same
+    /// way as PHP normalizes array keys.
+    ///
     /// # Parameters
     ///
     /// * `key` - The key to search for in the hash table.
@@ -114,6 +137,10 @@ impl<'a> ZendHashTable<'a> {
     /// * `Some(&Zval)` - A reference to the zval at the position in the hash table.
     /// * `None` - No value at the given position was found.
     pub fn get(&self, key: &str) -> Option<&Zval> {
+        if let Some(index) = numeric_key(key) {
+            return self.get_index(index as u64);
+        }
+
         let str = CString::new(key).ok()?;
         unsafe { zend_hash_str_find(self.ptr, str.as_ptr(), key.len() as _).as_ref() }
     }
@@ -132,52 +159,78 @@ impl<'a> ZendHashTable<'a> {
         unsafe { zend_hash_index_find(self.ptr, key).as_ref() }
     }
 
-    /// Attempts to remove a value from the hash table with a string key.
+    /// Returns whether the hash table contains the given string key.
+    ///
+    /// Keys which are integer strings, such as `"5"`, are looked up as integer keys, in the same
+    /// way as PHP normalizes array keys.
     ///
     /// # Parameters
     ///
-    /// * `key` - The key to remove from the hash table.
+    /// * `key` - The key to search for in the hash table.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Returns whether the hash table contains the given index.
     ///
-    /// # Returns
+    /// # Parameters
     ///
-    /// * `Some(())` - Key was successfully removed.
-    /// * `None` - No key was removed, did not exist.
-    pub fn remove<K>(&self, key: &str) -> Option<()> {
-        let result = unsafe {
-            zend_hash_str_del(self.ptr, CString::new(key).ok()?.as_ptr(), key.len() as _)
-        };
-
-        if result < 0 {
-            None
-        } else {
-            Some(())
-        }
+    /// * `index` - The index to search for in the hash table.
+    pub fn contains_index(&self, index: i64) -> bool {
+        self.get_index(index as u64).is_some()
     }
 
-    /// Attempts to remove a value from the hash table with a string key.
+    /// Removes a value from the hash table with a string key. Returns whether a value was
+    /// removed.
+    ///
+    /// Keys which are integer strings, such as `"5"`, remove the value at the integer key, in the
+    /// same way as PHP normalizes array keys.
     ///
     /// # Parameters
     ///
     /// * `key` - The key to remove from the hash table.
     ///
-    /// # Returns
+    /// # Examples
     ///
-    /// * `Ok(())` - Key was successfully removed.
-    /// * `None` - No key was removed, did not exist.
-    pub fn remove_index(&self, key: u64) -> Option<()> {
-        let result = unsafe { zend_hash_index_del(self.ptr, key) };
-
-        if result < 0 {
-            None
-        } else {
-            Some(())
+    /// ```no_run
+    /// use ext_php_rs::{php::types::array::ZendHashTable, php_function};
+    ///
+    /// // `without_key(['a' => 1, 5 => 2], '5')` returns `['a' => 1]`.
+    /// #[php_function]
+    /// pub fn without_key(arr: ZendHashTable, key: String) -> ZendHashTable {
+    ///     let mut arr = arr.clone();
+    ///     arr.remove(&key);
+    ///     arr
+    /// }
+    /// ```
+    pub fn remove(&mut self, key: &str) -> bool {
+        if let Some(index) = numeric_key(key) {
+            return self.remove_index(index);
         }
+
+        let str = match CString::new(key) {
+            Ok(str) => str,
+            Err(_) => return false,
+        };
+        unsafe { zend_hash_str_del(self.ptr, str.as_ptr(), key.len() as _) >= 0 }
+    }
+
+    /// Removes a value from the hash table at an index. Returns whether a value was removed.
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - The index to remove from the hash table.
+    pub fn remove_index(&mut self, index: i64) -> bool {
+        unsafe { zend_hash_index_del(self.ptr, index as u64) >= 0 }
     }
 
     /// Attempts to insert an item into the hash table, or update if the key already exists.
     /// Returns a result containing a [`HashTableInsertResult`], which will indicate a successful
     /// insert, with the insert result variants either containing the overwritten value or nothing.
     ///
+    /// Keys which are integer strings, such as `"5"`, are inserted as integer keys, in the same way
+    /// as PHP normalizes array keys.
+    ///
     /// # Parameters
     ///
     /// * `key` - The key to insert the value at in the hash table.
@@ -186,6 +239,10 @@ impl<'a> ZendHashTable<'a> {
     where
         V: IntoZval,
     {
+        if let Some(index) = numeric_key(key) {
+            return self.insert_at_index(index as u64, val);
+        }
+
         let mut val = val.into_zval(false)?;
         let existing_ptr = unsafe {
             zend_hash_str_update(
@@ -266,13 +323,28 @@ impl<'a> ZendHashTable<'a> {
     }
 }
 
+/// Returns the integer a string array key is normalized to by PHP, if any. Only canonical decimal
+/// integers within the range of a PHP integer are normalized, so `"5"` and `"-5"` are integer keys
+/// while `"05"`, `"+5"`, `"-0"` and `"5.0"` remain string keys.
+pub(crate) fn numeric_key(key: &str) -> Option<i64> {
+    let digits = key.strip_prefix('-').unwrap_or(key);
+    let canonical = match digits.as_bytes() {
+        [b'0'] => digits.len() == key.len(),
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    };
+
+    if canonical {
+        key.parse().ok()
+    } else {
+        None
+    }
+}
+
 impl<'a> Debug for ZendHashTable<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_map()
-            .entries(
-                self.iter()
-                    .map(|(k, k2, v)| (k2.unwrap_or_else(|| k.to_string()), v)),
-            )
+            .entries(self.iter().map(|(k, v)| (k.to_string(), v)))
             .finish()
     }
 }
@@ -303,7 +375,7 @@ impl<'a> Clone for ZendHashTable<'a> {
 }
 
 impl<'a> IntoIterator for ZendHashTable<'a> {
-    type Item = (u64, Option<String>, &'a Zval);
+    type Item = (ArrayKey, &'a Zval);
     type IntoIter = IntoIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -311,6 +383,17 @@ impl<'a> IntoIterator for ZendHashTable<'a> {
     }
 }
 
+impl<'a, 'b> IntoIterator for &'b ZendHashTable<'a> {
+    type Item = (ArrayKey, &'b Zval);
+    type IntoIter = Iter<'b>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter::new(self)
+    }
+}
+
+// Packed (list) arrays share the same bucket layout as hashed arrays, with a null string key and
+// the index stored in `h`, so both are walked the same way in insertion order.
 macro_rules! build_iter {
     ($name: ident, $ht: ty) => {
         pub struct $name<'a> {
@@ -329,22 +412,32 @@ macro_rules! build_iter {
         }
 
         impl<'a> Iterator for $name<'a> {
-            type Item = (u64, Option<String>, &'a Zval);
+            type Item = (ArrayKey, &'a Zval);
 
             fn next(&mut self) -> Option<Self::Item> {
+                // skip over buckets which have been deleted from the hash table
+                while self.pos != self.end
+                    && matches!(unsafe { (*self.pos).val.get_type() }, Ok(DataType::Undef))
+                {
+                    self.pos = unsafe { self.pos.offset(1) };
+                }
+
                 // iterator complete
                 if self.pos == self.end {
                     return None;
                 }
 
                 let result = if let Some(val) = unsafe { self.pos.as_ref() } {
-                    // SAFETY: We can ensure safety further by checking if it is null before
-                    // converting it to a reference (val.key.as_ref() returns None if ptr == null)
-                    let str_key = unsafe { ZendString::from_ptr(val.key, false) }
-                        .and_then(|s| s.try_into())
-                        .ok();
-
-                    Some((val.h, str_key, &val.val))
+                    // Buckets with integer keys have a null key, storing the integer in the hash.
+                    let key = match unsafe { val.key.as_ref() } {
+                        Some(key) => {
+                            ArrayKey::String(String::from_utf8_lossy(key.as_bytes()).into_owned())
+                        }
+                        // Integer keys are signed in PHP, even though they are stored as unsigned.
+                        None => ArrayKey::Long(val.h as ZendLong),
+                    };
+
+                    Some((key, &val.val))
                 } else {
                     None
                 };
@@ -366,6 +459,12 @@ macro_rules! build_iter {
 build_iter!(Iter, &'a ZendHashTable<'a>);
 build_iter!(IntoIter, ZendHashTable<'a>);
 
+/// Implementation for converting a `ZendHashTable` into a `HashMap` of given type.
+/// Will return an error type if one of the values inside the array cannot be converted into
+/// a type `T`.
+///
+/// Integer keys are converted into strings, matching the way PHP coerces array keys. Note that
+/// a `HashMap` does not preserve the insertion order of the array.
 impl<'a, V> TryFrom<ZendHashTable<'a>> for HashMap<String, V>
 where
     V: FromZval<'a>,
@@ -375,9 +474,9 @@ where
     fn try_from(zht: ZendHashTable<'a>) -> Result<Self> {
         let mut hm = HashMap::with_capacity(zht.len());
 
-        for (idx, key, val) in zht.into_iter() {
+        for (key, val) in zht.into_iter() {
             hm.insert(
-                key.unwrap_or_else(|| idx.to_string()),
+                key.to_string(),
                 V::from_zval(val).ok_or(Error::ZvalConversion(val.get_type()?))?,
             );
         }
@@ -386,6 +485,8 @@ where
     }
 }
 
+/// Implementation converting a Rust HashMap into a ZendHashTable. Elements are inserted in the
+/// iteration order of the map.
 impl<'a, K, V> TryFrom<HashMap<K, V>> for ZendHashTable<'a>
 where
     K: AsRef<str>,
@@ -425,9 +526,13 @@ where
     }
 }
 
-/// Implementation for converting a reference to `ZendHashTable` into a `Vec` of given type.
+/// Implementation for converting a `ZendHashTable` into a `Vec` of given type.
 /// Will return an error type if one of the values inside the array cannot be converted into
 /// a type `T`.
+///
+/// Values are collected in the order they were inserted into the array. Keys are discarded, so
+/// an associative array (or a list with string keys mixed in) produces a vector of its values,
+/// regardless of whether the keys were sequential or not.
 impl<'a, V> TryFrom<ZendHashTable<'a>> for Vec<V>
 where
     V: FromZval<'a>,
@@ -436,7 +541,7 @@ where
 
     fn try_from(ht: ZendHashTable<'a>) -> Result<Self> {
         ht.into_iter()
-            .map(|(_, _, v)| V::from_zval(v).ok_or(Error::ZvalConversion(v.get_type()?)))
+            .map(|(_, v)| V::from_zval(v).ok_or(Error::ZvalConversion(v.get_type()?)))
             .collect::<Result<Vec<_>>>()
     }
 }
@@ -476,3 +581,75 @@ where
         Ok(ht)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_key() {
+        assert_eq!(numeric_key("0"), Some(0));
+        assert_eq!(numeric_key("5"), Some(5));
+        assert_eq!(numeric_key("-5"), Some(-5));
+        assert_eq!(numeric_key("9223372036854775807"), Some(i64::MAX));
+        assert_eq!(numeric_key("-9223372036854775808"), Some(i64::MIN));
+
+        assert_eq!(numeric_key(""), None);
+        assert_eq!(numeric_key("-"), None);
+        assert_eq!(numeric_key("-0"), None);
+        assert_eq!(numeric_key("05"), None);
+        assert_eq!(numeric_key("+5"), None);
+        assert_eq!(numeric_key(" 5"), None);
+        assert_eq!(numeric_key("5.0"), None);
+        assert_eq!(numeric_key("9223372036854775808"), None);
+        assert_eq!(numeric_key("five"), None);
+    }
+
+    #[test]
+    fn test_iter_keys() {
+        use crate::bindings::zend_string;
+        use std::mem::MaybeUninit;
+
+        #[repr(C)]
+        struct Key {
+            str_: zend_string,
+            rest: [u8; 8],
+        }
+
+        // A string key which is not valid UTF-8.
+        let mut key = unsafe { MaybeUninit::<Key>::zeroed().assume_init() };
+        key.str_.len = 3;
+        unsafe {
+            let base = &mut key as *mut Key as *mut u8;
+            let val = base.add(key.str_.val.as_ptr() as usize - base as usize);
+            val.copy_from_nonoverlapping(b"a\xffb".as_ptr(), 3);
+        }
+
+        let mut buckets: Vec<_Bucket> = (0..2)
+            .map(|_| unsafe { MaybeUninit::zeroed().assume_init() })
+            .collect();
+        buckets[0].val.set_long(1);
+        buckets[0].h = -3i64 as u64;
+        buckets[1].val.set_long(2);
+        buckets[1].key = &mut key.str_;
+
+        let mut raw: HashTable = unsafe { MaybeUninit::zeroed().assume_init() };
+        raw.arData = buckets.as_mut_ptr();
+        raw.nNumUsed = 2;
+        raw.nNumOfElements = 2;
+
+        let ht = ZendHashTable {
+            ptr: &mut raw,
+            free: false,
+            phantom: PhantomData,
+        };
+        let keys: Vec<_> = ht.iter().map(|(key, _)| key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                ArrayKey::Long(-3),
+                ArrayKey::String("a\u{fffd}b".to_string())
+            ]
+        );
+    }
+}
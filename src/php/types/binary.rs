@@ -8,7 +8,10 @@ use std::{
 
 use crate::{
     errors::{Error, Result},
-    php::{enums::DataType, pack::Pack},
+    php::{
+        enums::DataType,
+        pack::{ByteOrder, Pack},
+    },
 };
 
 use super::zval::{FromZval, IntoZval, Zval};
@@ -28,6 +31,31 @@ impl<T: Pack> Binary<T> {
     pub fn new(data: impl Into<Vec<T>>) -> Self {
         Self(data.into())
     }
+
+    /// Converts each value in the binary wrapper between the byte order of the machine and the
+    /// given byte order. Use this on data received from PHP which was packed with an explicit
+    /// byte order to read the values, and on data being returned to PHP which will be unpacked
+    /// with an explicit byte order.
+    ///
+    /// # Parameters
+    ///
+    /// * `order` - The byte order to convert to or from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ext_php_rs::php::{pack::ByteOrder, types::binary::Binary};
+    ///
+    /// // Data packed in PHP with `pack('N*', 1, 2)`.
+    /// let data = Binary::new(vec![1u32.to_be(), 2u32.to_be()]);
+    /// assert_eq!(*data.convert_byte_order(ByteOrder::Big), vec![1, 2]);
+    /// ```
+    pub fn convert_byte_order(self, order: ByteOrder) -> Self {
+        self.0
+            .into_iter()
+            .map(|val| val.convert_byte_order(order))
+            .collect()
+    }
 }
 
 impl<T: Pack> Deref for Binary<T> {
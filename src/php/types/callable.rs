@@ -1,11 +1,15 @@
 //! Types related to callables in PHP (anonymous functions, functions, etc).
 
-use std::ops::Deref;
+use std::{mem, ptr};
 
-use super::zval::{IntoZvalDyn, Zval};
+use super::zval::{IntoZval, IntoZvalDyn, OwnedZval, Zval};
 use crate::{
-    bindings::_call_user_function_impl,
+    bindings::{
+        _call_user_function_impl, zend_call_function, zend_fcall_info, zend_fcall_info_cache,
+        zend_is_callable_ex,
+    },
     errors::{Error, Result},
+    php::{flags::ZendResult, globals::ExecutorGlobals},
 };
 
 /// Acts as a wrapper around a callable [`Zval`]. Allows the owner to call the [`Zval`] as if it
@@ -13,30 +17,6 @@ use crate::{
 #[derive(Debug)]
 pub struct Callable<'a>(OwnedZval<'a>);
 
-/// A container for a zval. Either contains a reference to a zval or an owned zval.
-#[derive(Debug)]
-enum OwnedZval<'a> {
-    Reference(&'a Zval),
-    Owned(Zval),
-}
-
-impl<'a> OwnedZval<'a> {
-    fn as_ref(&self) -> &Zval {
-        match self {
-            OwnedZval::Reference(zv) => *zv,
-            OwnedZval::Owned(zv) => zv,
-        }
-    }
-}
-
-impl<'a> Deref for OwnedZval<'a> {
-    type Target = Zval;
-
-    fn deref(&self) -> &Self::Target {
-        self.as_ref()
-    }
-}
-
 impl<'a> Callable<'a> {
     /// Attempts to create a new [`Callable`] from a zval.
     ///
@@ -82,10 +62,84 @@ impl<'a> Callable<'a> {
         Self::new_owned(callable)
     }
 
-    /// Attempts to call the callable with a list of arguments to pass to the function.
-    /// Note that a thrown exception inside the callable is not detectable, therefore you should
-    /// check if the return value is valid rather than unwrapping. Returns a result containing the
-    /// return value of the function, or an error.
+    /// Calls the callable with a list of arguments of the same type, returning the return value
+    /// of the function.
+    ///
+    /// The callable is resolved with `zend_is_callable_ex()` and called through the resulting
+    /// function call cache, so a closure is called with its bound `$this`, scope and captured
+    /// variables, in the same way as if it was called from PHP.
+    ///
+    /// If an exception is thrown inside the callable, [`Error::Exception`] is returned. The
+    /// exception is left in place and is rethrown once control returns to PHP, unless it is
+    /// cleared with [`take_exception()`](crate::php::executor::take_exception).
+    ///
+    /// # Parameters
+    ///
+    /// * `args` - The arguments to call the function with.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::types::callable::Callable;
+    ///
+    /// fn add(cb: Callable) -> Option<i64> {
+    ///     cb.invoke(vec![1, 2]).ok()?.long()
+    /// }
+    /// ```
+    pub fn invoke<A: IntoZval>(&self, args: Vec<A>) -> Result<Zval> {
+        let callable = self.0.as_ref() as *const Zval as *mut Zval;
+
+        // SAFETY: The cache is a plain C struct which is filled by `zend_is_callable_ex()`.
+        let mut fcc: zend_fcall_info_cache = unsafe { mem::zeroed() };
+        let valid = unsafe {
+            zend_is_callable_ex(
+                callable,
+                ptr::null_mut(),
+                0,
+                ptr::null_mut(),
+                &mut fcc,
+                ptr::null_mut(),
+            )
+        };
+
+        if !valid {
+            return Err(Error::Callable);
+        }
+
+        let mut params = args
+            .into_iter()
+            .map(|arg| arg.into_zval(false))
+            .collect::<Result<Vec<_>>>()?;
+        let mut retval = Zval::new();
+
+        let mut fci = zend_fcall_info {
+            size: mem::size_of::<zend_fcall_info>() as _,
+            function_name: Zval::new(),
+            retval: &mut retval,
+            params: params.as_mut_ptr(),
+            object: fcc.object,
+            param_count: params.len() as _,
+            named_params: ptr::null_mut(),
+        };
+
+        let result = unsafe { zend_call_function(&mut fci, &mut fcc) };
+
+        if result != ZendResult::Success.bits() {
+            Err(Error::Callable)
+        } else if ExecutorGlobals::get().exception().is_some() {
+            Err(Error::Exception)
+        } else {
+            Ok(retval)
+        }
+    }
+
+    /// Attempts to call the callable with a list of arguments to pass to the function. The
+    /// callable can be a function name, a closure or an `[object, 'method']` array. Returns a
+    /// result containing the return value of the function, or an error.
+    ///
+    /// If an exception is thrown inside the callable, [`Error::Exception`] is returned. The
+    /// exception is left in place and is rethrown once control returns to PHP, so the caller
+    /// should stop what it is doing and return.
     ///
     /// You should not call this function directly, rather through the [`call_user_func`] macro.
     ///
@@ -118,6 +172,8 @@ impl<'a> Callable<'a> {
 
         if result < 0 {
             Err(Error::Callable)
+        } else if ExecutorGlobals::get().exception().is_some() {
+            Err(Error::Exception)
         } else {
             Ok(retval)
         }
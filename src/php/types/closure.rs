@@ -120,7 +120,7 @@ impl Closure {
                 FunctionBuilder::new("__invoke", Self::invoke)
                     .not_required()
                     .arg(Arg::new("args", DataType::Mixed).is_variadic())
-                    .returns(DataType::Mixed, false, true)
+                    .returns_nullable(DataType::Mixed)
                     .build()
                     .expect("Failed to build `RustClosure` PHP class."),
                 MethodFlags::Public,
@@ -0,0 +1,125 @@
+//! Conversions between PHP date objects and [`chrono`] date times.
+//!
+//! PHP stores dates as objects of classes implementing `DateTimeInterface`, such as `DateTime`
+//! and `DateTimeImmutable`. These are converted through their Unix timestamp, keeping microsecond
+//! precision.
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{
+    errors::{Error, Result},
+    php::{class::ClassEntry, enums::DataType},
+};
+
+use super::zval::{FromZval, IntoZval, Zval};
+
+/// Accepts any object implementing `DateTimeInterface`. The point in time is kept, while the time
+/// zone of the PHP object is discarded.
+impl FromZval<'_> for DateTime<Utc> {
+    const TYPE: DataType = DataType::Object(Some("DateTimeInterface"));
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let obj = zval.object()?;
+        let interface = ClassEntry::try_find("DateTimeInterface")?;
+
+        if !unsafe { obj.ce.as_ref() }?.instance_of(interface) {
+            return None;
+        }
+
+        let timestamp = obj
+            .call_method("format", &["U.u".into_zval(false).ok()?])
+            .ok()?;
+
+        parse_timestamp(timestamp.str()?)
+    }
+}
+
+/// Returned to PHP as a `DateTimeImmutable` object in the UTC time zone.
+impl IntoZval for DateTime<Utc> {
+    const TYPE: DataType = DataType::Object(Some("DateTimeImmutable"));
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let ce = ClassEntry::try_find("DateTimeImmutable")
+            .ok_or_else(|| Error::UnknownClass("DateTimeImmutable".into()))?;
+
+        zv.init_object(ce)?
+            .call_method("__construct", &[format_timestamp(&self).into_zval(false)?])?;
+        Ok(())
+    }
+}
+
+/// Parses a timestamp formatted by PHP with the `U.u` format, where `U` is the number of whole
+/// seconds since the Unix epoch, rounded down, and `u` is the number of microseconds past that
+/// second. Returns [`None`] if the timestamp is invalid.
+///
+/// # Parameters
+///
+/// * `timestamp` - The formatted timestamp, i.e. `-2.500000` for 1.5 seconds before the epoch.
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    let (secs, micros) = timestamp.split_once('.')?;
+    let micros = micros
+        .parse::<u32>()
+        .ok()
+        .filter(|micros| *micros < 1_000_000)?;
+
+    Utc.timestamp_opt(secs.parse().ok()?, micros * 1000)
+        .single()
+}
+
+/// Formats a date time as a timestamp accepted by the `DateTimeImmutable` constructor. The
+/// constructor accepts a signed timestamp with a fractional part, i.e. `@-1.500000`, which is
+/// built from the total number of microseconds to get the sign right.
+///
+/// # Parameters
+///
+/// * `date` - The date time to format.
+fn format_timestamp(date: &DateTime<Utc>) -> String {
+    let micros =
+        date.timestamp() as i128 * 1_000_000 + date.timestamp_subsec_micros().min(999_999) as i128;
+
+    format!(
+        "@{}{}.{:06}",
+        if micros < 0 { "-" } else { "" },
+        micros.abs() / 1_000_000,
+        micros.abs() % 1_000_000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{format_timestamp, parse_timestamp};
+
+    fn date(secs: i64, micros: u32) -> DateTime<Utc> {
+        Utc.timestamp_opt(secs, micros * 1000).unwrap()
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("0.000000"), Some(date(0, 0)));
+        assert_eq!(
+            parse_timestamp("1625097600.123456"),
+            Some(date(1625097600, 123456))
+        );
+        // PHP rounds negative timestamps down, so -1.5 seconds is formatted as `-2.500000`.
+        assert_eq!(parse_timestamp("-2.500000"), Some(date(-2, 500000)));
+
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("1625097600"), None);
+        assert_eq!(parse_timestamp("now.000000"), None);
+        assert_eq!(parse_timestamp("1.-5"), None);
+        assert_eq!(parse_timestamp("1.1000000"), None);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(&date(0, 0)), "@0.000000");
+        assert_eq!(
+            format_timestamp(&date(1625097600, 123456)),
+            "@1625097600.123456"
+        );
+        assert_eq!(format_timestamp(&date(-2, 500000)), "@-1.500000");
+        assert_eq!(format_timestamp(&date(-1, 750000)), "@-0.250000");
+    }
+}
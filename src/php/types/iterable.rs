@@ -0,0 +1,188 @@
+//! Represents the `iterable` pseudo-type in PHP, accepting either an array or a `Traversable`
+//! object, such as a `Generator`.
+
+use crate::{
+    errors::{Error, Result},
+    php::{class::ClassEntry, enums::DataType},
+};
+
+use super::{
+    array::{ArrayKey, Iter as ArrayIter, ZendHashTable},
+    object::ZendObject,
+    zval::{FromZval, OwnedZval, Zval},
+};
+
+/// A value passed to an `iterable` parameter, which is either an array or an object implementing
+/// `Traversable`. Both can be iterated with [`iter()`](#method.iter) without branching on the
+/// kind of value.
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::{errors::Result, php::types::iterable::Iterable, php_function};
+///
+/// // Accepts `sum([1, 2, 3])` as well as `sum((function () { yield 1; yield 2; })())`.
+/// #[php_function]
+/// pub fn sum(mut items: Iterable) -> Result<i64> {
+///     let mut total = 0;
+///     for item in items.iter() {
+///         let (_, value) = item?;
+///         total += value.long().unwrap_or(0);
+///     }
+///     Ok(total)
+/// }
+/// ```
+pub enum Iterable<'a> {
+    /// An array.
+    Array(ZendHashTable<'a>),
+    /// An object implementing `Traversable`, i.e. an `Iterator`, an `IteratorAggregate` or a
+    /// `Generator`.
+    Traversable(&'a mut ZendObject),
+}
+
+impl<'a> Iterable<'a> {
+    /// Returns an iterator over the keys and values of the iterable.
+    ///
+    /// Arrays are iterated in order, and their values are borrowed from the array. Traversable
+    /// objects are iterated through the `Iterator` interface in the same way as `foreach`:
+    /// `IteratorAggregate::getIterator()` is called until an `Iterator` is returned, which is then
+    /// rewound and advanced. Keys and values are retrieved lazily, so generators only run as far
+    /// as they are iterated, and are owned by the item.
+    ///
+    /// Each item is a result, as iterating a traversable object runs PHP code. If an exception is
+    /// thrown, [`Error::Exception`] is returned and iteration stops. The exception is left in
+    /// place and is rethrown once control returns to PHP, unless it is cleared with
+    /// [`take_exception()`](crate::php::executor::take_exception).
+    pub fn iter(&mut self) -> Iter<'_> {
+        match self {
+            Self::Array(arr) => Iter::Array(arr.iter()),
+            Self::Traversable(obj) => Iter::Traversable(Traversal {
+                object: &mut **obj,
+                aggregates: vec![],
+                started: false,
+                done: false,
+            }),
+        }
+    }
+}
+
+impl<'a> FromZval<'a> for Iterable<'a> {
+    const TYPE: DataType = DataType::Iterable;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        if let Some(arr) = zval.array() {
+            return Some(Self::Array(arr));
+        }
+
+        let traversable = ClassEntry::traversable()?;
+        zval.object()
+            .filter(|obj| obj.instance_of(traversable))
+            .map(Self::Traversable)
+    }
+}
+
+/// Iterator over the keys and values of an [`Iterable`].
+pub enum Iter<'a> {
+    /// Iterator over an array.
+    Array(ArrayIter<'a>),
+    /// Iterator over a traversable object.
+    Traversable(Traversal<'a>),
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Result<(Zval, OwnedZval<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Array(iter) => iter.next().map(|(key, val)| {
+                let mut zv = Zval::new();
+                match key {
+                    ArrayKey::Long(key) => zv.set_long(key),
+                    ArrayKey::String(key) => zv.set_string(&key, false)?,
+                }
+                Ok((zv, OwnedZval::Reference(val)))
+            }),
+            Self::Traversable(traversal) => traversal
+                .next()
+                .map(|item| item.map(|(key, val)| (key, OwnedZval::Owned(val)))),
+        }
+    }
+}
+
+/// Iterates a traversable object through the `Iterator` interface.
+pub struct Traversal<'a> {
+    object: &'a mut ZendObject,
+    /// The iterators returned by `getIterator()`, the last of which is being iterated. Objects are
+    /// not released when a zval is dropped, so the iterators are kept alive until the end of the
+    /// request.
+    aggregates: Vec<Zval>,
+    started: bool,
+    done: bool,
+}
+
+impl Traversal<'_> {
+    /// Returns the object implementing `Iterator` which is being iterated.
+    fn iterator(&mut self) -> Result<&mut ZendObject> {
+        match self.aggregates.last() {
+            Some(zv) => zv
+                .object()
+                .ok_or(Error::ZvalTypeMismatch(zv.get_type()?, DataType::Iterable)),
+            None => Ok(&mut *self.object),
+        }
+    }
+
+    /// Advances the iterator, returning the next key and value, or [`None`] if the iterator is no
+    /// longer valid.
+    fn advance(&mut self) -> Result<Option<(Zval, Zval)>> {
+        if self.started {
+            self.iterator()?.call_method("next", &[])?;
+        } else {
+            self.started = true;
+
+            let iterator = ClassEntry::iterator().ok_or(Error::InvalidPointer)?;
+            let traversable = ClassEntry::traversable().ok_or(Error::InvalidPointer)?;
+
+            while !self.iterator()?.instance_of(iterator) {
+                let inner = self.iterator()?.call_method("getIterator", &[])?;
+                let is_traversable = inner
+                    .object()
+                    .map_or(false, |obj| obj.instance_of(traversable));
+
+                if !is_traversable {
+                    return Err(Error::ZvalTypeMismatch(
+                        inner.get_type()?,
+                        DataType::Iterable,
+                    ));
+                }
+                self.aggregates.push(inner);
+            }
+
+            self.iterator()?.call_method("rewind", &[])?;
+        }
+
+        let iterator = self.iterator()?;
+        if !iterator.call_method("valid", &[])?.is_true() {
+            return Ok(None);
+        }
+
+        let value = iterator.call_method("current", &[])?;
+        let key = iterator.call_method("key", &[])?;
+        Ok(Some((key, value)))
+    }
+}
+
+impl Iterator for Traversal<'_> {
+    type Item = Result<(Zval, Zval)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.advance().transpose();
+        if !matches!(item, Some(Ok(_))) {
+            self.done = true;
+        }
+        item
+    }
+}
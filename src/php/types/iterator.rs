@@ -0,0 +1,298 @@
+//! Types used for exporting Rust iterators to PHP.
+
+use crate::{
+    errors::Result,
+    php::{
+        class::{ClassBuilder, ClassEntry},
+        enums::DataType,
+        exceptions::PhpException,
+        execution_data::ExecutionData,
+        flags::MethodFlags,
+        function::{FunctionBuilder, FunctionHandler},
+        types::object::ClassMetadata,
+    },
+};
+
+use super::{
+    long::ZendLong,
+    object::RegisteredClass,
+    zval::{IntoZval, Zval},
+};
+
+/// Class entry and handlers for Rust iterators.
+static ITERATOR_META: ClassMetadata<PhpIterator> = ClassMetadata::new();
+
+/// Rust iterator which yields key-value pairs that have been converted into zvals.
+type ZvalIterator = Box<dyn Iterator<Item = Result<(Zval, Zval)>>>;
+
+/// Wrapper around a Rust iterator, which can be exported to PHP and consumed lazily, for example
+/// with `foreach`.
+///
+/// Internally, iterators are implemented as a PHP class. A class `RustIterator` is registered
+/// which implements the `Iterator` interface:
+///
+/// ```php
+/// <?php
+///
+/// class RustIterator implements Iterator {
+///     public function current(): mixed {}
+///     public function key(): mixed {}
+///     public function next(): void {}
+///     public function rewind(): void {}
+///     public function valid(): bool {}
+/// }
+/// ```
+///
+/// The Rust iterator is boxed and stored inside the PHP object. Items are only pulled from the
+/// iterator and converted into zvals when PHP advances it, so the sequence is never materialized
+/// as a whole. Only the current key and value are held in memory. Like a PHP `Generator`, the
+/// iterator can only be traversed once - rewinding it after it has been advanced throws an
+/// exception.
+///
+/// # Lifetimes
+///
+/// The wrapped iterator must have a static lifetime, as it is owned by the PHP object and lives
+/// for as long as PHP holds a reference to the object, which is usually well after the Rust
+/// function that created it has returned. The iterator therefore cannot borrow from `self` or
+/// any local variables; move owned data into it instead. The iterator, along with anything it
+/// captured, is dropped when the PHP object is freed, which happens at the end of the request at
+/// the latest.
+pub struct PhpIterator {
+    iter: Option<ZvalIterator>,
+    current: Option<(Zval, Zval)>,
+    started: bool,
+    advanced: bool,
+}
+
+unsafe impl Send for PhpIterator {}
+unsafe impl Sync for PhpIterator {}
+
+impl PhpIterator {
+    /// Wraps a Rust iterator into a type which can be returned to PHP. The items of the iterator
+    /// are the values seen by PHP, and are keyed with their position, starting at zero.
+    ///
+    /// The items must implement [`IntoZval`], and the iterator must have a static lifetime. See
+    /// the [struct documentation](PhpIterator) for more details.
+    ///
+    /// # Parameters
+    ///
+    /// * `iter` - The iterator to wrap.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ext_php_rs::php::types::iterator::PhpIterator;
+    ///
+    /// let iter = PhpIterator::wrap(0..1_000_000);
+    /// ```
+    pub fn wrap<I>(iter: I) -> Self
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: IntoZval,
+    {
+        Self::wrap_keyed(
+            iter.into_iter()
+                .enumerate()
+                .map(|(i, val)| (i as ZendLong, val)),
+        )
+    }
+
+    /// Wraps a Rust iterator of key-value pairs into a type which can be returned to PHP. Keys
+    /// can be of any type which implements [`IntoZval`], and do not have to be unique.
+    ///
+    /// # Parameters
+    ///
+    /// * `iter` - The iterator to wrap.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ext_php_rs::php::types::iterator::PhpIterator;
+    ///
+    /// let words = vec!["hello", "world"];
+    /// let iter = PhpIterator::wrap_keyed(words.into_iter().map(|word| (word, word.len())));
+    /// ```
+    pub fn wrap_keyed<I, K, V>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        I::IntoIter: 'static,
+        K: IntoZval,
+        V: IntoZval,
+    {
+        let iter = iter
+            .into_iter()
+            .map(|(key, val)| Ok((key.into_zval(false)?, val.into_zval(false)?)));
+
+        Self {
+            iter: Some(Box::new(iter)),
+            current: None,
+            started: false,
+            advanced: false,
+        }
+    }
+
+    /// Builds the class entry for [`PhpIterator`], registering it with PHP. This function should
+    /// only be called once inside your module startup function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function is called more than once.
+    pub fn build() {
+        if ITERATOR_META.has_ce() {
+            panic!("PhpIterator has already been built.");
+        }
+
+        let method = |name: &str, handler: FunctionHandler, ty: DataType, allow_null: bool| {
+            let func = FunctionBuilder::new(name, handler);
+            let func = if allow_null {
+                func.returns_nullable(ty)
+            } else {
+                func.returns(ty)
+            };
+
+            func.build()
+                .expect("Failed to build `RustIterator` PHP class.")
+        };
+
+        let ce = ClassBuilder::new("RustIterator")
+            .implements(
+                ClassEntry::iterator().expect("`Iterator` interface has not been registered."),
+            )
+            .method(
+                method("current", Self::current, DataType::Mixed, true),
+                MethodFlags::Public,
+            )
+            .method(
+                method("key", Self::key, DataType::Mixed, true),
+                MethodFlags::Public,
+            )
+            .method(
+                method("next", Self::next, DataType::Void, false),
+                MethodFlags::Public,
+            )
+            .method(
+                method("rewind", Self::rewind, DataType::Void, false),
+                MethodFlags::Public,
+            )
+            .method(
+                method("valid", Self::valid, DataType::Bool, false),
+                MethodFlags::Public,
+            )
+            .object_override::<Self>()
+            .build()
+            .expect("Failed to build `RustIterator` PHP class.");
+        ITERATOR_META.set_ce(ce);
+    }
+
+    /// Pulls the first item from the Rust iterator, if this has not been done already.
+    fn start(&mut self) {
+        if !self.started {
+            self.started = true;
+            self.fetch();
+        }
+    }
+
+    /// Replaces the current item with the next item from the Rust iterator. If the item could
+    /// not be converted into zvals, an exception is thrown and the iterator is finished.
+    fn fetch(&mut self) {
+        self.current = match self.iter.as_mut().and_then(|iter| iter.next()) {
+            Some(Ok(item)) => Some(item),
+            Some(Err(e)) => {
+                self.iter = None;
+                let _ =
+                    PhpException::default(format!("Failed to convert iterator item to PHP: {}", e))
+                        .throw();
+                None
+            }
+            None => None,
+        };
+    }
+
+    /// Calls a function with the iterator bound to `$this` in the execution data.
+    fn with_this(ex: &mut ExecutionData, f: impl FnOnce(&mut Self)) {
+        match unsafe { ex.get_object::<Self>() } {
+            Some(mut this) => f(&mut this),
+            None => {
+                let _ = PhpException::default("Failed to retrieve `RustIterator` object.".into())
+                    .throw();
+            }
+        }
+    }
+
+    /// External function used by the Zend interpreter to get the current value.
+    extern "C" fn current(ex: &mut ExecutionData, ret: &mut Zval) {
+        Self::with_this(ex, |this| {
+            this.start();
+
+            match &this.current {
+                Some((_, val)) => *ret = val.shallow_clone(),
+                None => ret.set_null(),
+            }
+        });
+    }
+
+    /// External function used by the Zend interpreter to get the current key.
+    extern "C" fn key(ex: &mut ExecutionData, ret: &mut Zval) {
+        Self::with_this(ex, |this| {
+            this.start();
+
+            match &this.current {
+                Some((key, _)) => *ret = key.shallow_clone(),
+                None => ret.set_null(),
+            }
+        });
+    }
+
+    /// External function used by the Zend interpreter to advance the iterator.
+    extern "C" fn next(ex: &mut ExecutionData, _: &mut Zval) {
+        Self::with_this(ex, |this| {
+            this.start();
+            this.advanced = true;
+            this.fetch();
+        });
+    }
+
+    /// External function used by the Zend interpreter to rewind the iterator. As Rust iterators
+    /// cannot be restarted, this is only valid before the iterator has been advanced.
+    extern "C" fn rewind(ex: &mut ExecutionData, _: &mut Zval) {
+        Self::with_this(ex, |this| {
+            if this.advanced {
+                let _ = PhpException::default(
+                    "Cannot rewind a `RustIterator` that has already been advanced.".into(),
+                )
+                .throw();
+            } else {
+                this.start();
+            }
+        });
+    }
+
+    /// External function used by the Zend interpreter to check if the iterator has a current item.
+    extern "C" fn valid(ex: &mut ExecutionData, ret: &mut Zval) {
+        Self::with_this(ex, |this| {
+            this.start();
+            ret.set_bool(this.current.is_some());
+        });
+    }
+}
+
+/// An iterator instantiated from PHP has no Rust iterator to consume, and is always empty.
+impl Default for PhpIterator {
+    fn default() -> Self {
+        Self {
+            iter: None,
+            current: None,
+            started: false,
+            advanced: false,
+        }
+    }
+}
+
+impl RegisteredClass for PhpIterator {
+    const CLASS_NAME: &'static str = "RustIterator";
+
+    fn get_metadata() -> &'static ClassMetadata<Self> {
+        &ITERATOR_META
+    }
+}
@@ -7,8 +7,18 @@ pub mod callable;
 #[cfg(any(docs, feature = "closure"))]
 #[cfg_attr(docs, doc(cfg(feature = "closure")))]
 pub mod closure;
+#[cfg(feature = "chrono")]
+#[cfg_attr(docs, doc(cfg(feature = "chrono")))]
+pub mod datetime;
+pub mod iterable;
+#[cfg(any(docs, feature = "iterator"))]
+#[cfg_attr(docs, doc(cfg(feature = "iterator")))]
+pub mod iterator;
 pub mod long;
 pub mod object;
+#[cfg(feature = "serde")]
+#[cfg_attr(docs, doc(cfg(feature = "serde")))]
+pub mod serde;
 pub mod string;
 pub mod zval;
 
@@ -171,3 +181,34 @@ impl ZendType {
         }) | Self::arg_info_flags(pass_by_ref, is_variadic)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ZendType;
+    use crate::{bindings::_ZEND_TYPE_NULLABLE_BIT, php::enums::DataType};
+
+    #[test]
+    fn test_nullable_type() {
+        let nullable = |ty: ZendType| ty.type_mask & _ZEND_TYPE_NULLABLE_BIT != 0;
+
+        // Argument types.
+        assert!(nullable(
+            ZendType::empty_from_type(DataType::String, false, false, true).unwrap()
+        ));
+        assert!(!nullable(
+            ZendType::empty_from_type(DataType::String, false, false, false).unwrap()
+        ));
+        assert!(nullable(
+            ZendType::empty_from_type(DataType::Object(Some("Foo")), false, false, true).unwrap()
+        ));
+
+        // Return types are built the same way as argument types, including when returning by
+        // reference.
+        assert!(nullable(
+            ZendType::empty_from_type(DataType::Long, true, false, true).unwrap()
+        ));
+        assert!(!nullable(
+            ZendType::empty_from_type(DataType::Long, true, false, false).unwrap()
+        ));
+    }
+}
@@ -3,10 +3,12 @@
 
 use std::{
     convert::TryInto,
+    ffi::c_void,
     fmt::Debug,
     marker::PhantomData,
     mem::{self, MaybeUninit},
     ops::{Deref, DerefMut},
+    os::raw::c_int,
     ptr::{self, NonNull},
     sync::atomic::{AtomicBool, AtomicPtr, Ordering},
 };
@@ -14,11 +16,15 @@ use std::{
 use crate::{
     bindings::{
         ext_php_rs_zend_object_alloc, ext_php_rs_zend_object_release, object_properties_init,
-        std_object_handlers, zend_object, zend_object_handlers, zend_object_std_init,
-        zend_objects_clone_members, ZEND_ISEMPTY, ZEND_PROPERTY_EXISTS, ZEND_PROPERTY_ISSET,
+        std_object_handlers, zend_call_known_function, zend_function, zend_hash_str_find_ptr_lc,
+        zend_object, zend_object_handlers, zend_object_std_init, zend_objects_clone_members,
+        zend_string, zval, Bucket, BP_VAR_IS, IS_INDIRECT, ZEND_ISEMPTY, ZEND_PROPERTY_EXISTS,
+        ZEND_PROPERTY_ISSET,
     },
     errors::{Error, Result},
-    php::{class::ClassEntry, enums::DataType, types::string::ZendString},
+    php::{
+        class::ClassEntry, enums::DataType, globals::ExecutorGlobals, types::string::ZendString,
+    },
 };
 
 use super::{
@@ -54,21 +60,157 @@ impl ZendObject {
         name.try_into()
     }
 
-    /// Checks if the given object is an instance of a registered class with Rust
-    /// type `T`.
+    /// Returns the name of the class of the object, read directly from its class entry.
+    ///
+    /// Anonymous classes are named by PHP with a `class@anonymous` prefix, followed by a NUL
+    /// byte and the location the class was declared. Only the prefix is returned, matching the
+    /// name shown by `get_class()` in PHP.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::types::object::ZendObject;
+    ///
+    /// fn describe(obj: &ZendObject) -> String {
+    ///     format!("object({})", obj.class_name())
+    /// }
+    /// ```
+    pub fn class_name(&self) -> &str {
+        let name = match unsafe { self.ce.as_ref().and_then(|ce| ce.name.as_ref()) } {
+            Some(name) => name.as_bytes(),
+            None => return "",
+        };
+        let name = match name.iter().position(|b| *b == 0) {
+            Some(nul) => &name[..nul],
+            None => name,
+        };
+
+        match std::str::from_utf8(name) {
+            Ok(name) => name,
+            // SAFETY: The bytes up to `valid_up_to` were checked to be valid UTF-8.
+            Err(e) => unsafe { std::str::from_utf8_unchecked(&name[..e.valid_up_to()]) },
+        }
+    }
+
+    /// Checks if the object is an instance of the given class, or of a class which extends or
+    /// implements it. Equivalent to the `instanceof` operator in PHP.
+    ///
+    /// # Parameters
+    ///
+    /// * `ce` - The class or interface to check against.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{class::ClassEntry, types::object::ZendObject};
+    ///
+    /// fn is_array_object(obj: &ZendObject) -> bool {
+    ///     ClassEntry::try_find("ArrayObject")
+    ///         .map(|ce| obj.instance_of(ce))
+    ///         .unwrap_or(false)
+    /// }
+    /// ```
+    pub fn instance_of(&self, ce: &ClassEntry) -> bool {
+        match unsafe { self.ce.as_ref() } {
+            Some(own) => own.instance_of(ce),
+            None => false,
+        }
+    }
+
+    /// Checks if the given object is an instance of a registered class with Rust type `T`, or of
+    /// a PHP class extending it. Returns false if `T` has not been registered with PHP.
+    ///
+    /// As well as comparing the class entry of the object against the class entry stored for
+    /// `T`, the handlers of the object are compared against the handlers registered for `T`,
+    /// which are only set on objects allocated by `T`. This rejects objects of the class which do
+    /// not contain a `T`, such as objects created by a class which was built without overriding
+    /// its object.
     pub fn is_instance<T: RegisteredClass>(&self) -> bool {
-        (self.ce as *const ClassEntry).eq(&(T::get_metadata().ce() as *const _))
+        let meta = T::get_metadata();
+
+        meta.has_ce() && self.instance_of(meta.ce()) && ptr::eq(self.handlers, meta.handlers())
+    }
+
+    /// Attempts to retrieve a reference to the Rust struct backing the object. Returns [`None`]
+    /// if the object is not an instance of the registered class `T` (see
+    /// [`is_instance()`](#method.is_instance)), so objects of any class can be safely checked.
+    ///
+    /// Also returns [`None`] if the class was registered with
+    /// [`ClassBuilder::object_override_uninit()`] and the object has not been initialized by its
+    /// constructor yet.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use ext_php_rs::{php::types::zval::Zval, prelude::*};
+    ///
+    /// #[php_class]
+    /// #[derive(Default)]
+    /// pub struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// #[php_function]
+    /// pub fn point_x(obj: &Zval) -> Option<i64> {
+    ///     // `None` (returned as `null`) for values which are not a `Point`.
+    ///     obj.object()?.get::<Point>().map(|point| point.x)
+    /// }
+    /// ```
+    ///
+    /// [`ClassBuilder::object_override_uninit()`]: crate::php::class::ClassBuilder::object_override_uninit
+    pub fn get<T: RegisteredClass>(&self) -> Option<&T> {
+        ZendClassObject::<T>::from_zend_obj_ptr(self)?.obj()
+    }
+
+    /// Attempts to retrieve a mutable reference to the Rust struct backing the object. Returns
+    /// [`None`] if the object is not an instance of the registered class `T` (see
+    /// [`is_instance()`](#method.is_instance)), or if the object has not been initialized yet
+    /// (see [`get()`](#method.get)).
+    pub fn get_mut<T: RegisteredClass>(&mut self) -> Option<&mut T> {
+        ZendClassObject::<T>::from_zend_obj_ptr(self)?.obj_mut()
+    }
+
+    /// Initializes the Rust struct backing the object with the given value, returning a mutable
+    /// reference to it. Returns [`None`] if the object is not an instance of the registered
+    /// class `T`, in which case `val` is dropped.
+    ///
+    /// Used in the constructor of classes registered with
+    /// [`ClassBuilder::object_override_uninit()`], whose objects are created without a value of
+    /// `T`. If the object has already been initialized, i.e. when the constructor is called a
+    /// second time, the previous value is dropped and replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The value to store in the object.
+    ///
+    /// [`ClassBuilder::object_override_uninit()`]: crate::php::class::ClassBuilder::object_override_uninit
+    pub fn initialize<T: RegisteredClass>(&mut self, val: T) -> Option<&mut T> {
+        Some(ZendClassObject::<T>::from_zend_obj_ptr(self)?.initialize(val))
     }
 
-    /// Attempts to read a property from the Object. Returns a result returning an
-    /// immutable reference to the [`Zval`] if the property exists and can be read,
-    /// and an [`Error`] otherwise.
+    /// Attempts to read a declared or dynamic property from the object, converting it into `T`.
+    /// Use `&Zval` as `T` to retrieve the property without converting it.
+    ///
+    /// Returns an [`Error::InvalidProperty`] if the property does not exist, without emitting a
+    /// PHP warning. Properties which are computed by the `__get` magic method cannot be read, as
+    /// their value does not live past the call.
     ///
     /// # Parameters
     ///
     /// * `name` - The name of the property.
-    /// * `query` - The type of query to use when attempting to get a property.
-    pub fn get_property(&self, name: &str) -> Result<&Zval> {
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::types::{object::ZendObject, zval::Zval};
+    ///
+    /// fn read(obj: &ZendObject) {
+    ///     let foo: i64 = obj.get_property("foo").unwrap();
+    ///     let bar: &Zval = obj.get_property("bar").unwrap();
+    /// }
+    /// ```
+    pub fn get_property<'a, T: FromZval<'a>>(&'a self, name: &str) -> Result<T> {
         if !self.has_property(name, PropertyQuery::Exists)? {
             return Err(Error::InvalidProperty);
         }
@@ -76,17 +218,25 @@ impl ZendObject {
         let name = ZendString::new(name, false)?;
         let mut rv = Zval::new();
 
-        unsafe {
+        let zv = unsafe {
             self.handlers()?.read_property.ok_or(Error::InvalidScope)?(
                 self.mut_ptr(),
                 name.borrow_ptr(),
-                1,
+                BP_VAR_IS as _,
                 std::ptr::null_mut(),
                 &mut rv,
             )
             .as_ref()
         }
-        .ok_or(Error::InvalidScope)
+        .ok_or(Error::InvalidScope)?;
+
+        // The value was written into the temporary return value rather than read from the
+        // property table, so it cannot be borrowed for `'a`.
+        if ptr::eq(zv, &rv) {
+            return Err(Error::InvalidProperty);
+        }
+
+        T::from_zval(zv).ok_or(Error::ZvalConversion(zv.get_type()?))
     }
 
     /// Attempts to set a property on the object, returning an immutable reference to
@@ -133,6 +283,69 @@ impl ZendObject {
         } > 0)
     }
 
+    /// Calls a method on the object with a list of arguments, returning the return value of the
+    /// method. The method is looked up in the class of the object, case-insensitively, like PHP
+    /// does. Methods handled by the `__call` magic method cannot be called, and visibility is not
+    /// checked, so private and protected methods can also be called.
+    ///
+    /// Returns an [`Error::InvalidMethod`] if the class does not have a method with the given
+    /// name. If an exception is thrown inside the method, [`Error::Exception`] is returned and
+    /// the exception is left in place, to be rethrown once control returns to PHP. An exception
+    /// which was already pending before the call is not treated as an error of this call.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the method to call.
+    /// * `args` - The arguments to pass to the method.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::types::{
+    ///     object::ZendObject,
+    ///     zval::{IntoZval, Zval},
+    /// };
+    ///
+    /// // Equivalent to `$logger->log($msg)`.
+    /// fn log(logger: &mut ZendObject, msg: &str) -> ext_php_rs::errors::Result<Zval> {
+    ///     logger.call_method("log", &[msg.into_zval(false)?])
+    /// }
+    /// ```
+    pub fn call_method(&mut self, name: &str, args: &[Zval]) -> Result<Zval> {
+        let ce = unsafe { self.ce.as_ref() }.ok_or(Error::InvalidPointer)?;
+        let func = unsafe {
+            zend_hash_str_find_ptr_lc(&ce.function_table, name.as_ptr() as _, name.len() as _)
+        } as *mut zend_function;
+
+        if func.is_null() {
+            return Err(Error::InvalidMethod(self.get_class_name()?, name.into()));
+        }
+
+        let mut retval = Zval::new();
+        let pending = ExecutorGlobals::get().exception;
+
+        // The arguments are copied into the call frame by PHP, so they are not modified.
+        unsafe {
+            zend_call_known_function(
+                func,
+                self,
+                self.ce,
+                &mut retval,
+                args.len() as _,
+                args.as_ptr() as *mut _,
+                ptr::null_mut(),
+            )
+        };
+
+        // A new exception replaces the pending exception, which becomes its previous exception.
+        let exception = ExecutorGlobals::get().exception;
+        if !exception.is_null() && exception != pending {
+            Err(Error::Exception)
+        } else {
+            Ok(retval)
+        }
+    }
+
     /// Attempts to retrieve the properties of the object. Returned inside a Zend Hashtable.
     pub fn get_properties(&self) -> Result<ZendHashTable> {
         unsafe {
@@ -143,6 +356,60 @@ impl ZendObject {
         }
     }
 
+    /// Returns an iterator over the names and values of the public properties of the object, in
+    /// the order they are stored. Declared properties come first, followed by properties which
+    /// were added dynamically.
+    ///
+    /// Only properties which are visible from outside of the class are returned, as with
+    /// `get_object_vars()` called from the global scope. Protected and private properties,
+    /// typed properties which have not been initialized and properties which have been unset are
+    /// skipped. Properties whose names are not valid UTF-8 are skipped as well. The properties of
+    /// objects whose class provides its own property table, such as `ArrayObject`, are retrieved
+    /// through the `get_properties` handler of the object.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{
+    ///     errors::Result,
+    ///     php::types::{array::ZendHashTable, zval::Zval},
+    ///     php_function,
+    /// };
+    ///
+    /// /// Converts an object into an associative array of its public properties.
+    /// #[php_function]
+    /// pub fn object_to_array(obj: &Zval) -> Result<ZendHashTable> {
+    ///     let mut arr = ZendHashTable::new();
+    ///     if let Some(obj) = obj.object() {
+    ///         for (name, value) in obj.properties() {
+    ///             arr.insert(name, value.shallow_clone())?;
+    ///         }
+    ///     }
+    ///     Ok(arr)
+    /// }
+    /// ```
+    pub fn properties(&self) -> Properties<'_> {
+        // SAFETY: The property table returned by the handler is owned by the object, and is valid
+        // for as long as the object is borrowed.
+        let ht = unsafe { self.handlers() }
+            .ok()
+            .and_then(|handlers| handlers.get_properties)
+            .and_then(|get_properties| unsafe { get_properties(self.mut_ptr()).as_ref() });
+
+        let (pos, end) = match ht {
+            Some(ht) => (ht.arData as *const Bucket, unsafe {
+                ht.arData.add(ht.nNumUsed as usize) as *const Bucket
+            }),
+            None => (ptr::null(), ptr::null()),
+        };
+
+        Properties {
+            pos,
+            end,
+            phantom: PhantomData,
+        }
+    }
+
     /// Attempts to retrieve a reference to the object handlers.
     #[inline]
     unsafe fn handlers(&self) -> Result<&ZendObjectHandlers> {
@@ -172,8 +439,8 @@ impl Debug for ZendObject {
         );
 
         if let Ok(props) = self.get_properties() {
-            for (id, key, val) in props.into_iter() {
-                dbg.field(key.unwrap_or_else(|| id.to_string()).as_str(), val);
+            for (key, val) in props.into_iter() {
+                dbg.field(key.to_string().as_str(), val);
             }
         }
 
@@ -181,6 +448,131 @@ impl Debug for ZendObject {
     }
 }
 
+/// Iterator over the names and values of the public properties of an object, returned by
+/// [`ZendObject::properties()`].
+pub struct Properties<'a> {
+    pos: *const Bucket,
+    end: *const Bucket,
+    phantom: PhantomData<&'a ZendObject>,
+}
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = (&'a str, &'a Zval);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos != self.end {
+            // SAFETY: `pos` is within the used buckets of the property table.
+            let bucket = unsafe { &*self.pos };
+            self.pos = unsafe { self.pos.add(1) };
+
+            // Declared properties are stored in the object itself, and are referenced from the
+            // property table through indirect zvals.
+            let mut val = &bucket.val;
+            if unsafe { val.u1.v.type_ } as u32 == IS_INDIRECT {
+                val = unsafe { &*val.value.zv };
+            }
+
+            // Deleted buckets, unset properties and uninitialized typed properties are undefined.
+            if val.is_undef() {
+                continue;
+            }
+
+            // The names of protected and private properties are prefixed with a null byte,
+            // followed by the scope of the property.
+            match unsafe { bucket.key.as_ref() }.map(|key| key.as_str()) {
+                Some(Ok(name)) if !name.starts_with('\0') => return Some((name, val)),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+}
+
+/// A weak handle to an object, identifying it by its handle in the object store of the executor
+/// (the value returned by `spl_object_id()`). Unlike a reference, the handle does not add to the
+/// reference count of the object, so it does not keep the object alive, and it can be checked
+/// for whether the object is still alive before the object is accessed.
+///
+/// # Lifetime
+///
+/// The object store is emptied at the end of every request, and handles are reused by objects
+/// created in later requests. An object handle is therefore only meaningful within the request
+/// it was created in, and **must not** be kept across requests, for example in a `static`.
+///
+/// Within a request, the handle of a destroyed object may be reused by a new object. The handle
+/// also keeps the address of the object, and is only upgraded if the object stored under the
+/// handle is the same allocation. If a new object is allocated at the address of a destroyed
+/// object and takes over its handle, the handle is upgraded to the new object. The object
+/// returned is always a valid, live object, but it may not be the object the handle was created
+/// from, so the class of the object should be checked where it matters, i.e. with
+/// [`ZendObject::get`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use ext_php_rs::php::types::object::{ObjectHandle, ZendObject};
+///
+/// fn remember(obj: &ZendObject) -> ObjectHandle {
+///     ObjectHandle::new(obj)
+/// }
+///
+/// fn describe(handle: &ObjectHandle) -> String {
+///     match handle.upgrade() {
+///         Some(obj) => format!("{} #{}", obj.class_name(), handle.id()),
+///         None => format!("destroyed object #{}", handle.id()),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectHandle {
+    handle: u32,
+    ptr: NonNull<ZendObject>,
+}
+
+impl ObjectHandle {
+    /// Creates a handle to an object.
+    ///
+    /// # Parameters
+    ///
+    /// * `obj` - The object to create a handle to.
+    pub fn new(obj: &ZendObject) -> Self {
+        Self {
+            handle: obj.handle,
+            ptr: NonNull::from(obj),
+        }
+    }
+
+    /// Returns the handle of the object, which is the same as the value returned by
+    /// `spl_object_id()` in PHP.
+    pub fn id(&self) -> u32 {
+        self.handle
+    }
+
+    /// Returns whether the object is still alive, i.e. it has not been freed.
+    pub fn is_alive(&self) -> bool {
+        self.upgrade().is_some()
+    }
+
+    /// Returns a reference to the object if it is still alive, or [`None`] if it has been freed.
+    pub fn upgrade(&self) -> Option<&ZendObject> {
+        let store = &ExecutorGlobals::get().objects_store;
+        if store.object_buckets.is_null() || self.handle >= store.top {
+            return None;
+        }
+
+        // SAFETY: The handle is within the used part of the store. Buckets of freed objects are
+        // marked by setting the lowest bit, which is never set on a valid object pointer.
+        let bucket = unsafe { *store.object_buckets.add(self.handle as usize) };
+        if bucket as usize & 1 != 0 || bucket != self.ptr.as_ptr() {
+            return None;
+        }
+
+        // SAFETY: The bucket contains a pointer to a live object.
+        unsafe { bucket.as_ref() }
+    }
+}
+
 pub struct ClassRef<'a, T: RegisteredClass> {
     ptr: &'a mut ZendClassObject<T>,
 }
@@ -207,10 +599,10 @@ pub struct ClassObject<'a, T: RegisteredClass> {
     free: bool,
 }
 
-impl<T: RegisteredClass> Default for ClassObject<'_, T> {
+impl<T: RegisteredClass + Default> Default for ClassObject<'_, T> {
     fn default() -> Self {
         let ptr = unsafe {
-            ZendClassObject::new_ptr(None)
+            ZendClassObject::new_ptr(Some(T::default()))
                 .as_mut()
                 .expect("Failed to allocate memory for class object.")
         };
@@ -235,6 +627,67 @@ impl<T: RegisteredClass> ClassObject<'_, T> {
         Self { ptr, free: true }
     }
 
+    /// Returns a reference to the object which can be returned to PHP. The reference count of
+    /// the object is incremented when the reference is converted into a zval, so the class object
+    /// keeps its own reference and the object is only freed once the class object and every PHP
+    /// variable holding the object have been dropped.
+    ///
+    /// This allows the same instance to be returned on each call, i.e. for a singleton, by
+    /// storing the class object in the [module globals](crate::php::module::ModuleGlobals).
+    /// Objects are allocated for the current request, and are freed by PHP at the end of the
+    /// request regardless of their reference count, so the class object must be dropped in the
+    /// request shutdown function. As the module globals are per-thread when PHP is built with
+    /// thread safety, each thread then has its own instance.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use std::cell::RefCell;
+    ///
+    /// use ext_php_rs::{
+    ///     errors::Result,
+    ///     php::{
+    ///         module::ModuleGlobals,
+    ///         types::{
+    ///             object::ClassObject,
+    ///             zval::{IntoZval, Zval},
+    ///         },
+    ///     },
+    ///     prelude::*,
+    /// };
+    ///
+    /// #[derive(Default)]
+    /// pub struct Globals {
+    ///     logger: RefCell<Option<ClassObject<'static, Logger>>>,
+    /// }
+    ///
+    /// static GLOBALS: ModuleGlobals<Globals> = ModuleGlobals::new();
+    ///
+    /// #[php_class]
+    /// #[derive(Default)]
+    /// pub struct Logger;
+    ///
+    /// #[php_impl]
+    /// impl Logger {
+    ///     pub fn instance() -> Result<Zval> {
+    ///         let mut logger = unsafe { GLOBALS.get() }.logger.borrow_mut();
+    ///         logger
+    ///             .get_or_insert_with(|| ClassObject::new(Logger))
+    ///             .share()
+    ///             .into_zval(false)
+    ///     }
+    /// }
+    ///
+    /// pub extern "C" fn request_shutdown(_type: i32, _module_number: i32) -> i32 {
+    ///     // Releases the reference held by the globals before PHP frees the object.
+    ///     unsafe { GLOBALS.get() }.logger.borrow_mut().take();
+    ///     0
+    /// }
+    /// ```
+    pub fn share(&mut self) -> ClassRef<'_, T> {
+        ClassRef { ptr: self.ptr }
+    }
+
     /// Consumes the class object, releasing the internal pointer without releasing the internal object.
     ///
     /// Used to transfer ownership of the object to PHP.
@@ -248,11 +701,6 @@ impl<T: RegisteredClass> ClassObject<'_, T> {
         self.ptr
     }
 
-    /// Returns a mutable reference to the underlying class object.
-    pub(crate) fn internal_mut(&mut self) -> &mut ZendClassObject<T> {
-        self.ptr
-    }
-
     /// Creates a new instance of [`ClassObject`] around a pre-existing class object.
     ///
     /// # Parameters
@@ -292,29 +740,30 @@ impl<T: RegisteredClass> Deref for ClassObject<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        // SAFETY: Class object constructor guarantees memory is allocated.
+        // SAFETY: Class objects are only created around initialized objects.
         unsafe { &*self.ptr.obj.as_ptr() }
     }
 }
 
 impl<T: RegisteredClass> DerefMut for ClassObject<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        // SAFETY: Class object constructor guarantees memory is allocated.
+        // SAFETY: Class objects are only created around initialized objects.
         unsafe { &mut *self.ptr.obj.as_mut_ptr() }
     }
 }
 
 impl<T: RegisteredClass + Clone> Clone for ClassObject<'_, T> {
     fn clone(&self) -> Self {
-        // SAFETY: Class object constructor guarantees memory is allocated.
-        let mut new = Self::new(unsafe { &*self.internal().obj.as_ptr() }.clone());
-        unsafe {
-            zend_objects_clone_members(
-                &mut new.internal_mut().std,
-                &self.internal().std as *const _ as *mut _,
-            )
-        }
-        new
+        // SAFETY: Class objects are only created around initialized objects.
+        let val = unsafe { &*self.internal().obj.as_ptr() }.clone();
+        let ptr = unsafe {
+            self.internal()
+                .clone_ptr(Some(val))
+                .as_mut()
+                .expect("Failed to allocate memory for class object.")
+        };
+
+        Self { ptr, free: true }
     }
 }
 
@@ -346,9 +795,7 @@ impl<'a, T: RegisteredClass> FromZval<'a> for &'a T {
     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
-        let cobj = ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?;
-
-        Some(unsafe { &*cobj.obj.as_mut_ptr() })
+        ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?.obj()
     }
 }
 
@@ -356,15 +803,21 @@ impl<'a, T: RegisteredClass> FromZval<'a> for &'a mut T {
     const TYPE: DataType = DataType::Object(Some(T::CLASS_NAME));
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
-        let cobj = ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?;
-
-        Some(unsafe { &mut *cobj.obj.as_mut_ptr() })
+        ZendClassObject::<T>::from_zend_obj_ptr(zval.object()?)?.obj_mut()
     }
 }
 
 /// Implemented on Rust types which are exported to PHP. Allows users to get and set PHP properties on
 /// the object.
-pub trait RegisteredClass: Default + Sized
+///
+/// Objects of registered classes cannot be cloned by default, as the standard clone handler of
+/// PHP only copies the properties of the object, which would leave the clone without the Rust
+/// value. Cloning an object with `clone $obj` therefore throws an `Error`, unless the clone handler
+/// is installed with [`ZendObjectHandlers::set_clone_handler`], usually by passing the `clone`
+/// argument to the [`macro@php_class`] macro.
+///
+/// [`macro@php_class`]: crate::php_class
+pub trait RegisteredClass: Sized
 where
     Self: 'static,
 {
@@ -396,8 +849,7 @@ where
     /// by a [`zend_object`], which is true when the object was instantiated by PHP.
     unsafe fn get_property<'a, T: FromZval<'a>>(&'a self, name: &str) -> Option<T> {
         let obj = ZendClassObject::<Self>::from_obj_ptr(self)?;
-        let zv = obj.std.get_property(name).ok()?;
-        T::from_zval(zv)
+        obj.std.get_property(name).ok()
     }
 
     /// Attempts to set the value of a property on the class object.
@@ -421,6 +873,113 @@ where
         obj.std.set_property(name, value).ok()?;
         Some(())
     }
+
+    /// Modifies the object handlers of the class. Called once, after the standard object
+    /// handlers have been copied into the class metadata, and before the first object of the
+    /// class is created.
+    ///
+    /// Does nothing by default. The [`macro@php_class`] macro overrides this to call
+    /// [`ZendObjectHandlers::set_property_handler`] when the `property_handler` argument is given,
+    /// and [`ZendObjectHandlers::set_clone_handler`] when the `clone` argument is given.
+    ///
+    /// # Parameters
+    ///
+    /// * `handlers` - The object handlers of the class.
+    ///
+    /// [`macro@php_class`]: crate::php_class
+    fn init_handlers(_handlers: &mut ZendObjectHandlers) {}
+}
+
+/// Intercepts property access on objects of a registered class, allowing properties to be
+/// computed or stored by Rust. Installed with [`ZendObjectHandlers::set_property_handler`], or by
+/// passing the `property_handler` argument to the [`macro@php_class`] macro.
+///
+/// Each method is called before PHP looks up the declared and dynamic properties of the object.
+/// Returning [`None`] (or `false` from [`write_property`]) falls back to the standard behaviour,
+/// so only the properties the class is interested in need to be handled.
+///
+/// Only plain reads, writes and `isset()`/`empty()` checks are intercepted. Properties which are
+/// modified in place, i.e. `$obj->list[] = 1`, are accessed through the standard handlers.
+///
+/// # Examples
+///
+/// ```ignore
+/// use ext_php_rs::{
+///     php::types::{
+///         object::{PropertyHandler, PropertyQuery},
+///         zval::{IntoZval, Zval},
+///     },
+///     prelude::*,
+/// };
+///
+/// #[php_class(property_handler)]
+/// #[derive(Default)]
+/// pub struct Lazy {
+///     computed: Option<i64>,
+/// }
+///
+/// impl PropertyHandler for Lazy {
+///     fn read_property(&mut self, name: &str) -> Option<Zval> {
+///         match name {
+///             // Computed on the first read of `$obj->computed`.
+///             "computed" => self.computed.get_or_insert_with(|| 6 * 7).into_zval(false).ok(),
+///             _ => None,
+///         }
+///     }
+///
+///     fn has_property(&mut self, name: &str, _: PropertyQuery) -> Option<bool> {
+///         match name {
+///             "computed" => Some(true),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`macro@php_class`]: crate::php_class
+/// [`write_property`]: #method.write_property
+pub trait PropertyHandler: RegisteredClass {
+    /// Called when a property is read, i.e. `$obj->name`.
+    ///
+    /// Returns the value of the property, or [`None`] to read the property from the object as
+    /// usual. The returned zval is moved into a temporary owned by PHP, which releases it once
+    /// it is no longer used. The zval must therefore own a reference to its value, which is the
+    /// case for any zval created through [`IntoZval`]. Zvals copied from elsewhere, such as
+    /// another property, must have their reference count incremented.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property.
+    fn read_property(&mut self, _name: &str) -> Option<Zval> {
+        None
+    }
+
+    /// Called when a property is written to, i.e. `$obj->name = $value`.
+    ///
+    /// Returns `true` if the write was handled, or `false` to write the property to the object as
+    /// usual. The value is borrowed from PHP, so its reference count must be incremented if it is
+    /// stored.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property.
+    /// * `value` - The value being written.
+    fn write_property(&mut self, _name: &str, _value: &Zval) -> bool {
+        false
+    }
+
+    /// Called when the existence of a property is checked, i.e. `isset($obj->name)`.
+    ///
+    /// Returns whether the property satisfies the query, or [`None`] to check the object as
+    /// usual.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the property.
+    /// * `query` - The check being performed. See [`PropertyQuery`].
+    fn has_property(&mut self, _name: &str, _query: PropertyQuery) -> Option<bool> {
+        None
+    }
 }
 
 /// Representation of a Zend class object in memory. Usually seen through its managed variant
@@ -428,28 +987,60 @@ where
 #[repr(C)]
 pub(crate) struct ZendClassObject<T> {
     obj: MaybeUninit<T>,
+    /// Whether `obj` holds a value. Only false for objects created by
+    /// [`ClassBuilder::object_override_uninit()`] which have not been initialized yet.
+    ///
+    /// [`ClassBuilder::object_override_uninit()`]: crate::php::class::ClassBuilder::object_override_uninit
+    init: bool,
     std: zend_object,
 }
 
 impl<T: RegisteredClass> ZendClassObject<T> {
     /// Allocates memory for a new PHP object. The memory is allocated using the Zend memory manager,
     /// and therefore it is returned as a pointer.
+    ///
+    /// If `val` is [`None`], the Rust object is left uninitialized until
+    /// [`initialize()`](#method.initialize) is called.
     pub(crate) fn new_ptr(val: Option<T>) -> *mut Self {
-        let size = mem::size_of::<Self>();
         let meta = T::get_metadata();
-        let ce = meta.ce() as *const _ as *mut _;
+        unsafe { Self::alloc_ptr(val, meta.ce() as *const _ as *mut _, meta.handlers()) }
+    }
+
+    /// Allocates memory for a clone of the object, and copies the properties of the object into
+    /// the clone. The clone is created with the class entry of the object rather than the class
+    /// entry of `T`, as the object may be an instance of a PHP class which extends `T`.
+    ///
+    /// If `val` is [`None`], the Rust object of the clone is left uninitialized.
+    pub(crate) fn clone_ptr(&self, val: Option<T>) -> *mut Self {
+        let std = &self.std as *const _ as *mut zend_object;
         unsafe {
-            let obj = (ext_php_rs_zend_object_alloc(size as _, ce) as *mut Self)
-                .as_mut()
-                .expect("Failed to allocate memory for new class object.");
+            let new = Self::alloc_ptr(val, self.std.ce, self.std.handlers);
+            zend_objects_clone_members(&mut (*new).std, std);
+            new
+        }
+    }
+
+    /// Allocates memory for a new PHP object of the class `ce`, which must be the class entry of
+    /// `T` or a class which extends it.
+    unsafe fn alloc_ptr(
+        val: Option<T>,
+        ce: *mut ClassEntry,
+        handlers: *const ZendObjectHandlers,
+    ) -> *mut Self {
+        let size = mem::size_of::<Self>();
+        let obj = (ext_php_rs_zend_object_alloc(size as _, ce) as *mut Self)
+            .as_mut()
+            .expect("Failed to allocate memory for new class object.");
 
-            zend_object_std_init(&mut obj.std, ce);
-            object_properties_init(&mut obj.std, ce);
+        zend_object_std_init(&mut obj.std, ce);
+        object_properties_init(&mut obj.std, ce);
 
-            obj.obj = MaybeUninit::new(val.unwrap_or_default());
-            obj.std.handlers = meta.handlers();
-            obj
+        obj.init = val.is_some();
+        if let Some(val) = val {
+            obj.obj = MaybeUninit::new(val);
         }
+        obj.std.handlers = handlers;
+        obj
     }
 
     /// Returns a reference to the [`ZendClassObject`] of a given object `T`. Returns [`None`]
@@ -480,16 +1071,16 @@ impl<T: RegisteredClass> ZendClassObject<T> {
     ///
     /// * `obj` - The zend object to get the [`ZendClassObject`] for.
     pub(crate) fn from_zend_obj_ptr(obj: &zend_object) -> Option<&mut Self> {
+        // The object is checked before the pointer is offset, as objects which are not instances
+        // of `T` are not contained in a class object.
+        if !obj.is_instance::<T>() {
+            return None;
+        }
+
         let ptr = obj as *const zend_object as *const i8;
-        let ptr = unsafe {
+        unsafe {
             let ptr = ptr.offset(0 - Self::std_offset() as isize) as *const Self;
-            (ptr as *mut Self).as_mut()?
-        };
-
-        if ptr.std.is_instance::<T>() {
-            Some(ptr)
-        } else {
-            None
+            (ptr as *mut Self).as_mut()
         }
     }
 
@@ -500,6 +1091,38 @@ impl<T: RegisteredClass> ZendClassObject<T> {
 }
 
 impl<T> ZendClassObject<T> {
+    /// Returns a reference to the Rust object, or [`None`] if it has not been initialized.
+    pub(crate) fn obj(&self) -> Option<&T> {
+        // SAFETY: `init` is only set once `obj` holds a value.
+        self.init.then(|| unsafe { &*self.obj.as_ptr() })
+    }
+
+    /// Returns a mutable reference to the Rust object, or [`None`] if it has not been
+    /// initialized.
+    pub(crate) fn obj_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `init` is only set once `obj` holds a value.
+        self.init
+            .then(move || unsafe { &mut *self.obj.as_mut_ptr() })
+    }
+
+    /// Stores the Rust object, dropping the previous value if the object was already
+    /// initialized.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The value to store.
+    pub(crate) fn initialize(&mut self, val: T) -> &mut T {
+        if self.init {
+            // SAFETY: `init` is only set once `obj` holds a value.
+            unsafe { ptr::drop_in_place(self.obj.as_mut_ptr()) };
+        }
+
+        self.obj = MaybeUninit::new(val);
+        self.init = true;
+        // SAFETY: `obj` was initialized above.
+        unsafe { &mut *self.obj.as_mut_ptr() }
+    }
+
     /// Returns the offset of the `std` property in the class object.
     pub(crate) fn std_offset() -> usize {
         unsafe {
@@ -510,12 +1133,35 @@ impl<T> ZendClassObject<T> {
             (std as usize) - (base as usize)
         }
     }
+
+    /// Drops the Rust object stored in front of a Zend object, leaving the Zend object itself
+    /// intact. Called by the `free_obj` handler before the Zend object is freed, so that any
+    /// resources held by the Rust object are released when PHP destroys the object. Does nothing
+    /// if the Rust object was never initialized.
+    ///
+    /// # Parameters
+    ///
+    /// * `object` - The Zend object to drop the Rust object of.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `object` is the `std` field of a [`ZendClassObject<T>`], and
+    /// that the Rust object has not already been dropped.
+    pub(crate) unsafe fn drop_obj(object: *mut zend_object) {
+        let ptr = (object as *mut u8).sub(Self::std_offset()) as *mut Self;
+        if (*ptr).init {
+            (*ptr).init = false;
+            ptr::drop_in_place((*ptr).obj.as_mut_ptr());
+        }
+    }
 }
 
 impl<T> Drop for ZendClassObject<T> {
     fn drop(&mut self) {
-        // SAFETY: All constructors guarantee that `obj` is valid.
-        unsafe { std::ptr::drop_in_place(self.obj.as_mut_ptr()) };
+        if self.init {
+            // SAFETY: `init` is only set once `obj` holds a value.
+            unsafe { std::ptr::drop_in_place(self.obj.as_mut_ptr()) };
+        }
     }
 }
 
@@ -539,14 +1185,6 @@ impl<T> ClassMetadata<T> {
         }
     }
 
-    /// Returns an immutable reference to the object handlers contained inside the class metadata.
-    pub fn handlers(&self) -> &ZendObjectHandlers {
-        self.check_handlers();
-
-        // SAFETY: `check_handlers` guarantees that `handlers` has been initialized.
-        unsafe { &*self.handlers.as_ptr() }
-    }
-
     /// Checks if the class entry has been stored, returning a boolean.
     pub fn has_ce(&self) -> bool {
         !self.ce.load(Ordering::SeqCst).is_null()
@@ -581,12 +1219,27 @@ impl<T> ClassMetadata<T> {
 
         self.ce.store(ce, Ordering::SeqCst);
     }
+}
+
+impl<T: RegisteredClass> ClassMetadata<T> {
+    /// Returns an immutable reference to the object handlers contained inside the class metadata.
+    pub fn handlers(&self) -> &ZendObjectHandlers {
+        self.check_handlers();
+
+        // SAFETY: `check_handlers` guarantees that `handlers` has been initialized.
+        unsafe { &*self.handlers.as_ptr() }
+    }
 
     /// Checks if the handlers have been initialized, and initializes them if they are not.
     fn check_handlers(&self) {
         if !self.handlers_init.load(Ordering::Acquire) {
-            // SAFETY: `MaybeUninit` has the same size as the handlers.
-            unsafe { ZendObjectHandlers::init::<T>(self.handlers.as_ptr() as *mut _) };
+            // SAFETY: `MaybeUninit` has the same size as the handlers, which are initialized
+            // before being passed to the class.
+            unsafe {
+                let handlers = self.handlers.as_ptr() as *mut ZendObjectHandlers;
+                ZendObjectHandlers::init::<T>(handlers);
+                T::init_handlers(&mut *handlers);
+            }
             self.handlers_init.store(true, Ordering::Release);
         }
     }
@@ -596,6 +1249,10 @@ impl ZendObjectHandlers {
     /// Initializes a given set of object handlers by copying the standard object handlers into
     /// the memory location, as well as setting up the `T` type destructor.
     ///
+    /// Cloning is disabled, as the standard clone handler creates an object without the Rust
+    /// value of `T`. Use [`set_clone_handler`](#method.set_clone_handler) to allow objects to be
+    /// cloned.
+    ///
     /// # Parameters
     ///
     //// * `ptr` - Pointer to memory location to copy the standard handlers to.
@@ -605,11 +1262,7 @@ impl ZendObjectHandlers {
     /// Caller must guarantee that the `ptr` given is a valid memory location.
     pub unsafe fn init<T>(ptr: *mut ZendObjectHandlers) {
         pub unsafe extern "C" fn free_obj<T>(object: *mut zend_object) {
-            let offset = ZendClassObject::<T>::std_offset();
-
-            // Cast to *mut u8 to work in byte offsets
-            let ptr = (object as *mut u8).offset(0 - offset as isize) as *mut T;
-            ptr::drop_in_place(ptr);
+            ZendClassObject::<T>::drop_obj(object);
 
             match std_object_handlers.free_obj {
                 Some(free) => free(object),
@@ -621,5 +1274,186 @@ impl ZendObjectHandlers {
         let offset = ZendClassObject::<T>::std_offset();
         (*ptr).offset = offset as _;
         (*ptr).free_obj = Some(free_obj::<T>);
+        (*ptr).clone_obj = None;
+    }
+
+    /// Replaces the `clone_obj` handler with a handler which clones the Rust value of `T` into
+    /// the new object, allowing objects of the class to be cloned with `clone $obj`. The
+    /// properties of the object are then copied and `__clone()` is called, as for regular PHP
+    /// objects, so the clone is independent of the original object.
+    ///
+    /// Without this handler, cloning an object of a registered class throws an `Error`. Objects
+    /// which have not been initialized by their constructor yet (see
+    /// [`ClassBuilder::object_override_uninit()`]) are cloned into another uninitialized object.
+    ///
+    /// Usually called from [`RegisteredClass::init_handlers`], or by passing the `clone` argument
+    /// to the [`macro@php_class`] macro.
+    ///
+    /// [`macro@php_class`]: crate::php_class
+    /// [`ClassBuilder::object_override_uninit()`]: crate::php::class::ClassBuilder::object_override_uninit
+    pub fn set_clone_handler<T: RegisteredClass + Clone>(&mut self) {
+        unsafe extern "C" fn clone_obj<T: RegisteredClass + Clone>(
+            object: *mut zend_object,
+        ) -> *mut zend_object {
+            // SAFETY: The handler is only installed on objects created by `ZendClassObject<T>`.
+            let ptr = (object as *mut u8).sub(ZendClassObject::<T>::std_offset())
+                as *mut ZendClassObject<T>;
+
+            let val = (*ptr).obj().cloned();
+            &mut (*(*ptr).clone_ptr(val)).std
+        }
+
+        self.clone_obj = Some(clone_obj::<T>);
+    }
+
+    /// Replaces the `read_property`, `write_property` and `has_property` handlers with handlers
+    /// which call the methods of the [`PropertyHandler`] implementation of `T`, falling back to
+    /// the standard handlers when a property is not handled by `T`.
+    ///
+    /// Usually called from [`RegisteredClass::init_handlers`].
+    pub fn set_property_handler<T: PropertyHandler>(&mut self) {
+        unsafe extern "C" fn read_property<T: PropertyHandler>(
+            object: *mut zend_object,
+            member: *mut zend_string,
+            type_: c_int,
+            cache_slot: *mut *mut c_void,
+            rv: *mut zval,
+        ) -> *mut zval {
+            let value = (*object)
+                .get_mut::<T>()
+                .zip((*member).as_str().ok())
+                .and_then(|(obj, name)| obj.read_property(name));
+
+            match value {
+                // PHP takes ownership of the value through `rv`, so it must not be dropped here.
+                Some(value) => {
+                    ptr::write(rv, value);
+                    rv
+                }
+                None => match std_object_handlers.read_property {
+                    Some(read) => read(object, member, type_, cache_slot, rv),
+                    None => unreachable!(),
+                },
+            }
+        }
+
+        unsafe extern "C" fn write_property<T: PropertyHandler>(
+            object: *mut zend_object,
+            member: *mut zend_string,
+            value: *mut zval,
+            cache_slot: *mut *mut c_void,
+        ) -> *mut zval {
+            let handled = (*object)
+                .get_mut::<T>()
+                .zip((*member).as_str().ok())
+                .map(|(obj, name)| obj.write_property(name, &*value))
+                .unwrap_or(false);
+
+            if handled {
+                return value;
+            }
+
+            match std_object_handlers.write_property {
+                Some(write) => write(object, member, value, cache_slot),
+                None => unreachable!(),
+            }
+        }
+
+        unsafe extern "C" fn has_property<T: PropertyHandler>(
+            object: *mut zend_object,
+            member: *mut zend_string,
+            has_set_exists: c_int,
+            cache_slot: *mut *mut c_void,
+        ) -> c_int {
+            let query = match has_set_exists as u32 {
+                ZEND_PROPERTY_ISSET => PropertyQuery::Isset,
+                ZEND_ISEMPTY => PropertyQuery::NotEmpty,
+                _ => PropertyQuery::Exists,
+            };
+            let result = (*object)
+                .get_mut::<T>()
+                .zip((*member).as_str().ok())
+                .and_then(|(obj, name)| obj.has_property(name, query));
+
+            match result {
+                Some(result) => result as c_int,
+                None => match std_object_handlers.has_property {
+                    Some(has) => has(object, member, has_set_exists, cache_slot),
+                    None => unreachable!(),
+                },
+            }
+        }
+
+        self.read_property = Some(read_property::<T>);
+        self.write_property = Some(write_property::<T>);
+        self.has_property = Some(has_property::<T>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        mem::MaybeUninit,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use super::{ClassMetadata, RegisteredClass, ZendClassObject, ZendObject};
+
+    static DROPPED: AtomicBool = AtomicBool::new(false);
+
+    struct Resource;
+
+    impl Drop for Resource {
+        fn drop(&mut self) {
+            DROPPED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_drop_obj() {
+        let mut obj = MaybeUninit::<ZendClassObject<Resource>>::zeroed();
+
+        unsafe {
+            let obj = &mut *obj.as_mut_ptr();
+            obj.initialize(Resource);
+
+            assert!(!DROPPED.load(Ordering::SeqCst));
+            ZendClassObject::<Resource>::drop_obj(&mut obj.std);
+        }
+
+        assert!(DROPPED.load(Ordering::SeqCst));
+    }
+
+    #[derive(Default)]
+    struct Unregistered;
+
+    static UNREGISTERED_META: ClassMetadata<Unregistered> = ClassMetadata::new();
+
+    impl RegisteredClass for Unregistered {
+        const CLASS_NAME: &'static str = "Unregistered";
+
+        fn get_metadata() -> &'static ClassMetadata<Self> {
+            &UNREGISTERED_META
+        }
+    }
+
+    #[test]
+    fn test_get_unregistered() {
+        let obj = unsafe { MaybeUninit::<ZendObject>::zeroed().assume_init() };
+
+        assert!(!obj.is_instance::<Unregistered>());
+        assert!(obj.get::<Unregistered>().is_none());
+    }
+
+    #[test]
+    fn test_uninit_obj() {
+        let mut obj = MaybeUninit::<ZendClassObject<Unregistered>>::zeroed();
+        let obj = unsafe { &mut *obj.as_mut_ptr() };
+
+        assert!(obj.obj().is_none());
+        assert!(obj.obj_mut().is_none());
+
+        obj.initialize(Unregistered);
+        assert!(obj.obj().is_some());
     }
 }
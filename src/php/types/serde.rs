@@ -0,0 +1,985 @@
+//! Conversions between PHP values and Rust types implementing [`Serialize`] and [`Deserialize`].
+//!
+//! Values are mapped in the same way as self-describing formats such as JSON:
+//!
+//! * Sequences, tuples and tuple structs are lists, i.e. `[1, 2, 3]`.
+//! * Maps and structs are associative arrays. Map keys must be integers or strings.
+//! * Unit variants are the name of the variant, while other enum variants are arrays with the name
+//! of the variant as the only key, i.e. `['Rgb' => [255, 0, 0]]`.
+//! * `None` and `()` are `null`.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use ext_php_rs::{php::types::serde::Serde, prelude::*};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! pub struct Config {
+//!     name: String,
+//!     retries: u32,
+//!     hosts: Vec<String>,
+//! }
+//!
+//! // Returns `['name' => 'default', 'retries' => 3, 'hosts' => ['localhost']]`.
+//! #[php_function]
+//! pub fn get_config() -> Serde<Config> {
+//!     Serde(Config {
+//!         name: "default".into(),
+//!         retries: 3,
+//!         hosts: vec!["localhost".into()],
+//!     })
+//! }
+//! ```
+
+use std::{convert::TryInto, fmt::Display, vec};
+
+use ::serde::{
+    de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    forward_to_deserialize_any,
+    ser::{
+        self, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+        SerializeTupleStruct, SerializeTupleVariant,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    errors::{Error, Result},
+    php::enums::DataType,
+};
+
+use super::{
+    array::{numeric_key, ArrayKey, ZendHashTable},
+    long::ZendLong,
+    zval::{FromZval, IntoZval, Zval},
+};
+
+/// Serializes a value into a zval.
+///
+/// # Parameters
+///
+/// * `value` - The value to serialize.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::collections::BTreeMap;
+///
+/// use ext_php_rs::php::types::serde::{from_zval, to_zval};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// enum Shape {
+///     Point,
+///     Circle(f64),
+///     Rect { width: u32, height: u32 },
+/// }
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Drawing {
+///     name: String,
+///     shapes: Vec<Shape>,
+///     layers: BTreeMap<u32, String>,
+/// }
+///
+/// let drawing = Drawing {
+///     name: "logo".into(),
+///     shapes: vec![
+///         Shape::Point,
+///         Shape::Circle(1.5),
+///         Shape::Rect { width: 2, height: 3 },
+///     ],
+///     layers: vec![(1, "background".into()), (5, "text".into())]
+///         .into_iter()
+///         .collect(),
+/// };
+///
+/// // [
+/// //     'name' => 'logo',
+/// //     'shapes' => ['Point', ['Circle' => 1.5], ['Rect' => ['width' => 2, 'height' => 3]]],
+/// //     'layers' => [1 => 'background', 5 => 'text'],
+/// // ]
+/// let zv = to_zval(&drawing).unwrap();
+/// assert_eq!(from_zval::<Drawing>(&zv).unwrap(), drawing);
+/// ```
+pub fn to_zval<T: Serialize + ?Sized>(value: &T) -> Result<Zval> {
+    value.serialize(ZvalSerializer)
+}
+
+/// Deserializes a value from a zval. Strings can be borrowed from the zval.
+///
+/// # Parameters
+///
+/// * `zval` - The zval to deserialize the value from.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::collections::HashMap;
+///
+/// use ext_php_rs::php::types::serde::{from_zval, to_zval};
+///
+/// // Lists and maps round trip, and string keys which are integers become integer keys.
+/// let list = vec![vec![1, 2], vec![], vec![3]];
+/// let zv = to_zval(&list).unwrap();
+/// assert_eq!(from_zval::<Vec<Vec<i32>>>(&zv).unwrap(), list);
+///
+/// let map: HashMap<String, bool> = vec![("5".into(), true), ("05".into(), false)]
+///     .into_iter()
+///     .collect();
+/// let zv = to_zval(&map).unwrap();
+/// assert_eq!(zv.array().unwrap().get_index(5).and_then(|zv| zv.bool()), Some(true));
+/// assert_eq!(from_zval::<HashMap<String, bool>>(&zv).unwrap(), map);
+///
+/// // Strings are borrowed from the zval.
+/// let zv = to_zval("hello").unwrap();
+/// let hello: &str = from_zval(&zv).unwrap();
+/// assert_eq!(hello, "hello");
+/// ```
+pub fn from_zval<'de, T: Deserialize<'de>>(zval: &'de Zval) -> Result<T> {
+    T::deserialize(ZvalDeserializer::new(zval))
+}
+
+/// Wrapper which allows any type implementing [`Serialize`] to be returned to PHP, and any type
+/// implementing [`Deserialize`] to be used as a parameter. See the [module documentation](self)
+/// for how values are mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Serde<T>(pub T);
+
+impl<T: Serialize> IntoZval for Serde<T> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        *zv = to_zval(&self.0)?;
+        Ok(())
+    }
+}
+
+impl<'a, T: Deserialize<'a>> FromZval<'a> for Serde<T> {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        from_zval(zval).ok().map(Serde)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serialization(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Serialization(msg.to_string())
+    }
+}
+
+/// Serializes values into zvals.
+struct ZvalSerializer;
+
+impl Serializer for ZvalSerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer;
+    type SerializeTuple = ArraySerializer;
+    type SerializeTupleStruct = ArraySerializer;
+    type SerializeTupleVariant = ArraySerializer;
+    type SerializeMap = ArraySerializer;
+    type SerializeStruct = ArraySerializer;
+    type SerializeStructVariant = ArraySerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Zval> {
+        v.to_string().into_zval(false)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Zval> {
+        v.into_zval(false)
+    }
+
+    fn serialize_none(self) -> Result<Zval> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Zval> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Zval> {
+        Ok(Zval::new())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Zval> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Zval> {
+        variant.into_zval(false)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Zval> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Zval> {
+        let mut ht = ZendHashTable::new();
+        ht.insert(variant, value.serialize(self)?)?;
+        ht.into_zval(false)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(len, None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(Some(len), None))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(Some(len), None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(Some(len), Some(variant)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(len, None))
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(Some(len), None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ArraySerializer> {
+        Ok(ArraySerializer::new(Some(len), Some(variant)))
+    }
+}
+
+/// Serializes compound values into a PHP array.
+struct ArraySerializer {
+    ht: ZendHashTable<'static>,
+    key: Option<Zval>,
+    variant: Option<&'static str>,
+}
+
+impl ArraySerializer {
+    /// Creates a new array serializer.
+    ///
+    /// # Parameters
+    ///
+    /// * `len` - The number of elements in the array, if known.
+    /// * `variant` - The name of the enum variant being serialized, if any. The array is wrapped
+    /// in another array, keyed by the name of the variant.
+    fn new(len: Option<usize>, variant: Option<&'static str>) -> Self {
+        let ht = match len {
+            Some(len) => ZendHashTable::with_capacity(len.try_into().unwrap_or(u32::MAX)),
+            None => ZendHashTable::new(),
+        };
+
+        Self {
+            ht,
+            key: None,
+            variant,
+        }
+    }
+
+    /// Appends a value to the end of the array.
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.ht.push(value.serialize(ZvalSerializer)?)
+    }
+
+    /// Inserts a value into the array with a string key.
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        self.ht.insert(key, value.serialize(ZvalSerializer)?)?;
+        Ok(())
+    }
+
+    /// Converts the array into a zval.
+    fn finish(self) -> Result<Zval> {
+        let array = self.ht.into_zval(false)?;
+
+        match self.variant {
+            Some(variant) => {
+                let mut ht = ZendHashTable::new();
+                ht.insert(variant, array)?;
+                ht.into_zval(false)
+            }
+            None => Ok(array),
+        }
+    }
+}
+
+impl SerializeSeq for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl SerializeTuple for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleStruct for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl SerializeTupleVariant for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl SerializeMap for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(key.serialize(ZvalSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::Serialization("Map value serialized before its key.".into()))?;
+
+        match array_key(&key)? {
+            ArrayKey::Long(index) => {
+                self.ht
+                    .insert_at_index(index as u64, value.serialize(ZvalSerializer)?)?;
+                Ok(())
+            }
+            ArrayKey::String(key) => self.insert(&key, value),
+        }
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+/// Converts a serialized map key into the key of an array element. PHP stores string keys which
+/// are canonical integers, i.e. `"5"` but not `"05"`, as integer keys.
+///
+/// # Parameters
+///
+/// * `key` - The serialized key.
+fn array_key(key: &Zval) -> Result<ArrayKey> {
+    if let Some(index) = key.long() {
+        return Ok(ArrayKey::Long(index));
+    }
+
+    match key.str() {
+        Some(key) => Ok(match numeric_key(key) {
+            Some(index) => ArrayKey::Long(index),
+            None => ArrayKey::String(key.into()),
+        }),
+        None => Err(Error::Serialization(
+            "Map keys must be integers or strings.".into(),
+        )),
+    }
+}
+
+impl SerializeStruct for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+impl SerializeStructVariant for ArraySerializer {
+    type Ok = Zval;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Zval> {
+        self.finish()
+    }
+}
+
+/// Deserializes values from a zval.
+struct ZvalDeserializer<'de>(&'de Zval);
+
+impl<'de> ZvalDeserializer<'de> {
+    /// Creates a new deserializer, following the zval if it is a reference.
+    fn new(zval: &'de Zval) -> Self {
+        match zval.reference() {
+            Some(zv) => Self(zv),
+            None => Self(zval),
+        }
+    }
+
+    /// Returns the entries of the zval in order, if it is an array.
+    fn entries(&self) -> Option<Vec<(ArrayKey, &'de Zval)>> {
+        Some(self.0.array()?.into_iter().collect())
+    }
+}
+
+impl<'de> Deserializer<'de> for ZvalDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let zv = self.0;
+
+        if zv.is_null() {
+            visitor.visit_unit()
+        } else if let Some(val) = zv.bool() {
+            visitor.visit_bool(val)
+        } else if let Some(val) = zv.long() {
+            visitor.visit_i64(val)
+        } else if let Some(val) = zv.double() {
+            visitor.visit_f64(val)
+        } else if let Some(val) = zv.zend_str() {
+            match val.as_str() {
+                Ok(val) => visitor.visit_borrowed_str(val),
+                Err(_) => visitor.visit_borrowed_bytes(val.as_bytes()),
+            }
+        } else if let Some(entries) = self.entries() {
+            let is_list = entries
+                .iter()
+                .enumerate()
+                .all(|(i, (key, _))| *key == ArrayKey::Long(i as ZendLong));
+
+            if is_list {
+                self.deserialize_seq(visitor)
+            } else {
+                self.deserialize_map(visitor)
+            }
+        } else {
+            Err(Error::ZvalConversion(zv.get_type()?))
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.0.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.entries() {
+            Some(entries) => visitor.visit_seq(SeqDeserializer {
+                iter: entries
+                    .into_iter()
+                    .map(|(_, val)| val)
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            }),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.entries() {
+            Some(entries) => visitor.visit_map(MapDeserializer {
+                iter: entries.into_iter(),
+                value: None,
+            }),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if let Some(variant) = self.0.str() {
+            return visitor.visit_enum(EnumDeserializer {
+                variant: variant.into(),
+                value: None,
+            });
+        }
+
+        match self.entries() {
+            Some(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(EnumDeserializer {
+                    variant: variant.to_string(),
+                    value: Some(value),
+                })
+            }
+            _ => Err(Error::Serialization(
+                "Enums must be a string or an array with a single key.".into(),
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit unit_struct
+        identifier ignored_any
+    }
+}
+
+/// Deserializes the values of an array as a sequence.
+struct SeqDeserializer<'de> {
+    iter: vec::IntoIter<&'de Zval>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        self.iter
+            .next()
+            .map(|val| seed.deserialize(ZvalDeserializer::new(val)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes the entries of an array as a map.
+struct MapDeserializer<'de> {
+    iter: vec::IntoIter<(ArrayKey, &'de Zval)>,
+    value: Option<&'de Zval>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyDeserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::Serialization("Map value deserialized before its key.".into()))?;
+
+        seed.deserialize(ZvalDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Deserializes the key of an array entry.
+struct KeyDeserializer(ArrayKey);
+
+impl<'de> Deserializer<'de> for KeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            ArrayKey::Long(key) => visitor.visit_i64(key),
+            ArrayKey::String(key) => visitor.visit_string(key),
+        }
+    }
+
+    // PHP converts numeric string keys into integer keys, which are converted back where a
+    // string is expected.
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.0.to_string())
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.0.to_string())
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.0.to_string())
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char bytes byte_buf option unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum ignored_any
+    }
+}
+
+/// Deserializes an enum, either from the name of a unit variant or from an array containing the
+/// value of the variant keyed by its name.
+struct EnumDeserializer<'de> {
+    variant: String,
+    value: Option<&'de Zval>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = Error;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(KeyDeserializer(ArrayKey::String(self.variant)))?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+/// Deserializes the value of an enum variant.
+struct VariantDeserializer<'de>(Option<&'de Zval>);
+
+impl<'de> VariantDeserializer<'de> {
+    /// Returns a deserializer for the value of the variant.
+    fn value(self) -> Result<ZvalDeserializer<'de>> {
+        self.0
+            .map(ZvalDeserializer::new)
+            .ok_or_else(|| Error::Serialization("Enum variant is missing its value.".into()))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.0 {
+            Some(value) => <()>::deserialize(ZvalDeserializer::new(value)),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.value()?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        self.value()?.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.value()?.deserialize_map(visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::MaybeUninit;
+
+    use serde::Deserialize;
+
+    use super::{array_key, from_zval, to_zval, KeyDeserializer};
+    use crate::{
+        bindings::zend_string,
+        errors::Error,
+        php::{
+            flags::ZvalTypeFlags,
+            types::{array::ArrayKey, zval::Zval},
+        },
+    };
+
+    /// Storage for a string which is not allocated by PHP.
+    #[repr(C)]
+    struct Str {
+        str_: zend_string,
+        rest: [u8; 15],
+    }
+
+    /// Creates an interned string zval, which is not released when dropped, in `storage`.
+    fn string(storage: &mut Str, bytes: &[u8]) -> Zval {
+        assert!(bytes.len() <= 16);
+        storage.str_.len = bytes.len() as _;
+        unsafe {
+            let base = storage as *mut Str as *mut u8;
+            let val = base.add(storage.str_.val.as_ptr() as usize - base as usize);
+            val.copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+        }
+
+        let mut zv = Zval::new();
+        zv.value.str_ = &mut storage.str_;
+        zv.u1.type_info = ZvalTypeFlags::InternedStringEx.bits();
+        zv
+    }
+
+    fn storage() -> Str {
+        unsafe { MaybeUninit::zeroed().assume_init() }
+    }
+
+    fn long(val: i64) -> Zval {
+        let mut zv = Zval::new();
+        zv.set_long(val);
+        zv
+    }
+
+    fn key<'de, T: Deserialize<'de>>(key: ArrayKey) -> Result<T, Error> {
+        T::deserialize(KeyDeserializer(key))
+    }
+
+    #[test]
+    fn test_array_key() {
+        assert_eq!(array_key(&long(-3)), Ok(ArrayKey::Long(-3)));
+
+        let mut buf = storage();
+        assert_eq!(array_key(&string(&mut buf, b"5")), Ok(ArrayKey::Long(5)));
+        assert_eq!(array_key(&string(&mut buf, b"-5")), Ok(ArrayKey::Long(-5)));
+        for non_canonical in &["05", "-0", "+5", "5.0", "", "key"] {
+            assert_eq!(
+                array_key(&string(&mut buf, non_canonical.as_bytes())),
+                Ok(ArrayKey::String(non_canonical.to_string()))
+            );
+        }
+
+        let mut double = Zval::new();
+        double.set_double(5.0);
+        assert!(array_key(&double).is_err());
+        assert!(array_key(&Zval::new()).is_err());
+        assert!(array_key(&string(&mut buf, b"\xff")).is_err());
+    }
+
+    #[test]
+    fn test_key_deserializer() {
+        assert_eq!(key::<i64>(ArrayKey::Long(5)), Ok(5));
+        assert_eq!(key::<u8>(ArrayKey::Long(5)), Ok(5));
+        assert!(key::<u8>(ArrayKey::Long(300)).is_err());
+        assert!(key::<i64>(ArrayKey::String("key".into())).is_err());
+
+        // Integer keys are converted back into strings where a string is expected.
+        assert_eq!(key::<String>(ArrayKey::Long(-5)), Ok("-5".to_string()));
+        assert_eq!(
+            key::<String>(ArrayKey::String("05".into())),
+            Ok("05".into())
+        );
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        #[serde(field_identifier)]
+        enum Field {
+            #[serde(rename = "0")]
+            Zero,
+            Name,
+        }
+
+        assert_eq!(key::<Field>(ArrayKey::Long(0)), Ok(Field::Zero));
+        assert_eq!(
+            key::<Field>(ArrayKey::String("Name".into())),
+            Ok(Field::Name)
+        );
+    }
+
+    #[test]
+    fn test_serialize_scalars() {
+        assert_eq!(to_zval(&5u8).unwrap().long(), Some(5));
+        assert_eq!(to_zval(&-5i64).unwrap().long(), Some(-5));
+        assert_eq!(to_zval(&1.5f64).unwrap().double(), Some(1.5));
+        assert_eq!(to_zval(&true).unwrap().bool(), Some(true));
+        assert!(to_zval(&()).unwrap().is_null());
+        assert!(to_zval(&None::<i64>).unwrap().is_null());
+        assert_eq!(to_zval(&Some(3)).unwrap().long(), Some(3));
+    }
+
+    #[test]
+    fn test_deserialize_scalars() {
+        assert_eq!(from_zval::<i64>(&long(5)), Ok(5));
+        assert_eq!(from_zval::<f64>(&long(5)), Ok(5.0));
+        assert_eq!(from_zval::<Option<i64>>(&long(5)), Ok(Some(5)));
+        assert_eq!(from_zval::<Option<i64>>(&Zval::new()), Ok(None));
+        assert_eq!(from_zval::<()>(&Zval::new()), Ok(()));
+        assert!(from_zval::<u8>(&long(300)).is_err());
+        assert!(from_zval::<u64>(&long(-1)).is_err());
+        assert!(from_zval::<bool>(&long(1)).is_err());
+
+        let mut zv = Zval::new();
+        zv.set_double(-0.5);
+        assert_eq!(from_zval::<f64>(&zv), Ok(-0.5));
+        assert!(from_zval::<i64>(&zv).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_strings() {
+        let mut buf = storage();
+        let zv = string(&mut buf, b"hello");
+        assert_eq!(from_zval::<&str>(&zv), Ok("hello"));
+        assert_eq!(from_zval::<String>(&zv), Ok("hello".into()));
+        assert!(from_zval::<i64>(&zv).is_err());
+
+        // Strings which are not valid UTF-8 are deserialized as bytes.
+        let mut buf = storage();
+        let zv = string(&mut buf, b"a\xffb");
+        assert_eq!(from_zval::<&[u8]>(&zv), Ok(&b"a\xffb"[..]));
+        assert!(from_zval::<&str>(&zv).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_unit_variant() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        enum Mode {
+            Read,
+            Write(u8),
+        }
+
+        let mut buf = storage();
+        assert_eq!(
+            from_zval::<Mode>(&string(&mut buf, b"Read")),
+            Ok(Mode::Read)
+        );
+        assert!(from_zval::<Mode>(&string(&mut buf, b"Write")).is_err());
+        assert!(from_zval::<Mode>(&string(&mut buf, b"Delete")).is_err());
+        assert_eq!(
+            from_zval::<Mode>(&long(0)),
+            Err(Error::Serialization(
+                "Enums must be a string or an array with a single key.".into()
+            ))
+        );
+    }
+}
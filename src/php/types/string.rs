@@ -1,7 +1,7 @@
 //! Represents a string in the PHP world. Similar to a C string, but is reference counted and
 //! contains the length of the string, meaning the string can contain the NUL character.
 
-use std::{convert::TryFrom, ffi::CString, fmt::Debug};
+use std::{convert::TryFrom, ffi::CString, fmt::Debug, slice, str::Utf8Error};
 
 use crate::{
     bindings::{
@@ -11,6 +11,57 @@ use crate::{
     errors::{Error, Result},
 };
 
+/// A borrowed Zend string. Used to read the contents of a Zend string without copying it, usually
+/// retrieved through [`Zval::zend_str()`].
+///
+/// [`Zval::zend_str()`]: crate::php::types::zval::Zval::zend_str
+pub type ZendStr = zend_string;
+
+impl ZendStr {
+    /// Retrieves an interned string, creating it if it does not exist. Interned strings are
+    /// persistent and shared, so an existing interned string with the same contents is reused
+    /// rather than allocating a new one.
+    ///
+    /// # Parameters
+    ///
+    /// * `str_` - The contents of the interned string.
+    #[allow(clippy::unwrap_used)]
+    pub fn from_interned(str_: &str) -> Result<&'static Self> {
+        // Unwrap is OK here - `zend_string_init_interned` will be a valid function ptr by the time
+        // our extension is loaded.
+        unsafe {
+            zend_string_init_interned.unwrap()(CString::new(str_)?.as_ptr(), str_.len() as _, true)
+                .as_ref()
+        }
+        .ok_or(Error::InvalidPointer)
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns whether the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the contents of the string as a byte slice. Unlike [`as_str()`](#method.as_str),
+    /// this does not require the string to be valid UTF-8.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: Zend strings have a length that we know we can read. The value of the string is
+        // represented in C as a `char` array of length 1, but the data can be read up to `len`
+        // bytes.
+        unsafe { slice::from_raw_parts(self.val.as_ptr() as *const u8, self.len()) }
+    }
+
+    /// Returns the contents of the string as a string slice, without copying. Returns an error if
+    /// the string is not valid UTF-8.
+    pub fn as_str(&self) -> std::result::Result<&str, Utf8Error> {
+        std::str::from_utf8(self.as_bytes())
+    }
+}
+
 /// A wrapper around the [`zend_string`] used within the Zend API. Essentially a C string, except
 /// that the structure contains the length of the string as well as the string being refcounted.
 pub struct ZendString {
@@ -84,15 +135,7 @@ impl ZendString {
 
     /// Extracts a string slice containing the contents of the [`ZendString`].
     pub fn as_str(&self) -> Option<&str> {
-        // SAFETY: Zend strings have a length that we know we can read.
-        // By reading this many bytes we should not run into any issues.
-        // The value of the string is represented in C as a `char` array of
-        // length 1, but the data can be read up to `ptr.len` bytes.
-        unsafe {
-            let ptr = self.ptr.as_ref()?;
-            let slice = std::slice::from_raw_parts(ptr.val.as_ptr() as *const u8, ptr.len as _);
-            std::str::from_utf8(slice).ok()
-        }
+        unsafe { self.ptr.as_ref() }?.as_str().ok()
     }
 
     /// Borrows the underlying internal pointer of the Zend string.
@@ -1,30 +1,42 @@
 //! The base value in PHP. A Zval can contain any PHP type, and the type that it contains is
 //! determined by a property inside the struct. The content of the Zval is stored in a union.
 
-use core::slice;
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    fmt::Debug,
+    fmt::{self, Debug, Display},
+    ops::Deref,
+    path::PathBuf,
     ptr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
     bindings::{
-        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, ext_php_rs_zend_string_release,
-        zend_is_callable, zend_resource, zend_value, zval,
+        _zval_struct__bindgen_ty_1, _zval_struct__bindgen_ty_2, ext_php_rs_zend_string_init,
+        ext_php_rs_zend_string_release, object_init_ex, zend_array_dup, zend_is_callable,
+        zend_is_true, zend_resource, zend_value, zval, GC_IMMUTABLE,
     },
     errors::{Error, Result},
     php::pack::Pack,
 };
 
 use crate::php::{
+    class::ClassEntry,
     enums::DataType,
-    flags::ZvalTypeFlags,
-    types::{long::ZendLong, string::ZendString},
+    exceptions::PhpException,
+    flags::{ZendResult, ZvalTypeFlags},
+    types::{
+        long::ZendLong,
+        string::{ZendStr, ZendString},
+    },
 };
 
-use super::{array::ZendHashTable, callable::Callable, object::ZendObject};
+use super::{
+    array::{ArrayKey, ZendHashTable},
+    callable::Callable,
+    object::ZendObject,
+};
 
 /// Zend value. Represents most data types that are in the Zend engine.
 pub type Zval = zval;
@@ -50,14 +62,18 @@ impl Zval {
 
     /// Returns the value of the zval if it is a long.
     pub fn long(&self) -> Option<ZendLong> {
-        if self.is_long() {
-            Some(unsafe { self.value.lval })
+        let zv = self.dereference();
+        if zv.is_long() {
+            Some(unsafe { zv.value.lval })
         } else {
             None
         }
     }
 
     /// Returns the value of the zval if it is a bool.
+    ///
+    /// No conversion is performed, so [`None`] is returned for any other type. See
+    /// [`is_truthy()`](#method.is_truthy) to convert any value into a bool.
     pub fn bool(&self) -> Option<bool> {
         if self.is_true() {
             Some(true)
@@ -70,8 +86,9 @@ impl Zval {
 
     /// Returns the value of the zval if it is a double.
     pub fn double(&self) -> Option<f64> {
-        if self.is_double() {
-            Some(unsafe { self.value.dval })
+        let zv = self.dereference();
+        if zv.is_double() {
+            Some(unsafe { zv.value.dval })
         } else {
             self.long().map(|x| x as f64)
         }
@@ -83,6 +100,12 @@ impl Zval {
     /// double or a long, and if so it will convert the value to a [`String`] and return it.
     /// Don't rely on this logic, as there is potential for this to change to match the output
     /// of the [`str()`](#method.str) function.
+    ///
+    /// [`None`] is returned if the string is not valid UTF-8. This is the conversion used by
+    /// [`FromZval`] for [`String`], and therefore by `String` parameters of functions, which
+    /// reject invalid UTF-8 as if the argument had the wrong type. See
+    /// [`string_strict()`](#method.string_strict) and [`string_lossy()`](#method.string_lossy)
+    /// to handle invalid UTF-8 explicitly, or [`zend_str()`](#method.zend_str) to read the bytes.
     pub fn string(&self) -> Option<String> {
         self.str()
             .map(|s| s.to_string())
@@ -94,20 +117,70 @@ impl Zval {
     /// Note that this functions output will not be the same as [`string()`](#method.string), as
     /// this function does not attempt to convert other types into a [`String`], as it could not
     /// pass back a [`&str`] in those cases.
+    ///
+    /// The string is borrowed from the zval without copying it. PHP strings are byte strings, so
+    /// the contents are checked to be valid UTF-8 on every call, which takes time proportional to
+    /// the length of the string. [`None`] is returned if the string is not valid UTF-8. Use
+    /// [`zend_str()`](#method.zend_str) to read the bytes without validating them.
     pub fn str(&self) -> Option<&str> {
-        if self.is_string() {
-            // SAFETY: Zend strings have a length that we know we can read.
-            // By reading this many bytes we will not run into any issues.
-            //
-            // We can safely cast our *const c_char into a *const u8 as both
-            // only occupy one byte.
-            unsafe {
-                std::str::from_utf8(slice::from_raw_parts(
-                    (*self.value.str_).val.as_ptr() as *const u8,
-                    (*self.value.str_).len as usize,
-                ))
-                .ok()
-            }
+        self.zend_str()?.as_str().ok()
+    }
+
+    /// Returns a copy of the value of the zval if it is a string, returning an error if the string
+    /// is not valid UTF-8. Unlike [`string()`](#method.string), other types are not converted
+    /// into a string.
+    ///
+    /// Returns [`Error::ZvalTypeMismatch`] if the zval is not a string, or [`Error::InvalidUtf8`]
+    /// with the offset of the first invalid byte if the string is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::{php::{exceptions::PhpResult, types::zval::Zval}, php_function};
+    ///
+    /// /// Returns the maximum nesting depth of a JSON document.
+    /// #[php_function]
+    /// pub fn json_depth(json: &Zval) -> PhpResult<i64> {
+    ///     // JSON documents must be valid UTF-8, so other strings are rejected up front.
+    ///     let json = json.string_strict()?;
+    ///     let (mut depth, mut max) = (0, 0);
+    ///     for c in json.chars() {
+    ///         match c {
+    ///             '[' | '{' => depth += 1,
+    ///             ']' | '}' => depth -= 1,
+    ///             _ => continue,
+    ///         }
+    ///         max = max.max(depth);
+    ///     }
+    ///     Ok(max)
+    /// }
+    /// ```
+    pub fn string_strict(&self) -> Result<String> {
+        let str = self
+            .zend_str()
+            .ok_or(Error::ZvalTypeMismatch(self.get_type()?, DataType::String))?;
+
+        str.as_str()
+            .map(|str| str.to_string())
+            .map_err(|e| Error::InvalidUtf8(e.valid_up_to()))
+    }
+
+    /// Returns a copy of the value of the zval if it is a string, replacing any sequences of bytes
+    /// which are not valid UTF-8 with the replacement character `U+FFFD`. Unlike
+    /// [`string()`](#method.string), other types are not converted into a string.
+    pub fn string_lossy(&self) -> Option<String> {
+        self.zend_str()
+            .map(|str| String::from_utf8_lossy(str.as_bytes()).into_owned())
+    }
+
+    /// Returns a borrow of the Zend string contained in the zval if it is a string, without
+    /// copying it. Unlike [`str()`](#method.str), the string does not have to be valid UTF-8, as
+    /// the contents can be read as bytes through [`ZendStr::as_bytes()`].
+    pub fn zend_str(&self) -> Option<&ZendStr> {
+        let zv = self.dereference();
+        if zv.is_string() {
+            // SAFETY: Type is string therefore the pointer is a valid Zend string.
+            unsafe { zv.value.str_.as_ref() }
         } else {
             None
         }
@@ -126,9 +199,10 @@ impl Zval {
     ///
     /// [`pack`]: https://www.php.net/manual/en/function.pack.php
     pub fn binary<T: Pack>(&self) -> Option<Vec<T>> {
-        if self.is_string() {
+        let zv = self.dereference();
+        if zv.is_string() {
             // SAFETY: Type is string therefore we are able to take a reference.
-            Some(T::unpack_into(unsafe { self.value.str_.as_ref() }?))
+            Some(T::unpack_into(unsafe { zv.value.str_.as_ref() }?))
         } else {
             None
         }
@@ -138,26 +212,68 @@ impl Zval {
     pub fn resource(&self) -> Option<*mut zend_resource> {
         // TODO: Can we improve this function? I haven't done much research into
         // resources so I don't know if this is the optimal way to return this.
-        if self.is_resource() {
-            Some(unsafe { self.value.res })
+        let zv = self.dereference();
+        if zv.is_resource() {
+            Some(unsafe { zv.value.res })
         } else {
             None
         }
     }
 
     /// Returns the value of the zval if it is an array.
+    ///
+    /// The returned hash table borrows the array from the zval, and will not be freed when it goes
+    /// out of scope. If the zval is a reference to an array, the reference is followed and the
+    /// referenced array is returned.
     pub fn array(&self) -> Option<ZendHashTable> {
-        if self.is_array() {
-            unsafe { ZendHashTable::from_ptr(self.value.arr, false) }.ok()
+        let zv = self.dereference();
+        if zv.is_array() {
+            unsafe { ZendHashTable::from_ptr(zv.value.arr, false) }.ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable borrow of the value of the zval if it is an array.
+    ///
+    /// Like [`array()`](#method.array), references to arrays are followed. The hash table is not
+    /// freed when it goes out of scope, so any changes made to it are reflected in the zval.
+    ///
+    /// If the array is shared with other zvals, it is first separated by duplicating it, so that
+    /// modifications do not affect the other zvals.
+    pub fn array_mut(&mut self) -> Option<ZendHashTable> {
+        let zv = self.dereference_mut();
+        if zv.is_array() {
+            zv.separate_array();
+            unsafe { ZendHashTable::from_ptr(zv.value.arr, false) }.ok()
         } else {
             None
         }
     }
 
+    /// Separates the array contained in the zval from any other zvals that share it, by
+    /// duplicating the array if its reference count is greater than one.
+    /// Translation of the `SEPARATE_ARRAY` macro from zend_types.h.
+    fn separate_array(&mut self) {
+        // SAFETY: The zval has been checked to contain an array by the caller.
+        let arr = unsafe { &mut *self.value.arr };
+
+        if arr.gc.refcount > 1 {
+            self.u1.type_info = ZvalTypeFlags::ArrayEx.bits();
+            self.value.arr = unsafe { zend_array_dup(arr) };
+
+            // Immutable arrays are not reference counted.
+            if unsafe { arr.gc.u.type_info } & GC_IMMUTABLE == 0 {
+                arr.gc.refcount -= 1;
+            }
+        }
+    }
+
     /// Returns the value of the zval if it is an object.
     pub fn object(&self) -> Option<&mut ZendObject> {
-        if self.is_object() {
-            unsafe { self.value.obj.as_mut() }
+        let zv = self.dereference();
+        if zv.is_object() {
+            unsafe { zv.value.obj.as_mut() }
         } else {
             None
         }
@@ -172,6 +288,27 @@ impl Zval {
         }
     }
 
+    /// Returns the zval which the zval refers to if it is a reference, otherwise returns the zval
+    /// itself. References in PHP cannot refer to other references, so the returned zval is never a
+    /// reference.
+    pub fn dereference(&self) -> &Zval {
+        match self.reference() {
+            Some(zv) => &*zv,
+            None => self,
+        }
+    }
+
+    /// Returns a mutable borrow of the zval which the zval refers to if it is a reference,
+    /// otherwise returns the zval itself. See [`dereference()`](#method.dereference).
+    pub fn dereference_mut(&mut self) -> &mut Zval {
+        if self.is_reference() {
+            // SAFETY: Type is reference therefore the pointer is a valid Zend reference.
+            unsafe { &mut (*self.value.ref_).val }
+        } else {
+            self
+        }
+    }
+
     /// Returns the value of the zval if it is callable.
     pub fn callable(&self) -> Option<Callable> {
         // The Zval is checked if it is callable in the `new` function.
@@ -179,9 +316,9 @@ impl Zval {
     }
 
     /// Attempts to call the zval as a callable with a list of arguments to pass to the function.
-    /// Note that a thrown exception inside the callable is not detectable, therefore you should
-    /// check if the return value is valid rather than unwrapping. Returns a result containing the
-    /// return value of the function, or an error.
+    /// Returns a result containing the return value of the function, or an error. See
+    /// [`Callable::try_call`](crate::php::types::callable::Callable::try_call) for how thrown
+    /// exceptions are handled.
     ///
     /// You should not call this function directly, rather through the [`call_user_func`] macro.
     ///
@@ -192,29 +329,53 @@ impl Zval {
         self.callable().ok_or(Error::Callable)?.try_call(params)
     }
 
-    /// Returns the type of the Zval.
+    /// Returns the type of the Zval. Unlike the `is_*` predicates, references are not followed,
+    /// and [`DataType::Reference`] is returned for a reference.
     pub fn get_type(&self) -> Result<DataType> {
-        DataType::try_from(unsafe { self.u1.v.type_ } as u32)
+        DataType::try_from(self.type_tag())
+    }
+
+    /// Returns the type tag of the zval, without following references.
+    fn type_tag(&self) -> u32 {
+        unsafe { self.u1.v.type_ as u32 }
+    }
+
+    /// Returns true if the zval, or the zval it refers to if it is a reference, has the given
+    /// type.
+    fn is_type(&self, ty: DataType) -> bool {
+        self.dereference().type_tag() == ty.as_u32()
     }
 
     /// Returns true if the zval is a long, false otherwise.
     pub fn is_long(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Long.as_u32() }
+        self.is_type(DataType::Long)
     }
 
     /// Returns true if the zval is null, false otherwise.
+    ///
+    /// An undefined zval is not null, see [`is_undef()`](#method.is_undef).
     pub fn is_null(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Null.as_u32() }
+        self.is_type(DataType::Null)
+    }
+
+    /// Returns true if the zval is undefined, false otherwise.
+    ///
+    /// `IS_UNDEF` is distinct from `IS_NULL`: it marks a zval which has never been given a value,
+    /// such as an unset variable, an uninitialized typed property or an empty hash table bucket.
+    /// PHP code never sees an undefined value directly. Reading it either emits a warning and
+    /// yields null or, for typed properties, throws an [`Error`](https://www.php.net/manual/en/class.error.php).
+    pub fn is_undef(&self) -> bool {
+        self.is_type(DataType::Undef)
     }
 
     /// Returns true if the zval is true, false otherwise.
     pub fn is_true(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::True.as_u32() }
+        self.is_type(DataType::True)
     }
 
     /// Returns true if the zval is false, false otherwise.
     pub fn is_false(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::False.as_u32() }
+        self.is_type(DataType::False)
     }
 
     /// Returns true if the zval is a bool, false otherwise.
@@ -224,32 +385,44 @@ impl Zval {
 
     /// Returns true if the zval is a double, false otherwise.
     pub fn is_double(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Double.as_u32() }
+        self.is_type(DataType::Double)
     }
 
     /// Returns true if the zval is a string, false otherwise.
     pub fn is_string(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::String.as_u32() }
+        self.is_type(DataType::String)
     }
 
     /// Returns true if the zval is a resource, false otherwise.
     pub fn is_resource(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Resource.as_u32() }
+        self.is_type(DataType::Resource)
     }
 
     /// Returns true if the zval is an array, false otherwise.
     pub fn is_array(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Array.as_u32() }
+        self.is_type(DataType::Array)
     }
 
     /// Returns true if the zval is an object, false otherwise.
     pub fn is_object(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Object(None).as_u32() }
+        self.is_type(DataType::Object(None))
     }
 
-    /// Returns true if the zval is a reference, false otherwise.
+    /// Returns true if the zval is a reference, false otherwise. Unlike the other predicates,
+    /// which check the type of the referenced zval, references are not followed.
     pub fn is_reference(&self) -> bool {
-        unsafe { self.u1.v.type_ as u32 == DataType::Reference.as_u32() }
+        self.type_tag() == DataType::Reference.as_u32()
+    }
+
+    /// Returns the value of the zval converted into a bool, in the same way as a `(bool)` cast or an
+    /// `if` condition in PHP.
+    ///
+    /// `false`, `null`, `0`, `0.0`, `-0.0`, the empty string, the string `"0"` and empty arrays
+    /// are falsy. Every other value is truthy, including the string `"0.0"`, `NAN`, resources and
+    /// all objects apart from internal objects which override the cast, such as `SimpleXMLElement`.
+    pub fn is_truthy(&self) -> bool {
+        let ptr: *const Self = self;
+        unsafe { zend_is_true(ptr as *mut Self) != 0 }
     }
 
     /// Returns true if the zval is callable, false otherwise.
@@ -271,6 +444,22 @@ impl Zval {
         Ok(())
     }
 
+    /// Sets the value of the zval as a string containing the given bytes, which do not need to be
+    /// valid UTF-8. The length of the string is taken from the slice, so the bytes may contain
+    /// NUL bytes and do not need to be NUL-terminated.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The value to set the zval as.
+    /// * `persistent` - Whether the string should persist between requests.
+    pub fn set_bytes(&mut self, val: &[u8], persistent: bool) {
+        // The string is initialized with a copy of the bytes.
+        self.value.str_ = unsafe {
+            ext_php_rs_zend_string_init(val.as_ptr() as *const _, val.len() as _, persistent)
+        };
+        self.u1.type_info = ZvalTypeFlags::StringEx.bits();
+    }
+
     /// Sets the value of the zval as a binary string, which is represented in Rust as a vector.
     ///
     /// # Parameters
@@ -296,6 +485,10 @@ impl Zval {
 
     /// Sets the value of the zval as a long.
     ///
+    /// Only types which always fit into a [`ZendLong`] are accepted, which depends on the
+    /// platform: `zend_long` is 64 bits wide on 64-bit platforms and 32 bits wide on 32-bit
+    /// platforms. A value such as an [`i64`] therefore compiles on 64-bit builds only. Use [`try_set_long()`](#method.try_set_long) to set values which may not fit.
+    ///
     /// # Parameters
     ///
     /// * `val` - The value to set the zval as.
@@ -304,6 +497,22 @@ impl Zval {
         self.u1.type_info = ZvalTypeFlags::Long.bits();
     }
 
+    /// Attempts to set the value of the zval as a long, for values which may not fit into a
+    /// [`ZendLong`] on every platform, i.e. an [`i64`] on a 32-bit build or a [`u64`] above
+    /// [`i64::MAX`].
+    ///
+    /// Returns [`Error::IntegerOverflow`] if the value does not fit, in which case the zval is
+    /// left unchanged. Values are never truncated or saturated.
+    ///
+    /// # Parameters
+    ///
+    /// * `val` - The value to set the zval as.
+    pub fn try_set_long<T: TryInto<ZendLong>>(&mut self, val: T) -> Result<()> {
+        let val = val.try_into().map_err(|_| Error::IntegerOverflow)?;
+        self.set_long(val);
+        Ok(())
+    }
+
     /// Sets the value of the zval as a double.
     ///
     /// # Parameters
@@ -327,9 +536,9 @@ impl Zval {
         };
     }
 
-    /// Sets the value of the zval as null.
-    ///
-    /// This is the default of a zval.
+    /// Sets the value of the zval as null, i.e. an explicit PHP `null`, which is what a function
+    /// returns when it does not return a value. This is not the same as leaving the zval
+    /// undefined, see [`is_undef()`](#method.is_undef).
     pub fn set_null(&mut self) {
         self.u1.type_info = ZvalTypeFlags::Null.bits();
     }
@@ -355,14 +564,106 @@ impl Zval {
         self.value.obj = (val as *const ZendObject) as *mut ZendObject;
     }
 
-    /// Sets the value of the zval as an array. Returns nothng in a result on success.
+    /// Sets the value of the zval as a new instance of the given class, returning a mutable
+    /// reference to the new object so that its properties can be populated. The properties of
+    /// the object are initialized to their default values. Any previous value of the zval is
+    /// released.
+    ///
+    /// Note that the constructor of the class is **not** called. Classes registered from Rust
+    /// are initialized with the [`Default`] implementation of the Rust struct. To return an
+    /// instance of a Rust struct with specific values, return the struct itself, which is
+    /// converted through [`IntoZval`].
+    ///
+    /// Returns an error if the class cannot be instantiated, i.e. it is abstract or an interface,
+    /// in which case PHP will also have thrown an `Error`.
+    ///
+    /// # Parameters
+    ///
+    /// * `ce` - The class to create an instance of.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{class::ClassEntry, execution_data::ExecutionData, types::zval::Zval};
+    ///
+    /// pub extern "C" fn make_point(_: &mut ExecutionData, retval: &mut Zval) {
+    ///     let ce = ClassEntry::try_find("Point").unwrap();
+    ///     let point = retval.init_object(ce).unwrap();
+    ///     point.set_property("x", 1).unwrap();
+    ///     point.set_property("y", 2).unwrap();
+    /// }
+    /// ```
+    pub fn init_object(&mut self, ce: &ClassEntry) -> Result<&mut ZendObject> {
+        *self = Zval::new();
+
+        let result = unsafe { object_init_ex(self, (ce as *const ClassEntry) as *mut ClassEntry) };
+
+        if result == ZendResult::Success.bits() {
+            self.object().ok_or(Error::InvalidPointer)
+        } else {
+            Err(Error::Instantiation(ce.flags()))
+        }
+    }
+
+    /// Sets the value of the zval as an array. Accepts any value which can be converted into a
+    /// [`ZendHashTable`], such as a [`Vec`] (creating a list) or a [`HashMap`]. The zval takes
+    /// ownership of the resulting hash table. Returns nothing in a result on success.
     ///
     /// # Parameters
     ///
     /// * `val` - The value to set the zval as.
-    pub fn set_array(&mut self, val: ZendHashTable) {
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{execution_data::ExecutionData, types::zval::Zval};
+    ///
+    /// pub extern "C" fn example_fn(_: &mut ExecutionData, retval: &mut Zval) {
+    ///     // [1, 2, 3]
+    ///     let _ = retval.set_array(vec![1, 2, 3]);
+    /// }
+    /// ```
+    pub fn set_array<'a, T>(&mut self, val: T) -> Result<()>
+    where
+        T: TryInto<ZendHashTable<'a>>,
+        Error: From<T::Error>,
+    {
+        let ht = val.try_into()?;
         self.u1.type_info = ZvalTypeFlags::ArrayEx.bits();
-        self.value.arr = val.into_ptr();
+        self.value.arr = ht.into_ptr();
+        Ok(())
+    }
+
+    /// Sets the value of the zval as an associative array, built from a list of key-value pairs.
+    /// The pairs are inserted in order. Returns nothing in a result on success.
+    ///
+    /// # Parameters
+    ///
+    /// * `pairs` - The key-value pairs to insert into the array.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ext_php_rs::php::{execution_data::ExecutionData, types::zval::Zval};
+    ///
+    /// pub extern "C" fn example_fn(_: &mut ExecutionData, retval: &mut Zval) {
+    ///     // ['hello' => 1, 'world' => 2]
+    ///     let _ = retval.set_assoc_array(vec![("hello", 1), ("world", 2)]);
+    /// }
+    /// ```
+    pub fn set_assoc_array<I, K, V>(&mut self, pairs: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: IntoZval,
+    {
+        let mut ht = ZendHashTable::new();
+
+        for (key, val) in pairs.into_iter() {
+            ht.insert(key.as_ref(), val)?;
+        }
+
+        self.set_array(ht)
     }
 
     /// Used to drop the Zval but keep the value of the zval intact.
@@ -373,47 +674,279 @@ impl Zval {
     pub(crate) fn release(mut self) {
         self.u1.type_info = ZvalTypeFlags::Null.bits();
     }
+
+    /// Creates a copy of the zval which points to the same value, following the semantics of
+    /// `ZVAL_COPY`. Scalars are copied by value, while strings, arrays, objects and references
+    /// are shared with the original zval, incrementing their reference count. Interned strings
+    /// and immutable arrays are not reference counted, and are only copied by pointer.
+    ///
+    /// This allows a value borrowed from PHP, i.e. a function argument, to be kept after the
+    /// function returns without being freed by PHP in the meantime. Strings are released when the
+    /// copy is dropped. Arrays and objects are not released when a zval is dropped, so they are
+    /// kept alive until the end of the request. As the value is shared, modifying the copy
+    /// modifies the original value as well.
+    ///
+    /// Values are allocated for the current request and are freed by PHP at the end of the
+    /// request regardless of their reference count, so a cached copy must not outlive the
+    /// request, and should be dropped in the request shutdown function.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::cell::RefCell;
+    ///
+    /// use ext_php_rs::{php::types::zval::Zval, php_function};
+    ///
+    /// thread_local! {
+    ///     static LAST: RefCell<Option<Zval>> = RefCell::new(None);
+    /// }
+    ///
+    /// #[php_function]
+    /// pub fn remember(value: &Zval) {
+    ///     LAST.with(|last| last.borrow_mut().replace(value.shallow_clone()));
+    /// }
+    ///
+    /// #[php_function]
+    /// pub fn recall() -> Option<Zval> {
+    ///     LAST.with(|last| last.borrow_mut().take())
+    /// }
+    ///
+    /// pub extern "C" fn request_shutdown(_type: i32, _module_number: i32) -> i32 {
+    ///     LAST.with(|last| last.borrow_mut().take());
+    ///     0
+    /// }
+    /// ```
+    pub fn shallow_clone(&self) -> Self {
+        // SAFETY: The zval is plain data. The reference count is incremented below, so the
+        // value is not freed while either copy is alive.
+        let zv = unsafe { ptr::read(self) };
+
+        // SAFETY: `u1` union is only used for easier bitmasking. It is valid to read from either
+        // of the variants. Reference counted values always point to a valid `zend_refcounted`.
+        unsafe {
+            if ZvalTypeFlags::from_bits_truncate(self.u1.type_info)
+                .contains(ZvalTypeFlags::RefCounted)
+            {
+                (*self.value.counted).gc.refcount += 1;
+            }
+        }
+
+        zv
+    }
 }
 
+/// Formats the type of the zval along with a preview of its value, i.e. `Long(5)` or
+/// `Array(2) {0: String("a"), "b": Array(1)}`. The elements of nested arrays are not shown, and
+/// objects are shown by their class name and handle, so formatting never recurses deeply or runs
+/// userland code.
 impl Debug for Zval {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut dbg = f.debug_struct("Zval");
-        let ty = self.get_type();
-        dbg.field("type", &ty);
-
-        if let Ok(ty) = ty {
-            macro_rules! field {
-                ($value: expr) => {
-                    dbg.field("val", &$value)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_debug(f, true)
+    }
+}
+
+/// Formats scalar zvals in the same way as PHP converts them into strings, i.e. `true` becomes
+/// `1` and `null` becomes an empty string. Floats are formatted with the default `precision` of
+/// 14 significant digits.
+///
+/// Arrays are formatted as `Array` and resources as `Resource id #1`, as in PHP. Objects are
+/// formatted as `Object(ClassName)`, as calling `__toString()` could have side effects.
+impl Display for Zval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = match self.get_type() {
+            Ok(ty) => ty,
+            Err(_) => return Ok(()),
+        };
+
+        match ty {
+            DataType::Undef | DataType::Null | DataType::False => Ok(()),
+            DataType::True => write!(f, "1"),
+            DataType::Bool if self.bool() == Some(true) => write!(f, "1"),
+            DataType::Long => write!(f, "{}", self.long().unwrap_or_default()),
+            DataType::Double => f.write_str(&double_to_string(self.double().unwrap_or_default())),
+            DataType::String => match self.zend_str() {
+                Some(val) => f.write_str(&String::from_utf8_lossy(val.as_bytes())),
+                None => Ok(()),
+            },
+            DataType::Array => write!(f, "Array"),
+            DataType::Object(_) => match self.object() {
+                Some(obj) => write!(f, "Object({})", obj.class_name()),
+                None => Ok(()),
+            },
+            DataType::Resource => match self.resource() {
+                Some(res) => write!(f, "Resource id #{}", unsafe { (*res).handle }),
+                None => Ok(()),
+            },
+            DataType::Reference => match self.reference() {
+                Some(zv) => Display::fmt(zv, f),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Zval {
+    /// Formats the zval for [`Debug`], only showing the elements of an array if `expand` is
+    /// `true`.
+    fn fmt_debug(&self, f: &mut fmt::Formatter<'_>, expand: bool) -> fmt::Result {
+        /// Formats the elements of an array without expanding them.
+        struct Preview<'a>(&'a Zval);
+
+        impl Debug for Preview<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt_debug(f, false)
+            }
+        }
+
+        /// Formats array keys as PHP array literals do, quoting only string keys.
+        struct Key(ArrayKey);
+
+        impl Debug for Key {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.0 {
+                    ArrayKey::Long(key) => write!(f, "{}", key),
+                    ArrayKey::String(key) => write!(f, "{:?}", key),
+                }
+            }
+        }
+
+        let ty = match self.get_type() {
+            Ok(ty) => ty,
+            Err(_) => return write!(f, "Unknown"),
+        };
+
+        match ty {
+            DataType::Undef => write!(f, "Undef"),
+            DataType::Null => write!(f, "Null"),
+            DataType::False | DataType::True | DataType::Bool => f
+                .debug_tuple("Bool")
+                .field(&self.bool().unwrap_or_default())
+                .finish(),
+            DataType::Long => f
+                .debug_tuple("Long")
+                .field(&self.long().unwrap_or_default())
+                .finish(),
+            DataType::Double => f
+                .debug_tuple("Double")
+                .field(&self.double().unwrap_or_default())
+                .finish(),
+            DataType::String => f
+                .debug_tuple("String")
+                .field(&String::from_utf8_lossy(
+                    self.zend_str().map(|s| s.as_bytes()).unwrap_or_default(),
+                ))
+                .finish(),
+            DataType::Array => {
+                let arr = match self.array() {
+                    Some(arr) => arr,
+                    None => return write!(f, "Array"),
                 };
+                write!(f, "Array({})", arr.len())?;
+
+                if expand {
+                    f.write_str(" ")?;
+                    f.debug_map()
+                        .entries(arr.into_iter().map(|(k, v)| (Key(k), Preview(v))))
+                        .finish()?;
+                }
+
+                Ok(())
             }
+            DataType::Object(_) => match self.object() {
+                Some(obj) => write!(f, "Object({}#{})", obj.class_name(), obj.handle),
+                None => write!(f, "Object"),
+            },
+            DataType::Resource => match self.resource() {
+                Some(res) => write!(f, "Resource(#{})", unsafe { (*res).handle }),
+                None => write!(f, "Resource"),
+            },
+            DataType::Reference => match self.reference() {
+                Some(zv) => {
+                    f.write_str("Reference(")?;
+                    zv.fmt_debug(f, expand)?;
+                    f.write_str(")")
+                }
+                None => write!(f, "Reference"),
+            },
+            ty => write!(f, "{}", ty),
+        }
+    }
+}
 
-            match ty {
-                DataType::Undef => field!(Option::<()>::None),
-                DataType::Null => field!(Option::<()>::None),
-                DataType::False => field!(false),
-                DataType::True => field!(true),
-                DataType::Long => field!(self.long()),
-                DataType::Double => field!(self.double()),
-                DataType::String | DataType::Mixed => field!(self.string()),
-                DataType::Array => field!(self.array()),
-                DataType::Object(_) => field!(self.object()),
-                DataType::Resource => field!(self.resource()),
-                DataType::Reference => field!(self.reference()),
-                DataType::Callable => field!(self.string()),
-                DataType::ConstantExpression => field!(Option::<()>::None),
-                DataType::Void => field!(Option::<()>::None),
-                DataType::Bool => field!(self.bool()),
-            };
+/// Converts a float into a string in the same way as PHP, using 14 significant digits. Numbers
+/// with a large or small exponent are written in scientific notation, i.e. `1.0E+25`.
+fn double_to_string(val: f64) -> String {
+    const PRECISION: usize = 14;
+
+    if val.is_nan() {
+        return "NAN".into();
+    } else if val.is_infinite() {
+        return if val > 0.0 { "INF" } else { "-INF" }.into();
+    } else if val == 0.0 {
+        return if val.is_sign_negative() { "-0" } else { "0" }.into();
+    }
+
+    fn trim(num: &str) -> &str {
+        if num.contains('.') {
+            num.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            num
         }
+    }
+
+    // The exponent is taken after rounding, which can carry into the next power of ten.
+    let sci = format!("{:.*e}", PRECISION - 1, val);
+    let (mantissa, exp) = sci.split_at(sci.find('e').unwrap_or(sci.len()));
+    let exp: i32 = exp.trim_start_matches('e').parse().unwrap_or_default();
+
+    if exp < -4 || exp >= PRECISION as i32 {
+        let mantissa = trim(mantissa);
+        format!(
+            "{}{}E{}{}",
+            mantissa,
+            if mantissa.contains('.') { "" } else { ".0" },
+            if exp < 0 { '-' } else { '+' },
+            exp.abs()
+        )
+    } else {
+        let fixed = format!("{:.*}", (PRECISION as i32 - 1 - exp) as usize, val);
+        trim(&fixed).into()
+    }
+}
+
+/// A container for a zval. Either contains a reference to a zval or an owned zval, which is
+/// dropped with the container.
+#[derive(Debug)]
+pub enum OwnedZval<'a> {
+    /// A zval borrowed from elsewhere, i.e. an element of an array.
+    Reference(&'a Zval),
+    /// An owned zval.
+    Owned(Zval),
+}
+
+impl<'a> AsRef<Zval> for OwnedZval<'a> {
+    fn as_ref(&self) -> &Zval {
+        match self {
+            OwnedZval::Reference(zv) => *zv,
+            OwnedZval::Owned(zv) => zv,
+        }
+    }
+}
+
+impl<'a> Deref for OwnedZval<'a> {
+    type Target = Zval;
 
-        dbg.finish()
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
     }
 }
 
 impl Drop for Zval {
     fn drop(&mut self) {
-        if self.is_string() {
+        // The string of a referenced zval is owned by the reference, so references are not
+        // followed here.
+        if self.type_tag() == DataType::String.as_u32() {
             unsafe { ext_php_rs_zend_string_release(self.value.str_) };
         }
     }
@@ -515,8 +1048,7 @@ macro_rules! try_into_zval_int {
 
             fn try_from(val: $type) -> Result<Self> {
                 let mut zv = Self::new();
-                let val: ZendLong = val.try_into().map_err(|_| Error::IntegerOverflow)?;
-                zv.set_long(val);
+                zv.try_set_long(val)?;
                 Ok(zv)
             }
         }
@@ -525,9 +1057,7 @@ macro_rules! try_into_zval_int {
             const TYPE: DataType = DataType::Long;
 
             fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
-                let val: ZendLong = self.try_into().map_err(|_| Error::IntegerOverflow)?;
-                zv.set_long(val);
-                Ok(())
+                zv.try_set_long(self)
             }
         }
     };
@@ -565,6 +1095,18 @@ macro_rules! try_into_zval_str {
 try_into_zval_str!(String);
 try_into_zval_str!(&str);
 
+/// Converts a byte slice into a PHP string, which may contain binary data such as the output of
+/// a hash function. To return an owned [`Vec<u8>`], which is otherwise converted into an array,
+/// wrap it in [`Binary`](super::binary::Binary) or return it as a slice.
+impl IntoZval for &[u8] {
+    const TYPE: DataType = DataType::String;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        zv.set_bytes(self, persistent);
+        Ok(())
+    }
+}
+
 impl IntoZval for () {
     const TYPE: DataType = DataType::Void;
 
@@ -574,6 +1116,15 @@ impl IntoZval for () {
     }
 }
 
+impl IntoZval for Zval {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        *zv = self;
+        Ok(())
+    }
+}
+
 impl<T> IntoZval for Option<T>
 where
     T: IntoZval,
@@ -591,12 +1142,52 @@ where
     }
 }
 
+/// Converts a result into a zval, setting the value of the zval on [`Ok`]. On [`Err`], the error is
+/// converted into a [`PhpException`] and thrown, and the zval is left as `null`, which PHP
+/// discards once the exception is thrown. An [`Error`] is only returned if the value could not be
+/// converted or the exception could not be thrown.
+///
+/// This allows manually written function handlers to return a value or throw an exception in
+/// one call:
+///
+/// ```no_run
+/// use ext_php_rs::php::{
+///     exceptions::PhpException,
+///     execution_data::ExecutionData,
+///     types::zval::{IntoZval, Zval},
+/// };
+///
+/// fn parse_port(input: &str) -> Result<i64, PhpException<'static>> {
+///     input.parse().map_err(|_| "Invalid port number.".into())
+/// }
+///
+/// pub extern "C" fn port(_: &mut ExecutionData, retval: &mut Zval) {
+///     let _ = parse_port("8080").set_zval(retval, false);
+/// }
+/// ```
+impl<'a, T, E> IntoZval for std::result::Result<T, E>
+where
+    T: IntoZval,
+    E: Into<PhpException<'a>>,
+{
+    const TYPE: DataType = T::TYPE;
+
+    fn set_zval(self, zv: &mut Zval, persistent: bool) -> Result<()> {
+        match self {
+            Ok(val) => val.set_zval(zv, persistent),
+            Err(e) => {
+                zv.set_null();
+                e.into().throw()
+            }
+        }
+    }
+}
+
 impl<'a> IntoZval for ZendHashTable<'a> {
     const TYPE: DataType = DataType::Array;
 
     fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
-        zv.set_array(self);
-        Ok(())
+        zv.set_array(self)
     }
 }
 
@@ -607,11 +1198,10 @@ where
     const TYPE: DataType = DataType::Array;
 
     fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
-        let hm = self
+        let hm: ZendHashTable = self
             .try_into()
             .map_err(|_| Error::ZvalConversion(DataType::Array))?;
-        zv.set_array(hm);
-        Ok(())
+        zv.set_array(hm)
     }
 }
 
@@ -623,12 +1213,51 @@ where
     const TYPE: DataType = DataType::Array;
 
     fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
-        let hm = self.try_into()?;
-        zv.set_array(hm);
-        Ok(())
+        zv.set_array(self)
     }
 }
 
+/// Implements [`IntoZval`] and [`FromZval`] for a tuple, converting it to and from a PHP list.
+macro_rules! tuple_zval {
+    ($len: literal; $($t: ident $n: tt),+) => {
+        /// Converts the tuple into a PHP list, with the elements of the tuple at indexes `0..N`.
+        impl<$($t),+> IntoZval for ($($t,)+)
+        where
+            $($t: IntoZval),+
+        {
+            const TYPE: DataType = DataType::Array;
+
+            fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+                let mut ht = ZendHashTable::with_capacity($len);
+                $(ht.push(self.$n)?;)+
+                zv.set_array(ht)
+            }
+        }
+
+        /// Converts a PHP list into the tuple, reading the first `N` values of the array in order.
+        /// Returns [`None`] if the array has less than `N` values, or if any of the values could
+        /// not be converted. Any values after the first `N` are ignored.
+        impl<'a, $($t),+> FromZval<'a> for ($($t,)+)
+        where
+            $($t: FromZval<'a>),+
+        {
+            const TYPE: DataType = DataType::Array;
+
+            fn from_zval(zval: &'a Zval) -> Option<Self> {
+                let mut iter = zval.array()?.into_iter();
+                Some(($($t::from_zval(iter.next()?.1)?,)+))
+            }
+        }
+    };
+}
+
+tuple_zval!(1; A 0);
+tuple_zval!(2; A 0, B 1);
+tuple_zval!(3; A 0, B 1, C 2);
+tuple_zval!(4; A 0, B 1, C 2, D 3);
+tuple_zval!(5; A 0, B 1, C 2, D 3, E 4);
+tuple_zval!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
 /// Allows zvals to be converted into Rust types in a fallible way. Reciprocal of the [`IntoZval`]
 /// trait.
 pub trait FromZval<'a>: Sized {
@@ -643,6 +1272,8 @@ pub trait FromZval<'a>: Sized {
     fn from_zval(zval: &'a Zval) -> Option<Self>;
 }
 
+/// Converts a zval into an optional value. `null` is converted into `Some(None)`, while any value
+/// which could not be converted into `T` returns [`None`].
 impl<'a, T> FromZval<'a> for Option<T>
 where
     T: FromZval<'a>,
@@ -650,10 +1281,21 @@ where
     const TYPE: DataType = T::TYPE;
 
     fn from_zval(zval: &'a Zval) -> Option<Self> {
-        Some(T::from_zval(zval))
+        if zval.is_null() {
+            Some(None)
+        } else {
+            T::from_zval(zval).map(Some)
+        }
     }
 }
 
+/// Implements [`FromZval`], [`TryFrom<Zval>`] and [`TryFrom<&Zval>`] for a type, using the given
+/// zval accessor. The value is converted with [`TryInto`], so integers which do not fit in the
+/// target type are rejected rather than truncated.
+///
+/// Converting from a reference gives more detail on failure than [`FromZval`]:
+/// [`Error::ZvalTypeMismatch`] is returned when the zval has the wrong type, and
+/// [`Error::IntegerOverflow`] when an integer does not fit in the target type.
 macro_rules! try_from_zval {
     ($type: ty, $fn: ident, $dt: ident) => {
         impl FromZval<'_> for $type {
@@ -671,6 +1313,17 @@ macro_rules! try_from_zval {
                 Self::from_zval(&value).ok_or(Error::ZvalConversion(value.get_type()?))
             }
         }
+
+        impl TryFrom<&Zval> for $type {
+            type Error = Error;
+
+            fn try_from(value: &Zval) -> Result<Self> {
+                match value.$fn() {
+                    Some(val) => val.try_into().map_err(|_| Error::IntegerOverflow),
+                    None => Err(Error::ZvalTypeMismatch(value.get_type()?, DataType::$dt)),
+                }
+            }
+        }
     };
 }
 
@@ -699,6 +1352,8 @@ impl FromZval<'_> for f32 {
     }
 }
 
+/// Borrows the contents of a string zval without copying them. The string must be valid UTF-8,
+/// which is checked on each conversion. See [`Zval::str`].
 impl<'a> FromZval<'a> for &'a str {
     const TYPE: DataType = DataType::String;
 
@@ -707,6 +1362,134 @@ impl<'a> FromZval<'a> for &'a str {
     }
 }
 
+/// Borrows the bytes of a string zval without copying or validating them, for strings which may
+/// contain binary data or text in an encoding other than UTF-8.
+impl<'a> FromZval<'a> for &'a [u8] {
+    const TYPE: DataType = DataType::String;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        zval.zend_str().map(|s| s.as_bytes())
+    }
+}
+
+/// Converts a string zval into a path. On Unix, the bytes of the string are used as the path
+/// directly, as paths are not required to be valid UTF-8. On other platforms, the string is
+/// converted into UTF-8 lossily, replacing any invalid sequences with the replacement character.
+impl FromZval<'_> for PathBuf {
+    const TYPE: DataType = DataType::String;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let bytes = zval.zend_str()?.as_bytes();
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            Some(OsStr::from_bytes(bytes).into())
+        }
+
+        #[cfg(not(unix))]
+        {
+            Some(String::from_utf8_lossy(bytes).into_owned().into())
+        }
+    }
+}
+
+impl TryFrom<Zval> for PathBuf {
+    type Error = Error;
+
+    fn try_from(value: Zval) -> Result<Self> {
+        Self::from_zval(&value).ok_or(Error::ZvalConversion(value.get_type()?))
+    }
+}
+
+/// Converts a duration into a float of seconds, the unit used by PHP's own timing functions such
+/// as `microtime(true)`. Durations are precise to the nanosecond up to around 104 days, after
+/// which they are rounded to the precision of a float. The conversion never fails.
+impl IntoZval for Duration {
+    const TYPE: DataType = DataType::Double;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        zv.set_double(self.as_secs_f64());
+        Ok(())
+    }
+}
+
+/// Converts a float (or integer) of seconds into a duration. Negative, infinite and `NaN` values,
+/// and values which are too large to be represented by a [`Duration`], return [`None`].
+impl FromZval<'_> for Duration {
+    const TYPE: DataType = DataType::Double;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let secs = zval
+            .double()
+            .or_else(|| zval.long().map(|val| val as f64))?;
+
+        if secs.is_finite() && secs >= 0.0 && secs < u64::MAX as f64 {
+            Some(Duration::from_secs_f64(secs))
+        } else {
+            None
+        }
+    }
+}
+
+/// Converts a time into a float Unix timestamp, as returned by `microtime(true)`. The time is
+/// truncated to the microsecond, rounding towards the past, so times before the Unix epoch are
+/// negative.
+///
+/// Timestamps are precise to the microsecond until around the year 2255, after which they are
+/// rounded to the precision of a float. Times far in the future are therefore less precise, but
+/// the conversion never overflows or fails.
+impl IntoZval for SystemTime {
+    const TYPE: DataType = DataType::Double;
+
+    fn set_zval(self, zv: &mut Zval, _: bool) -> Result<()> {
+        let micros = match self.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_micros() as f64,
+            Err(before) => {
+                let before = before.duration();
+                let micros = before.as_micros() + (before.subsec_nanos() % 1000 != 0) as u128;
+                -(micros as f64)
+            }
+        };
+
+        zv.set_double(micros / 1_000_000.0);
+        Ok(())
+    }
+}
+
+/// Converts a float (or integer) Unix timestamp into a time, rounding to the nearest microsecond.
+/// Infinite and `NaN` timestamps, and timestamps outside of the range of [`SystemTime`] on the
+/// platform, return [`None`].
+impl FromZval<'_> for SystemTime {
+    const TYPE: DataType = DataType::Double;
+
+    fn from_zval(zval: &Zval) -> Option<Self> {
+        let secs = zval
+            .double()
+            .or_else(|| zval.long().map(|val| val as f64))?;
+        let micros = (secs * 1_000_000.0).round();
+
+        if !micros.is_finite() || micros.abs() >= u64::MAX as f64 {
+            return None;
+        }
+
+        let offset = Duration::from_micros(micros.abs() as u64);
+        if micros < 0.0 {
+            UNIX_EPOCH.checked_sub(offset)
+        } else {
+            UNIX_EPOCH.checked_add(offset)
+        }
+    }
+}
+
+impl<'a> FromZval<'a> for &'a Zval {
+    const TYPE: DataType = DataType::Mixed;
+
+    fn from_zval(zval: &'a Zval) -> Option<Self> {
+        Some(zval)
+    }
+}
+
 impl<'a> FromZval<'a> for ZendHashTable<'a> {
     const TYPE: DataType = DataType::Array;
 
@@ -715,6 +1498,12 @@ impl<'a> FromZval<'a> for ZendHashTable<'a> {
     }
 }
 
+/// Converts a PHP array into a vector, returning [`None`] if any of the values could not be
+/// converted into `T`.
+///
+/// Values are taken in the order they were inserted into the array and the keys are ignored. This
+/// means that an array with string keys mixed in (or non-sequential integer keys) is treated the
+/// same as a list, with each value being pushed onto the vector in order.
 impl<'a, T> FromZval<'a> for Vec<T>
 where
     T: FromZval<'a>,
@@ -780,3 +1569,218 @@ impl<'a> TryFrom<Zval> for Callable<'a> {
         Callable::new_owned(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::TryFrom,
+        mem::MaybeUninit,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{double_to_string, FromZval, IntoZval, Zval};
+    use crate::{
+        bindings::{zend_reference, HashTable},
+        errors::Error,
+        php::{enums::DataType, flags::ZvalTypeFlags},
+    };
+
+    fn long(val: i64) -> Zval {
+        let mut zv = Zval::new();
+        zv.set_long(val);
+        zv
+    }
+
+    #[test]
+    fn test_unsigned_bounds() {
+        assert_eq!(u8::from_zval(&long(0)), Some(0));
+        assert_eq!(u8::from_zval(&long(255)), Some(255));
+        assert_eq!(u8::from_zval(&long(300)), None);
+        assert_eq!(u8::from_zval(&long(-1)), None);
+
+        assert_eq!(u16::from_zval(&long(65535)), Some(65535));
+        assert_eq!(u16::from_zval(&long(65536)), None);
+
+        assert_eq!(u32::from_zval(&long(u32::MAX as i64)), Some(u32::MAX));
+        assert_eq!(u32::from_zval(&long(u32::MAX as i64 + 1)), None);
+
+        assert_eq!(u64::from_zval(&long(i64::MAX)), Some(i64::MAX as u64));
+        assert_eq!(u64::from_zval(&long(-1)), None);
+
+        assert_eq!(usize::from_zval(&long(0)), Some(0));
+        assert_eq!(usize::from_zval(&long(-1)), None);
+    }
+
+    #[test]
+    fn test_signed_bounds() {
+        assert_eq!(i8::from_zval(&long(-128)), Some(-128));
+        assert_eq!(i8::from_zval(&long(127)), Some(127));
+        assert_eq!(i8::from_zval(&long(128)), None);
+        assert_eq!(i8::from_zval(&long(-129)), None);
+
+        assert_eq!(i16::from_zval(&long(i16::MIN as i64)), Some(i16::MIN));
+        assert_eq!(i16::from_zval(&long(i16::MAX as i64 + 1)), None);
+
+        assert_eq!(i32::from_zval(&long(i32::MIN as i64)), Some(i32::MIN));
+        assert_eq!(i32::from_zval(&long(i32::MAX as i64 + 1)), None);
+
+        assert_eq!(i64::from_zval(&long(i64::MIN)), Some(i64::MIN));
+        assert_eq!(isize::from_zval(&long(-1)), Some(-1));
+    }
+
+    #[test]
+    fn test_predicates_follow_references() {
+        let mut reference: zend_reference = unsafe { MaybeUninit::zeroed().assume_init() };
+        reference.val = long(5);
+
+        let mut zv = Zval::new();
+        zv.value.ref_ = &mut reference;
+        zv.u1.type_info = ZvalTypeFlags::ReferenceEx.bits();
+
+        assert!(zv.is_reference());
+        assert!(zv.is_long());
+        assert!(!zv.is_null());
+        assert_eq!(zv.long(), Some(5));
+        assert_eq!(zv.get_type(), Ok(DataType::Reference));
+        assert!(!zv.dereference().is_reference());
+    }
+
+    #[test]
+    fn test_shallow_clone() {
+        let copy = long(5).shallow_clone();
+        assert_eq!(copy.long(), Some(5));
+
+        let mut reference: zend_reference = unsafe { MaybeUninit::zeroed().assume_init() };
+        reference.gc.refcount = 1;
+
+        let mut zv = Zval::new();
+        zv.value.ref_ = &mut reference;
+        zv.u1.type_info = ZvalTypeFlags::ReferenceEx.bits();
+
+        let copy = zv.shallow_clone();
+        assert!(copy.is_reference());
+        assert_eq!(reference.gc.refcount, 2);
+
+        let mut arr: HashTable = unsafe { MaybeUninit::zeroed().assume_init() };
+        arr.gc.refcount = 1;
+
+        let mut zv = Zval::new();
+        zv.value.arr = &mut arr;
+        zv.u1.type_info = ZvalTypeFlags::ArrayEx.bits();
+
+        let copy = zv.shallow_clone();
+        assert!(copy.is_array());
+        assert_eq!(arr.gc.refcount, 2);
+    }
+
+    #[test]
+    fn test_null_and_undef() {
+        let mut zv = long(5);
+        zv.set_null();
+        assert!(zv.is_null());
+        assert!(!zv.is_undef());
+
+        zv.u1.type_info = DataType::Undef.as_u32();
+        assert!(zv.is_undef());
+        assert!(!zv.is_null());
+        assert_eq!(zv.get_type(), Ok(DataType::Undef));
+    }
+
+    #[test]
+    fn test_time() {
+        let zv = Duration::from_millis(1500).into_zval(false).unwrap();
+        assert_eq!(zv.double(), Some(1.5));
+        assert_eq!(Duration::from_zval(&zv), Some(Duration::from_millis(1500)));
+        assert_eq!(Duration::from_zval(&long(2)), Some(Duration::from_secs(2)));
+        assert_eq!(Duration::from_zval(&long(-1)), None);
+
+        let time = UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789);
+        let zv = time.into_zval(false).unwrap();
+        assert_eq!(zv.double(), Some(1_600_000_000.123456));
+        assert_eq!(
+            SystemTime::from_zval(&zv),
+            Some(UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_000))
+        );
+
+        let before = UNIX_EPOCH - Duration::new(1, 500);
+        assert_eq!(before.into_zval(false).unwrap().double(), Some(-1.000001));
+    }
+
+    #[test]
+    fn test_try_from_ref() {
+        assert_eq!(i64::try_from(&long(5)), Ok(5));
+        assert_eq!(u8::try_from(&long(256)), Err(Error::IntegerOverflow));
+
+        let mut zv = Zval::new();
+        zv.set_bool(true);
+        assert_eq!(bool::try_from(&zv), Ok(true));
+        assert_eq!(
+            i64::try_from(&zv),
+            Err(Error::ZvalTypeMismatch(DataType::True, DataType::Long))
+        );
+    }
+
+    #[test]
+    fn test_display_scalars() {
+        let mut zv = Zval::new();
+        assert_eq!(zv.to_string(), "");
+
+        zv.set_bool(true);
+        assert_eq!(zv.to_string(), "1");
+        zv.set_bool(false);
+        assert_eq!(zv.to_string(), "");
+
+        assert_eq!(long(-42).to_string(), "-42");
+
+        zv.set_double(1.5);
+        assert_eq!(zv.to_string(), "1.5");
+    }
+
+    #[test]
+    fn test_debug_scalars() {
+        let mut zv = Zval::new();
+        assert_eq!(format!("{:?}", zv), "Null");
+
+        zv.set_bool(true);
+        assert_eq!(format!("{:?}", zv), "Bool(true)");
+
+        assert_eq!(format!("{:?}", long(5)), "Long(5)");
+    }
+
+    #[test]
+    fn test_double_to_string() {
+        assert_eq!(double_to_string(0.1 + 0.2), "0.3");
+        assert_eq!(double_to_string(1.0), "1");
+        assert_eq!(double_to_string(-0.0), "-0");
+        assert_eq!(double_to_string(1.0 / 3.0), "0.33333333333333");
+        assert_eq!(double_to_string(99999999999999.0), "99999999999999");
+        assert_eq!(double_to_string(1e15), "1.0E+15");
+        assert_eq!(double_to_string(1.5e-7), "1.5E-7");
+        assert_eq!(double_to_string(0.0001), "0.0001");
+        assert_eq!(double_to_string(f64::NAN), "NAN");
+        assert_eq!(double_to_string(f64::NEG_INFINITY), "-INF");
+    }
+
+    #[test]
+    fn test_try_set_long() {
+        let mut zv = long(1);
+
+        assert!(zv.try_set_long(u64::MAX).is_err());
+        assert_eq!(zv.long(), Some(1));
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert!(zv.try_set_long(i64::MAX).is_ok());
+            assert_eq!(zv.long(), Some(i64::MAX));
+        }
+
+        #[cfg(target_pointer_width = "32")]
+        {
+            assert!(matches!(
+                zv.try_set_long(i64::MAX),
+                Err(Error::IntegerOverflow)
+            ));
+            assert_eq!(zv.long(), Some(1));
+        }
+    }
+}
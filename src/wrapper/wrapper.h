@@ -2,6 +2,7 @@
 #include "ext/standard/info.h"
 #include "zend_exceptions.h"
 #include "zend_inheritance.h"
+#include "zend_interfaces.h"
 
 zend_string *ext_php_rs_zend_string_init(const char *str, size_t len, bool persistent);
 void ext_php_rs_zend_string_release(zend_string *zs);